@@ -1,7 +1,10 @@
 use crate::coords::{HexCoord, BoardType};
+use crate::move_tables::MoveTables;
 use crate::pieces::{Piece, PieceType, Color};
+use crate::variants::PawnMovement;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Represents a hexagonal chess board
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +17,27 @@ pub struct Board {
     pub valid_coords: std::collections::HashSet<HexCoord>,
     /// Cell colors for rendering (3 colors for regular hex boards)
     pub cell_colors: HashMap<HexCoord, CellColor>,
+    /// Cells pawns start on, by color; a pawn may advance two cells from its own
+    #[serde(default)]
+    pub pawn_homes: HashMap<HexCoord, Color>,
+    /// Set when the last move was a pawn's double step, until the next move
+    #[serde(default)]
+    pub en_passant: Option<EnPassant>,
+    /// Kings and rooks that may still castle, by their starting cells; a cell drops out once anything moves from or to it
+    #[serde(default)]
+    pub castling_pieces: std::collections::HashSet<HexCoord>,
+    /// Where pawns may step without capturing, from the variant
+    #[serde(default)]
+    pub pawn_movement: PawnMovement,
+}
+
+/// A pawn that just advanced two cells and may be taken en passant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnPassant {
+    /// The cell the pawn passed over, which a capturing pawn moves to
+    pub target: HexCoord,
+    /// Where the pawn now stands, and is removed from when taken
+    pub pawn: HexCoord,
 }
 
 /// Cell colors for hexagonal boards
@@ -28,26 +52,34 @@ impl Board {
     /// Create a new empty board
     pub fn new(board_type: BoardType) -> Self {
         let valid_coords = board_type.valid_coords();
-        let cell_colors = Self::generate_cell_colors(&valid_coords, board_type);
+        let cell_colors = Self::generate_cell_colors(&valid_coords, &board_type);
         
         Self {
             board_type,
             pieces: HashMap::new(),
             valid_coords,
             cell_colors,
+            pawn_homes: HashMap::new(),
+            en_passant: None,
+            castling_pieces: std::collections::HashSet::new(),
+            pawn_movement: PawnMovement::Standard,
         }
     }
 
     /// Generate cell colors for the board
     fn generate_cell_colors(
         coords: &std::collections::HashSet<HexCoord>,
-        board_type: BoardType,
+        board_type: &BoardType,
     ) -> HashMap<HexCoord, CellColor> {
         let mut colors = HashMap::new();
         
         for &coord in coords {
             let color = match board_type {
-                BoardType::Regular { .. } | BoardType::Small => {
+                BoardType::Regular { .. }
+                | BoardType::Small { .. }
+                | BoardType::Shafran
+                | BoardType::DeVasa
+                | BoardType::Custom(_) => {
                     // Regular hex boards use 3 colors arranged along the three bishop diagonals.
                     // Rotate the pattern so it aligns with the visual convention used in Gliński's Chess.
                     let (q, r, _s) = coord.to_cube();
@@ -97,6 +129,11 @@ impl Board {
         self.valid_coords.contains(&coord)
     }
 
+    /// Get the precomputed move geometry shared by every board of this type
+    pub fn move_tables(&self) -> Arc<MoveTables> {
+        MoveTables::for_board_type(&self.board_type)
+    }
+
     /// Get all pieces of a specific color
     pub fn get_pieces_by_color(&self, color: Color) -> Vec<(HexCoord, &Piece)> {
         self.pieces
@@ -106,6 +143,184 @@ impl Board {
             .collect()
     }
 
+    /// Deterministic Zobrist-style hash of the piece placement
+    ///
+    /// Keys are derived from each (cell, piece) pair instead of a random table,
+    /// so the same position hashes identically across builds and platforms.
+    pub fn position_hash(&self) -> u64 {
+        self.pieces
+            .iter()
+            .fold(0, |hash, (&coord, &piece)| hash ^ piece_square_key(coord, piece))
+            ^ self.en_passant.map_or(0, |ep| splitmix64(cell_key(ep.target, 1)))
+            ^ self
+                .castling_pieces
+                .iter()
+                .fold(0, |hash, &coord| hash ^ splitmix64(cell_key(coord, 2)))
+    }
+
+    /// The step a pawn of `color` advances by under this board's pawn movement
+    pub fn pawn_forward(&self, color: Color) -> HexCoord {
+        PieceType::pawn_forward(color, &self.pawn_movement)
+    }
+
+    /// The pawn taken if the piece on `from` moves to `to` en passant
+    pub fn en_passant_capture(&self, from: HexCoord, to: HexCoord) -> Option<HexCoord> {
+        let ep = self.en_passant?;
+        let piece = self.get_piece(from)?;
+        let taken = self.get_piece(ep.pawn)?;
+        (piece.piece_type == PieceType::Pawn && ep.target == to && !self.is_occupied(to) && taken.color != piece.color)
+            .then_some(ep.pawn)
+    }
+
+    /// The rook's (from, to) if moving the king on `from` to `to` castles
+    ///
+    /// The king moves two cells along a line toward an unmoved rook with only
+    /// empty cells between them, and the rook lands on the cell the king crossed.
+    pub fn castling_rook(&self, from: HexCoord, to: HexCoord) -> Option<(HexCoord, HexCoord)> {
+        let king = self.get_piece(from).filter(|piece| piece.piece_type == PieceType::King)?;
+        if !self.castling_pieces.contains(&from) {
+            return None;
+        }
+        let tables = self.move_tables();
+        let ray = tables.orthogonal_rays(from).iter().find(|ray| ray.get(1) == Some(&to))?;
+        let rook_at = ray.iter().position(|&cell| self.is_occupied(cell)).filter(|&index| index >= 2)?;
+        let rook = self.get_piece(ray[rook_at])?;
+        (rook.piece_type == PieceType::Rook && rook.color == king.color && self.castling_pieces.contains(&ray[rook_at]))
+            .then_some((ray[rook_at], ray[0]))
+    }
+
+    /// Castling moves for the king on `from`, which may not castle out of or through an attacked cell
+    pub fn castling_moves(&self, from: HexCoord) -> Vec<HexCoord> {
+        let Some(king) = self.get_piece(from) else {
+            return Vec::new();
+        };
+        let opponent = match king.color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        if !self.castling_pieces.contains(&from) || self.is_attacked(from, opponent) {
+            return Vec::new();
+        }
+        self.move_tables()
+            .orthogonal_rays(from)
+            .iter()
+            .filter_map(|ray| ray.get(1).copied())
+            .filter(|&to| {
+                self.castling_rook(from, to)
+                    .is_some_and(|(_, crossed)| !self.is_attacked(crossed, opponent))
+            })
+            .collect()
+    }
+
+    /// Whether any piece of `by` attacks a cell
+    ///
+    /// Looks outward from the cell for each way of attacking it, rather than
+    /// generating every enemy piece's moves.
+    pub fn is_attacked(&self, coord: HexCoord, by: Color) -> bool {
+        self.attacking_pieces(&self.move_tables(), coord, by).next().is_some()
+    }
+
+    /// Pieces giving check to the king of `color`, sorted
+    pub fn checkers(&self, color: Color) -> Vec<HexCoord> {
+        let Some(king) = self.get_king(color) else {
+            return Vec::new();
+        };
+        let opponent = match color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        let mut checkers: Vec<HexCoord> = self.attacking_pieces(&self.move_tables(), king, opponent).collect();
+        checkers.sort_by_key(|c| (c.q, c.r));
+        checkers
+    }
+
+    /// Pieces of `color` that can't leave the line between their king and an
+    /// enemy slider without exposing the king, sorted
+    pub fn pinned_pieces(&self, color: Color) -> Vec<HexCoord> {
+        let Some(king) = self.get_king(color) else {
+            return Vec::new();
+        };
+        let tables = self.move_tables();
+        let lines = [
+            (tables.orthogonal_rays(king), [PieceType::Rook, PieceType::Queen, PieceType::Chancellor]),
+            (tables.diagonal_rays(king), [PieceType::Bishop, PieceType::Queen, PieceType::Archbishop]),
+        ];
+
+        let mut pinned = Vec::new();
+        for (rays, sliders) in lines {
+            for ray in rays {
+                let mut occupied = ray.iter().filter_map(|&cell| self.get_piece(cell).map(|piece| (cell, piece)));
+                if let (Some((cell, own)), Some((_, enemy))) = (occupied.next(), occupied.next()) {
+                    if own.color == color && enemy.color != color && sliders.contains(&enemy.piece_type) {
+                        pinned.push(cell);
+                    }
+                }
+            }
+        }
+        pinned.sort_by_key(|c| (c.q, c.r));
+        pinned
+    }
+
+    /// Pieces of `by` attacking a cell, found by looking outward from it
+    fn attacking_pieces<'a>(&'a self, tables: &'a MoveTables, coord: HexCoord, by: Color) -> impl Iterator<Item = HexCoord> + 'a {
+        let holds = move |cell: HexCoord, types: &[PieceType]| {
+            self.get_piece(cell).is_some_and(|piece| piece.color == by && types.contains(&piece.piece_type))
+        };
+        let first_on = |ray: &Vec<HexCoord>| ray.iter().copied().find(|&cell| self.is_occupied(cell));
+
+        let pawns = PieceType::pawn_captures(by, &self.pawn_movement)
+            .into_iter()
+            .map(move |direction| HexCoord::new(coord.q - direction.q, coord.r - direction.r))
+            .filter(move |&cell| holds(cell, &[PieceType::Pawn]));
+        let kings = tables.king_steps(coord).iter().copied().filter(move |&cell| holds(cell, &[PieceType::King]));
+        let knights = tables
+            .knight_leaps(coord)
+            .iter()
+            .copied()
+            .filter(move |&cell| holds(cell, &[PieceType::Knight, PieceType::Chancellor, PieceType::Archbishop]));
+        let rooks = tables
+            .orthogonal_rays(coord)
+            .iter()
+            .filter_map(first_on)
+            .filter(move |&cell| holds(cell, &[PieceType::Rook, PieceType::Queen, PieceType::Chancellor]));
+        let bishops = tables
+            .diagonal_rays(coord)
+            .iter()
+            .filter_map(first_on)
+            .filter(move |&cell| holds(cell, &[PieceType::Bishop, PieceType::Queen, PieceType::Archbishop]));
+        pawns.chain(kings).chain(knights).chain(rooks).chain(bishops)
+    }
+
+    /// Every cell the pieces of `color` attack, empty or not
+    pub fn attacked_squares(&self, color: Color) -> std::collections::HashSet<HexCoord> {
+        self.pieces
+            .iter()
+            .filter(|(_, piece)| piece.color == color)
+            .flat_map(|(&from, piece)| piece.piece_type.attacks(from, piece.color, self))
+            .collect()
+    }
+
+    /// Pieces attacking a cell, as (white, black) coordinate lists
+    ///
+    /// Pieces of the same color as the cell's occupant are its defenders.
+    pub fn attackers(&self, coord: HexCoord) -> (Vec<HexCoord>, Vec<HexCoord>) {
+        let mut white = Vec::new();
+        let mut black = Vec::new();
+
+        for (&from, piece) in &self.pieces {
+            if from != coord && piece.piece_type.attacks(from, piece.color, self).contains(&coord) {
+                match piece.color {
+                    Color::White => white.push(from),
+                    Color::Black => black.push(from),
+                }
+            }
+        }
+
+        white.sort_by_key(|c| (c.q, c.r));
+        black.sort_by_key(|c| (c.q, c.r));
+        (white, black)
+    }
+
     /// Get the king of a specific color
     pub fn get_king(&self, color: Color) -> Option<HexCoord> {
         self.pieces
@@ -120,13 +335,32 @@ impl Board {
             return Err(BoardError::InvalidCoordinate);
         }
         
+        let passed = self.en_passant_capture(from, to);
+        let castling = self.castling_rook(from, to);
         let piece = self.pieces.remove(&from)
             .ok_or(BoardError::NoPieceAtCoordinate)?;
         
-        // If there's a piece at the destination, it's captured
-        let captured = self.pieces.insert(to, piece);
+        // If there's a piece at the destination, it's captured; en passant takes the pawn beside it
+        let captured = match passed {
+            Some(pawn) => {
+                self.pieces.insert(to, piece);
+                self.pieces.remove(&pawn)
+            }
+            None => self.pieces.insert(to, piece),
+        };
+        if let Some((rook_from, rook_to)) = castling {
+            if let Some(rook) = self.pieces.remove(&rook_from) {
+                self.pieces.insert(rook_to, rook);
+            }
+            self.castling_pieces.remove(&rook_from);
+        }
+        self.castling_pieces.remove(&from);
+        self.castling_pieces.remove(&to);
+        let forward = self.pawn_forward(piece.color);
+        self.en_passant = (piece.piece_type == PieceType::Pawn && to == from + forward + forward)
+            .then(|| EnPassant { target: from + forward, pawn: to });
         
-        Ok(captured.unwrap_or_else(|| Piece {
+        Ok(captured.unwrap_or(Piece {
             piece_type: PieceType::Pawn, // Dummy piece for captures
             color: Color::White,
         }))
@@ -150,6 +384,9 @@ impl Board {
                 moves.push(target);
             }
         }
+        if piece.piece_type == PieceType::King {
+            moves.extend(self.castling_moves(coord));
+        }
         
         moves
     }
@@ -184,6 +421,29 @@ impl Board {
     }
 }
 
+/// Hash key for one piece standing on one cell
+fn piece_square_key(coord: HexCoord, piece: Piece) -> u64 {
+    let q = (coord.q + 128) as u64;
+    let r = (coord.r + 128) as u64;
+    splitmix64(q << 24 | r << 12 | (piece.piece_type as u64) << 1 | piece.color as u64)
+}
+
+/// Hash input for a cell marked by en passant (tag 1) or castling rights (tag 2),
+/// kept apart from the piece keys
+fn cell_key(coord: HexCoord, tag: u64) -> u64 {
+    let q = (coord.q + 128) as u64;
+    let r = (coord.r + 128) as u64;
+    tag << 40 | q << 24 | r << 12
+}
+
+/// SplitMix64 finalizer, used to spread small integers over 64 bits
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BoardError {
     #[error("Invalid coordinate for this board")]
@@ -203,7 +463,25 @@ mod tests {
     fn test_board_creation() {
         let board = Board::new(BoardType::Regular { radius: 2 });
         assert_eq!(board.pieces.len(), 0);
-        assert!(board.valid_coords.len() > 0);
+        assert!(!board.valid_coords.is_empty());
+    }
+
+    #[test]
+    fn test_custom_board() {
+        // A radius-2 hexagon with its middle cell cut out
+        let cells = BoardType::Regular { radius: 2 }
+            .valid_coords()
+            .into_iter()
+            .filter(|&coord| coord != HexCoord::new(0, 0));
+        let mut board = Board::new(BoardType::custom(cells));
+        assert_eq!(board.cell_colors.len(), 18);
+        assert!(board.place_piece(HexCoord::new(0, 0), Piece::new(PieceType::Rook, Color::White)).is_err());
+
+        // Lines end at the hole as they do at the edge
+        board.place_piece(HexCoord::new(0, -2), Piece::new(PieceType::Rook, Color::White)).unwrap();
+        let moves = board.get_valid_moves(HexCoord::new(0, -2));
+        assert!(moves.contains(&HexCoord::new(0, -1)));
+        assert!(!moves.contains(&HexCoord::new(0, 1)));
     }
 
     #[test]
@@ -215,10 +493,100 @@ mod tests {
         };
         
         let coord = HexCoord::new(0, 0);
-        assert!(board.place_piece(coord, piece.clone()).is_ok());
+        assert!(board.place_piece(coord, piece).is_ok());
         assert_eq!(board.get_piece(coord), Some(&piece));
     }
 
+    #[test]
+    fn test_position_hash_depends_on_placement() {
+        let mut board = Board::new(BoardType::Regular { radius: 2 });
+        let empty_hash = board.position_hash();
+
+        board.place_piece(HexCoord::new(0, 0), Piece::new(PieceType::King, Color::White)).unwrap();
+        let king_hash = board.position_hash();
+        assert_ne!(king_hash, empty_hash);
+
+        board.move_piece(HexCoord::new(0, 0), HexCoord::new(1, 0)).unwrap();
+        assert_ne!(board.position_hash(), king_hash);
+
+        board.move_piece(HexCoord::new(1, 0), HexCoord::new(0, 0)).unwrap();
+        assert_eq!(board.position_hash(), king_hash);
+    }
+
+    #[test]
+    fn test_attackers_and_defenders() {
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        let target = HexCoord::new(0, 0);
+        board.place_piece(target, Piece::new(PieceType::Knight, Color::Black)).unwrap();
+        // White: a rook on the file, a pawn capturing forward-right, a bishop blocked by its own pawn
+        board.place_piece(HexCoord::new(0, -4), Piece::new(PieceType::Rook, Color::White)).unwrap();
+        board.place_piece(HexCoord::new(-1, 0), Piece::new(PieceType::Pawn, Color::White)).unwrap();
+        board.place_piece(HexCoord::new(-4, 2), Piece::new(PieceType::Bishop, Color::White)).unwrap();
+        board.place_piece(HexCoord::new(-2, 1), Piece::new(PieceType::Pawn, Color::White)).unwrap();
+        // Black: a knight defending, a pawn standing in front (not attacking)
+        board.place_piece(HexCoord::new(2, 1), Piece::new(PieceType::Knight, Color::Black)).unwrap();
+        board.place_piece(HexCoord::new(0, 1), Piece::new(PieceType::Pawn, Color::Black)).unwrap();
+
+        let (white, black) = board.attackers(target);
+        assert_eq!(white, vec![HexCoord::new(-1, 0), HexCoord::new(0, -4)]);
+        assert_eq!(black, vec![HexCoord::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_attack_map_matches_attackers() {
+        let mut game = crate::game::Game::new(crate::variants::Variants::glinski_capablanca_chess());
+        for seed in 0..4 {
+            game.play_random(seed, 10);
+            let board = &game.board;
+            for color in [Color::White, Color::Black] {
+                let attacked = board.attacked_squares(color);
+                for &coord in &board.valid_coords {
+                    let (white, black) = board.attackers(coord);
+                    let by = match color {
+                        Color::White => &white,
+                        Color::Black => &black,
+                    };
+                    assert_eq!(board.is_attacked(coord, color), !by.is_empty(), "{:?} on {:?}", color, coord);
+                    assert_eq!(attacked.contains(&coord), board.is_attacked(coord, color), "{:?} on {:?}", color, coord);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pins_and_checkers() {
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        let king = HexCoord::new(0, 0);
+        board.place_piece(king, Piece::new(PieceType::King, Color::White)).unwrap();
+        let tables = board.move_tables();
+        let file = &tables.orthogonal_rays(king)[0];
+        let diagonal = &tables.diagonal_rays(king)[0];
+        let other_file = &tables.orthogonal_rays(king)[1];
+
+        // A knight pinned on the file, a bishop on the diagonal it can move along
+        board.place_piece(file[0], Piece::new(PieceType::Knight, Color::White)).unwrap();
+        board.place_piece(file[2], Piece::new(PieceType::Rook, Color::Black)).unwrap();
+        board.place_piece(diagonal[0], Piece::new(PieceType::Bishop, Color::White)).unwrap();
+        board.place_piece(diagonal[1], Piece::new(PieceType::Queen, Color::Black)).unwrap();
+        // Two pieces in the way is no pin, and a bishop can't pin along a file
+        board.place_piece(other_file[0], Piece::new(PieceType::Pawn, Color::White)).unwrap();
+        board.place_piece(other_file[1], Piece::new(PieceType::Bishop, Color::Black)).unwrap();
+        let mut expected = vec![file[0], diagonal[0]];
+        expected.sort_by_key(|c| (c.q, c.r));
+        assert_eq!(board.pinned_pieces(Color::White), expected);
+        assert!(board.checkers(Color::White).is_empty());
+
+        // A knight check alongside the queen once the bishop steps away
+        let knight = tables.knight_leaps(king)[0];
+        board.place_piece(knight, Piece::new(PieceType::Knight, Color::Black)).unwrap();
+        board.remove_piece(diagonal[0]);
+        let mut expected = vec![knight, diagonal[1]];
+        expected.sort_by_key(|c| (c.q, c.r));
+        assert_eq!(board.checkers(Color::White), expected);
+        assert_eq!(board.pinned_pieces(Color::White), vec![file[0]]);
+        assert!(board.checkers(Color::Black).is_empty());
+    }
+
     #[test]
     fn test_invalid_coordinate() {
         let mut board = Board::new(BoardType::Regular { radius: 1 });
@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Axial coordinates for hexagonal grids
 /// q = column, r = row in hex coordinate system
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub struct HexCoord {
     pub q: i32,
     pub r: i32,
@@ -48,11 +49,11 @@ impl HexCoord {
         ]
     }
 
-    /// Calculate distance to another hex coordinate
+    /// Calculate distance to another hex coordinate (number of single steps)
     pub fn distance_to(self, other: HexCoord) -> i32 {
         let (q1, r1, s1) = self.to_cube();
         let (q2, r2, s2) = other.to_cube();
-        (q1 - q2).abs() + (r1 - r2).abs() + (s1 - s2).abs()
+        ((q1 - q2).abs() + (r1 - r2).abs() + (s1 - s2).abs()) / 2
     }
 
     /// Get all coordinates in a line from this point to another
@@ -115,80 +116,32 @@ impl HexCoord {
 /// Files: a b c d e f g h i k l (no j), where f is the vertical spine at q=0
 /// Ranks: 1-11, with White at bottom (ranks 1-6) and Black at top (ranks 7-11)
 pub fn file_rank_to_axial(file: char, rank: u8) -> Option<HexCoord> {
-    let file = file.to_ascii_lowercase();
-    let q = match file {
-        'a' => -5,
-        'b' => -4,
-        'c' => -3,
-        'd' => -2,
-        'e' => -1,
-        'f' => 0,
-        'g' => 1,
-        'h' => 2,
-        'i' => 3,
-        'k' => 4,
-        'l' => 5,
-        _ => return None,
-    };
-
-    if !(1..=11).contains(&rank) {
-        return None;
-    }
-
-    let r = rank as i32 - 6;
-    let s = -q - r;
-
-    if q.abs() <= 5 && r.abs() <= 5 && s.abs() <= 5 {
-        Some(HexCoord::new(q, r))
-    } else {
-        None
-    }
+    BoardType::Regular { radius: 5 }.cell_at(file, rank)
 }
 
 /// Convert axial (q, r) coordinates to Gliński file/rank notation
 /// Reverse lookup from coordinates to file/rank string like "f5", "g1", etc.
 pub fn axial_to_file_rank(q: i32, r: i32) -> Option<String> {
-    // Map q to file character
-    let file = match q {
-        -5 => 'a',
-        -4 => 'b',
-        -3 => 'c',
-        -2 => 'd',
-        -1 => 'e',
-        0 => 'f',
-        1 => 'g',
-        2 => 'h',
-        3 => 'i',
-        4 => 'k',
-        5 => 'l',
-        _ => return None,  // Out of valid range
-    };
-    
-    // Map r to rank
-    let rank = match r {
-        -5 => 1,
-        -4 => 2,
-        -3 => 3,
-        -2 => 4,
-        -1 => 5,
-        0 => 6,
-        1 => 7,
-        2 => 8,
-        3 => 9,
-        4 => 10,
-        5 => 11,
-        _ => return None,  // Out of valid range
-    };
-
-    let rank = rank as u8;
-    // Validate by converting back
-    if file_rank_to_axial(file, rank) == Some(HexCoord::new(q, r)) {
-        Some(format!("{}{}", file, rank))
-    } else {
-        None
-    }
+    BoardType::Regular { radius: 5 }.square_name(HexCoord::new(q, r))
+}
+
+/// Whether a cell is on Shafran's board, which runs from e1 at the bottom to e10 at the top
+fn in_shafran(coord: HexCoord) -> bool {
+    let (q, r, _) = coord.to_cube();
+    q.abs() <= 4 && (-5..=4).contains(&r) && (-5..=4).contains(&(q + r))
 }
 
+/// Whether a cell is on De Vasa's board, a rhombus of nine files by nine ranks
+fn in_de_vasa(coord: HexCoord) -> bool {
+    coord.q.abs() <= 4 && coord.r.abs() <= 4
+}
+
+/// File letters from left to right; as in Gliński's notation there is no j
+const FILE_LETTERS: [char; 25] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'k', 'l', 'm', 'n',
+    'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+
 impl std::ops::Add for HexCoord {
     type Output = Self;
 
@@ -206,21 +159,55 @@ impl std::ops::Sub for HexCoord {
 }
 
 /// Hexagonal board types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BoardType {
     /// Regular hexagon with radius (number of rings)
     Regular { radius: i32 },
-    /// Irregular board with custom shape
+    /// Placeholder with no cells, for variants not laid out yet; irregular shapes use `Custom`
     Irregular,
-    /// Small hexagon (37 cells)
-    Small,
+    /// Small hexagon with radius, e.g. radius 3 (37 cells) for Mini Hexchess
+    Small { radius: i32 },
+    /// Shafran's 70-cell hexagon: 9 files, 6 cells long at the edges and 10 down the middle
+    Shafran,
+    /// De Vasa's 81-cell rhombus: 9 files of 9 cells, each rank running up and to the right
+    DeVasa,
+    /// Any set of cells; build it with `BoardType::custom`
+    Custom(CustomCells),
+}
+
+/// The cells of a custom board, sorted by (q, r) without repeats so the same
+/// shape always makes the same board type
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "Vec<HexCoord>")]
+pub struct CustomCells(Vec<HexCoord>);
+
+impl From<Vec<HexCoord>> for CustomCells {
+    fn from(mut cells: Vec<HexCoord>) -> Self {
+        cells.sort_by_key(|coord| (coord.q, coord.r));
+        cells.dedup();
+        Self(cells)
+    }
+}
+
+/// The cells in (q, r) order
+impl std::ops::Deref for CustomCells {
+    type Target = [HexCoord];
+
+    fn deref(&self) -> &[HexCoord] {
+        &self.0
+    }
 }
 
 impl BoardType {
+    /// A board of exactly these cells
+    pub fn custom(cells: impl IntoIterator<Item = HexCoord>) -> Self {
+        BoardType::Custom(CustomCells::from(cells.into_iter().collect::<Vec<_>>()))
+    }
+
     /// Get all valid coordinates for this board type
-    pub fn valid_coords(self) -> HashSet<HexCoord> {
-        match self {
-            BoardType::Regular { radius } => {
+    pub fn valid_coords(&self) -> HashSet<HexCoord> {
+        match *self {
+            BoardType::Regular { radius } | BoardType::Small { radius } => {
                 let mut coords = HashSet::new();
                 for q in -radius..=radius {
                     for r in -radius..=radius {
@@ -232,31 +219,108 @@ impl BoardType {
                 }
                 coords
             }
-            BoardType::Small => {
-                // Mini Hexchess has 37 cells in a specific pattern
+            BoardType::Irregular => {
+                // Will be defined per variant
+                HashSet::new()
+            }
+            BoardType::Shafran => {
                 let mut coords = HashSet::new();
-                for q in -3..=3 {
-                    for r in -3..=3 {
+                for q in -4..=4 {
+                    for r in -5..=4 {
                         let coord = HexCoord::new(q, r);
-                        if coord.in_hexagon(3) {
+                        if in_shafran(coord) {
                             coords.insert(coord);
                         }
                     }
                 }
                 coords
             }
-            BoardType::Irregular => {
-                // Will be defined per variant
-                HashSet::new()
+            BoardType::DeVasa => {
+                let mut coords = HashSet::new();
+                for q in -4..=4 {
+                    for r in -4..=4 {
+                        coords.insert(HexCoord::new(q, r));
+                    }
+                }
+                coords
             }
+            BoardType::Custom(ref cells) => cells.iter().copied().collect(),
         }
     }
 
+    /// The (q, r) of file `a` and rank 1; irregular boards borrow Gliński's
+    fn label_origin(&self) -> (i32, i32) {
+        match *self {
+            BoardType::Regular { radius } | BoardType::Small { radius } => (-radius, -radius),
+            BoardType::Irregular => (-5, -5),
+            BoardType::Shafran => (-4, -5),
+            BoardType::DeVasa => (-4, -4),
+            // Files start at the leftmost column and ranks at the lowest row
+            BoardType::Custom(ref cells) => (
+                cells.iter().map(|coord| coord.q).min().unwrap_or(0),
+                cells.iter().map(|coord| coord.r).min().unwrap_or(0),
+            ),
+        }
+    }
+
+    /// Whether a cell gets a file and rank name
+    fn is_labelled(&self, coord: HexCoord) -> bool {
+        match *self {
+            BoardType::Regular { radius } | BoardType::Small { radius } => coord.in_hexagon(radius),
+            BoardType::Irregular => coord.in_hexagon(5),
+            BoardType::Shafran => in_shafran(coord),
+            BoardType::DeVasa => in_de_vasa(coord),
+            BoardType::Custom(ref cells) => cells.contains(&coord),
+        }
+    }
+
+    /// Name of a cell on this board: files run left to right from `a` (skipping `j`)
+    /// and ranks upward from 1, so the centre is f6 on Gliński's board and d4 on Mini Hexchess
+    pub fn square_name(&self, coord: HexCoord) -> Option<String> {
+        if !self.is_labelled(coord) {
+            return None;
+        }
+        let (first_file, first_rank) = self.label_origin();
+        let file = FILE_LETTERS.get(usize::try_from(coord.q - first_file).ok()?)?;
+        Some(format!("{}{}", file, coord.r - first_rank + 1))
+    }
+
+    /// The cell a file letter and rank name on this board
+    pub fn cell_at(&self, file: char, rank: u8) -> Option<HexCoord> {
+        let (first_file, first_rank) = self.label_origin();
+        let file = FILE_LETTERS.iter().position(|&letter| letter == file.to_ascii_lowercase())?;
+        let coord = HexCoord::new(file as i32 + first_file, rank as i32 + first_rank - 1);
+        self.is_labelled(coord).then_some(coord)
+    }
+
     /// Get the center coordinate of the board
-    pub fn center(self) -> HexCoord {
-        match self {
-            BoardType::Regular { .. } | BoardType::Small => HexCoord::new(0, 0),
+    pub fn center(&self) -> HexCoord {
+        match *self {
+            BoardType::Regular { .. } | BoardType::Small { .. } => HexCoord::new(0, 0),
             BoardType::Irregular => HexCoord::new(0, 0), // Will be overridden per variant
+            // The middle falls between e5 and e6; take White's side
+            BoardType::Shafran => HexCoord::new(0, -1),
+            BoardType::DeVasa => HexCoord::new(0, 0),
+            // The cell nearest the middle of the shape
+            BoardType::Custom(ref cells) => {
+                let count = cells.len().max(1) as f32;
+                let (sum_x, sum_y) = cells.iter().fold((0.0, 0.0), |(x, y), coord| {
+                    let (cell_x, cell_y) = coord.to_pixel();
+                    (x + cell_x, y + cell_y)
+                });
+                let (mid_x, mid_y) = (sum_x / count, sum_y / count);
+                cells
+                    .iter()
+                    .copied()
+                    .min_by(|a, b| {
+                        let distance = |coord: &HexCoord| {
+                            let (x, y) = coord.to_pixel();
+                            (x - mid_x).powi(2) + (y - mid_y).powi(2)
+                        };
+                        distance(a).total_cmp(&distance(b))
+                    })
+                    .unwrap_or(HexCoord::new(0, 0))
+            }
         }
     }
 }
@@ -278,10 +342,13 @@ mod tests {
     fn test_distance_calculation() {
         let center = HexCoord::new(0, 0);
         let neighbor = HexCoord::new(1, 0);
-        let far = HexCoord::new(2, 1);
+        let far = HexCoord::new(2, -1);
+        let knight_leap = HexCoord::new(2, 1);
         
         assert_eq!(center.distance_to(neighbor), 1);
         assert_eq!(center.distance_to(far), 2);
+        assert_eq!(center.distance_to(knight_leap), 3);
+        assert_eq!(knight_leap.distance_to(center), 3);
     }
 
     #[test]
@@ -306,4 +373,72 @@ mod tests {
         assert!(coords.contains(&HexCoord::new(1, 0)));
         assert!(coords.contains(&HexCoord::new(-1, 0)));
     }
+
+    #[test]
+    fn test_shafran_board_coords() {
+        let board = BoardType::Shafran;
+        let coords = board.valid_coords();
+
+        assert_eq!(coords.len(), 70);
+        // The middle file is ten cells long and the edge files six
+        for (file, length) in [('a', 6), ('e', 10), ('i', 6)] {
+            let cells = (1..=10).filter(|&rank| board.cell_at(file, rank).is_some()).count();
+            assert_eq!(cells, length);
+        }
+        assert_eq!(board.cell_at('e', 1), Some(HexCoord::new(0, -5)));
+        assert_eq!(board.square_name(HexCoord::new(0, 4)).as_deref(), Some("e10"));
+        assert_eq!(board.cell_at('k', 1), None);
+    }
+
+    #[test]
+    fn test_de_vasa_board_coords() {
+        let board = BoardType::DeVasa;
+        let coords = board.valid_coords();
+
+        assert_eq!(coords.len(), 81);
+        // Every file runs the full nine ranks
+        for file in ['a', 'e', 'i'] {
+            assert!((1..=9).all(|rank| board.cell_at(file, rank).is_some()));
+            assert_eq!(board.cell_at(file, 10), None);
+        }
+        assert_eq!(board.cell_at('a', 1), Some(HexCoord::new(-4, -4)));
+        assert_eq!(board.square_name(board.center()).as_deref(), Some("e5"));
+        assert_eq!(board.cell_at('k', 1), None);
+    }
+
+    #[test]
+    fn test_custom_board_coords() {
+        // A radius-2 hexagon with its middle cell cut out
+        let cells = BoardType::Regular { radius: 2 }
+            .valid_coords()
+            .into_iter()
+            .filter(|&coord| coord != HexCoord::new(0, 0));
+        let board = BoardType::custom(cells);
+        assert_eq!(board.valid_coords().len(), 18);
+        assert!(!board.valid_coords().contains(&HexCoord::new(0, 0)));
+
+        // Labels start from the bottom-left cell, and stop at the hole
+        assert_eq!(board.cell_at('a', 1), None);
+        assert_eq!(board.cell_at('a', 3), Some(HexCoord::new(-2, 0)));
+        assert_eq!(board.cell_at('c', 3), None);
+        assert_ne!(board.center(), HexCoord::new(0, 0));
+        assert_eq!(board.center().distance_to(HexCoord::new(0, 0)), 1);
+
+        // Order and repeats don't make a different board
+        let shuffled = BoardType::custom([HexCoord::new(1, 0), HexCoord::new(0, 1), HexCoord::new(1, 0)]);
+        assert_eq!(shuffled, BoardType::custom([HexCoord::new(0, 1), HexCoord::new(1, 0)]));
+        // Nor when read back from a save that listed them out of order
+        let json = r#"{"Custom":[{"q":9,"r":9},{"q":1,"r":0},{"q":9,"r":9}]}"#;
+        let read: BoardType = serde_json::from_str(json).unwrap();
+        assert_eq!(read, BoardType::custom([HexCoord::new(1, 0), HexCoord::new(9, 9)]));
+    }
+
+    #[test]
+    fn test_small_board_coords() {
+        let coords = BoardType::Small { radius: 3 }.valid_coords();
+
+        assert_eq!(coords.len(), 37);
+        assert!(coords.contains(&HexCoord::new(3, -3)));
+        assert!(!coords.contains(&HexCoord::new(2, 2)));
+    }
 }
@@ -0,0 +1,181 @@
+use crate::board::{splitmix64, Board};
+use crate::coords::{BoardType, HexCoord};
+use crate::pieces::{Color, Piece, PieceType};
+use std::collections::{HashMap, VecDeque};
+
+/// Practice drill: steer a lone piece through marked cells, in order, in as few moves as possible
+///
+/// Teaches how each piece gets around a hex board. The piece is alone, so
+/// every move it could make on an empty board is allowed.
+#[derive(Debug, Clone)]
+pub struct Drill {
+    /// The board, holding just the drill piece
+    pub board: Board,
+    pub piece: Piece,
+    pub position: HexCoord,
+    /// Cells still to reach, the next one first
+    pub targets: VecDeque<HexCoord>,
+    /// Moves made so far
+    pub moves: u32,
+    /// Fewest moves that reach every target
+    pub par: u32,
+}
+
+impl Drill {
+    /// Lay out a drill for `piece_type` with `target_count` targets, the same every time for a given seed
+    pub fn generate(board_type: BoardType, piece_type: PieceType, target_count: usize, seed: u64) -> Result<Self, DrillError> {
+        if piece_type == PieceType::Pawn {
+            return Err(DrillError::UnsupportedPiece(piece_type));
+        }
+        let piece = Piece::new(piece_type, Color::White);
+        let empty = Board::new(board_type);
+        let mut cells: Vec<HexCoord> = empty.valid_coords.iter().copied().collect();
+        cells.sort_by_key(|coord| (coord.q, coord.r));
+        if cells.len() <= target_count {
+            return Err(DrillError::BoardTooSmall);
+        }
+
+        let mut state = seed;
+        let mut pick = |cells: &[HexCoord]| {
+            state = splitmix64(state);
+            cells[(state % cells.len() as u64) as usize]
+        };
+        let start = pick(&cells);
+
+        // Each target is a cell the piece can reach from the last one, but not in a single move
+        let mut targets = VecDeque::new();
+        let mut par = 0;
+        let mut from = start;
+        for _ in 0..target_count {
+            let distances = distances_from(&empty, piece_type, from);
+            let mut candidates: Vec<HexCoord> = cells
+                .iter()
+                .copied()
+                .filter(|coord| *coord != start && !targets.contains(coord))
+                .filter(|coord| distances.get(coord).is_some_and(|&distance| distance >= 2))
+                .collect();
+            if candidates.is_empty() {
+                candidates = cells
+                    .iter()
+                    .copied()
+                    .filter(|coord| *coord != start && !targets.contains(coord) && distances.contains_key(coord))
+                    .collect();
+            }
+            if candidates.is_empty() {
+                return Err(DrillError::BoardTooSmall);
+            }
+            let target = pick(&candidates);
+            par += distances[&target];
+            targets.push_back(target);
+            from = target;
+        }
+
+        let mut board = empty;
+        board.pieces.insert(start, piece);
+        Ok(Self { board, piece, position: start, targets, moves: 0, par })
+    }
+
+    /// Cells the piece can move to from where it stands
+    pub fn legal_moves(&self) -> Vec<HexCoord> {
+        self.piece.piece_type.get_moves(self.position, &self.board)
+    }
+
+    /// Move the piece; returns true if it reached the next target
+    pub fn make_move(&mut self, to: HexCoord) -> Result<bool, DrillError> {
+        if self.is_complete() {
+            return Err(DrillError::Complete);
+        }
+        if !self.legal_moves().contains(&to) {
+            return Err(DrillError::IllegalMove(to));
+        }
+        self.board.pieces.remove(&self.position);
+        self.board.pieces.insert(to, self.piece);
+        self.position = to;
+        self.moves += 1;
+
+        let reached = self.targets.front() == Some(&to);
+        if reached {
+            self.targets.pop_front();
+        }
+        Ok(reached)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+/// Fewest moves a lone piece needs between two cells, if it can get there at all
+pub fn fewest_moves(board_type: BoardType, piece_type: PieceType, from: HexCoord, to: HexCoord) -> Option<u32> {
+    distances_from(&Board::new(board_type), piece_type, from).get(&to).copied()
+}
+
+/// Breadth-first move counts from `from` to every cell the piece can reach on an empty board
+fn distances_from(empty: &Board, piece_type: PieceType, from: HexCoord) -> HashMap<HexCoord, u32> {
+    let mut distances = HashMap::from([(from, 0)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(cell) = queue.pop_front() {
+        let distance = distances[&cell];
+        for next in piece_type.get_moves(cell, empty) {
+            if let std::collections::hash_map::Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DrillError {
+    #[error("Drills move a single non-pawn piece, not {0:?}")]
+    UnsupportedPiece(PieceType),
+    #[error("The board has too few reachable cells for this drill")]
+    BoardTooSmall,
+    #[error("The piece cannot move to {0:?}")]
+    IllegalMove(HexCoord),
+    #[error("The drill is already complete")]
+    Complete,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: BoardType = BoardType::Regular { radius: 5 };
+
+    #[test]
+    fn test_fewest_moves() {
+        let center = HexCoord::new(0, 0);
+        assert_eq!(fewest_moves(BOARD, PieceType::Rook, center, HexCoord::new(0, 4)), Some(1));
+        assert_eq!(fewest_moves(BOARD, PieceType::King, center, center), Some(0));
+        // Bishops never leave their cell color
+        assert_eq!(fewest_moves(BOARD, PieceType::Bishop, center, HexCoord::new(0, 1)), None);
+    }
+
+    #[test]
+    fn test_drill_reaches_par() {
+        assert_eq!(
+            Drill::generate(BOARD, PieceType::Pawn, 3, 1).unwrap_err(),
+            DrillError::UnsupportedPiece(PieceType::Pawn)
+        );
+
+        let mut drill = Drill::generate(BOARD, PieceType::Knight, 3, 7).unwrap();
+        assert_eq!(drill.targets.len(), 3);
+        assert!(drill.par >= 6);
+
+        // Walking shortest paths target by target completes the drill exactly on par
+        while let Some(&target) = drill.targets.front() {
+            let distance = fewest_moves(BOARD, PieceType::Knight, drill.position, target).unwrap();
+            let step = drill
+                .legal_moves()
+                .into_iter()
+                .find(|&next| fewest_moves(BOARD, PieceType::Knight, next, target) == Some(distance - 1))
+                .unwrap();
+            drill.make_move(step).unwrap();
+        }
+        assert!(drill.is_complete());
+        assert_eq!(drill.moves, drill.par);
+        assert_eq!(drill.make_move(drill.position), Err(DrillError::Complete));
+    }
+}
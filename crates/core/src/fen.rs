@@ -0,0 +1,280 @@
+use crate::board::EnPassant;
+use crate::coords::HexCoord;
+use crate::game::Game;
+use crate::notation::{parse_square, piece_from_letter, NotationError};
+use crate::pieces::{Color, Piece, PieceType};
+use crate::variants::VariantConfig;
+
+/// Problems reading a HexFEN position
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum FenError {
+    #[error("Expected 6 fields, found {0}")]
+    FieldCount(usize),
+    #[error("Expected {expected} rows, found {found}")]
+    RowCount { expected: usize, found: usize },
+    #[error("Row {row} from the top has {expected} cells, not {found}")]
+    RowLength { row: usize, expected: usize, found: usize },
+    #[error("Unknown piece letter {0}")]
+    UnknownPiece(char),
+    #[error("Side to move must be w or b, not {0}")]
+    SideToMove(String),
+    #[error("Reserves don't close with ]")]
+    UnclosedReserves,
+    #[error("No pawn can be taken en passant on {0}")]
+    EnPassant(String),
+    #[error("Bad move counter {0}")]
+    Counter(String),
+    #[error(transparent)]
+    Square(#[from] NotationError),
+}
+
+impl Game {
+    /// The position in HexFEN, a FEN for hexagonal boards
+    ///
+    /// Six space-separated fields:
+    /// - placement: the board's rows of equal axial `r`, the top (Black's) row first,
+    ///   separated by `/`; each row lists its cells from left to right, a piece's letter
+    ///   (upper case for White) or a count of empty cells. Variants with drops follow
+    ///   it with both reserves in brackets, e.g. `[QNp]`
+    /// - side to move, `w` or `b`
+    /// - cells of kings and rooks that may still castle, comma-separated, or `-`
+    /// - en passant target cell, or `-`
+    /// - half-move clock
+    /// - full-move number
+    ///
+    /// Only the position is kept: a game read back has no move history.
+    pub fn to_fen(&self) -> String {
+        let mut placement = Vec::new();
+        for row in board_rows(self) {
+            let mut text = String::new();
+            let mut empty = 0;
+            for coord in row {
+                match self.board.get_piece(coord) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            text += &empty.to_string();
+                            empty = 0;
+                        }
+                        text.push(piece.symbol());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                text += &empty.to_string();
+            }
+            placement.push(text);
+        }
+        let mut placement = placement.join("/");
+        if self.variant.allows_drops() {
+            placement.push('[');
+            for color in [Color::White, Color::Black] {
+                placement.extend(self.reserves.get(color).iter().map(|&piece_type| Piece::new(piece_type, color).symbol()));
+            }
+            placement.push(']');
+        }
+
+        let side = match self.current_player {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+        let board_type = &self.board.board_type;
+        let mut castling: Vec<HexCoord> = self.board.castling_pieces.iter().copied().collect();
+        castling.sort_by_key(|coord| (coord.q, coord.r));
+        let castling: Vec<String> = castling.into_iter().filter_map(|coord| board_type.square_name(coord)).collect();
+        let castling = if castling.is_empty() { "-".to_string() } else { castling.join(",") };
+        let en_passant = self
+            .board
+            .en_passant
+            .and_then(|en_passant| board_type.square_name(en_passant.target))
+            .unwrap_or_else(|| "-".to_string());
+
+        format!("{} {} {} {} {} {}", placement, side, castling, en_passant, self.halfmove_clock, self.fullmove_number)
+    }
+
+    /// Set up a position from HexFEN (see `to_fen`) on `variant`'s board
+    pub fn from_fen(variant: VariantConfig, fen: &str) -> Result<Self, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        let [placement, side, castling, en_passant, halfmove, fullmove] = fields[..] else {
+            return Err(FenError::FieldCount(fields.len()));
+        };
+
+        let mut game = Game::new(variant);
+        game.board.pieces.clear();
+
+        let (placement, reserves) = match placement.split_once('[') {
+            Some((placement, reserves)) => (placement, Some(reserves.strip_suffix(']').ok_or(FenError::UnclosedReserves)?)),
+            None => (placement, None),
+        };
+        let rows = board_rows(&game);
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != rows.len() {
+            return Err(FenError::RowCount { expected: rows.len(), found: ranks.len() });
+        }
+        for (index, (row, text)) in rows.iter().zip(ranks).enumerate() {
+            let mut cells = row.iter();
+            let mut found = 0;
+            let mut digits = text.chars().peekable();
+            while let Some(c) = digits.next() {
+                if let Some(digit) = c.to_digit(10) {
+                    let mut empty = digit as usize;
+                    while let Some(more) = digits.peek().and_then(|c| c.to_digit(10)) {
+                        empty = empty * 10 + more as usize;
+                        digits.next();
+                    }
+                    found += empty;
+                    if empty > 0 {
+                        cells.nth(empty - 1);
+                    }
+                    continue;
+                }
+                let piece_type = piece_from_letter(c).ok_or(FenError::UnknownPiece(c))?;
+                let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+                found += 1;
+                if let Some(&coord) = cells.next() {
+                    game.board.pieces.insert(coord, Piece::new(piece_type, color));
+                }
+            }
+            if found != row.len() {
+                return Err(FenError::RowLength { row: index + 1, expected: row.len(), found });
+            }
+        }
+        for c in reserves.unwrap_or_default().chars() {
+            let piece_type = piece_from_letter(c).ok_or(FenError::UnknownPiece(c))?;
+            let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+            game.reserves.add(color, piece_type);
+        }
+
+        game.current_player = match side {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(FenError::SideToMove(side.to_string())),
+        };
+        let board_type = game.board.board_type.clone();
+        game.board.castling_pieces.clear();
+        if castling != "-" {
+            for square in castling.split(',') {
+                game.board.castling_pieces.insert(parse_square(&board_type, square)?);
+            }
+        }
+        if en_passant != "-" {
+            // The pawn that double-stepped belongs to the side that just moved, and stands a step past the target
+            let mover = match game.current_player {
+                Color::White => Color::Black,
+                Color::Black => Color::White,
+            };
+            let target = parse_square(&board_type, en_passant)?;
+            let pawn = target + game.board.pawn_forward(mover);
+            if game.board.get_piece(pawn) != Some(&Piece::new(PieceType::Pawn, mover)) {
+                return Err(FenError::EnPassant(en_passant.to_string()));
+            }
+            game.board.en_passant = Some(EnPassant { target, pawn });
+        }
+        game.starting_en_passant = game.board.en_passant;
+        game.halfmove_clock = halfmove.parse().map_err(|_| FenError::Counter(halfmove.to_string()))?;
+        game.starting_halfmove_clock = game.halfmove_clock;
+        game.fullmove_number = fullmove.parse().map_err(|_| FenError::Counter(fullmove.to_string()))?;
+
+        game.position_history = vec![game.position_hash()];
+        game.update_game_state();
+        Ok(game)
+    }
+}
+
+/// The board's cells in rows of equal axial `r`, top row first, each from left to right
+fn board_rows(game: &Game) -> Vec<Vec<HexCoord>> {
+    let mut cells: Vec<HexCoord> = game.board.valid_coords.iter().copied().collect();
+    cells.sort_by_key(|coord| (-coord.r, coord.q));
+    cells.chunk_by(|a, b| a.r == b.r).map(<[HexCoord]>::to_vec).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variants::Variants;
+
+    #[test]
+    fn test_fen_round_trip() {
+        let mut game = Game::new(Variants::glinski_chess());
+        let start = game.to_fen();
+        assert_eq!(start.split('/').count(), 11);
+        assert!(start.ends_with(" w - - 0 1"));
+
+        // e5-e7 double step leaves an en passant target on e6
+        game.make_move(HexCoord::new(-1, -1), HexCoord::new(-1, 1)).unwrap();
+        let fen = game.to_fen();
+        assert!(fen.ends_with(" b - e6 0 1"), "{}", fen);
+
+        let restored = Game::from_fen(Variants::glinski_chess(), &fen).unwrap();
+        assert_eq!(restored.position_hash(), game.position_hash());
+        assert_eq!(restored.board.en_passant, game.board.en_passant);
+        assert_eq!(restored.to_fen(), fen);
+        assert_eq!(restored.all_legal_moves().len(), game.all_legal_moves().len());
+
+        // Undoing back to a loaded position reopens its en passant chance
+        let mut restored = restored;
+        let (from, to) = restored
+            .all_legal_moves()
+            .into_iter()
+            .find(|&(from, _)| restored.board.get_piece(from).unwrap().piece_type == PieceType::Knight)
+            .unwrap();
+        restored.make_move(from, to).unwrap();
+        assert_eq!(restored.board.en_passant.map(|en_passant| en_passant.target), None);
+        restored.undo_move().unwrap();
+        assert_eq!(restored.board.en_passant, game.board.en_passant);
+        assert_eq!(restored.to_fen(), fen);
+
+        let restored = Game::from_fen(Variants::glinski_chess(), &start).unwrap();
+        assert_eq!(restored.position_hash(), Game::new(Variants::glinski_chess()).position_hash());
+
+        // Undoing back to a loaded position gives back its half-move clock
+        let mut game = Game::from_fen(Variants::glinski_chess(), &start.replace(" w - - 0 1", " w - - 7 1")).unwrap();
+        let (from, to) = game
+            .all_legal_moves()
+            .into_iter()
+            .find(|&(from, _)| game.board.get_piece(from).unwrap().piece_type == PieceType::Knight)
+            .unwrap();
+        game.make_move(from, to).unwrap();
+        assert_eq!(game.halfmove_clock, 8);
+        game.undo_move().unwrap();
+        assert_eq!(game.halfmove_clock, 7);
+        assert!(game.to_fen().ends_with(" w - - 7 1"));
+    }
+
+    #[test]
+    fn test_fen_castling_and_reserves() {
+        let game = Game::new(Variants::rhombus_chess());
+        let fen = game.to_fen();
+        assert_eq!(fen.split(' ').nth(2).map(|castling| castling.split(',').count()), Some(6));
+        let restored = Game::from_fen(Variants::rhombus_chess(), &fen).unwrap();
+        assert_eq!(restored.board.castling_pieces, game.board.castling_pieces);
+
+        let mut game = Game::new(Variants::glinski_crazyhouse());
+        game.reserves.add(Color::White, PieceType::Knight);
+        game.reserves.add(Color::Black, PieceType::Pawn);
+        let fen = game.to_fen();
+        assert!(fen.split(' ').next().unwrap().ends_with("[Np]"), "{}", fen);
+        let restored = Game::from_fen(Variants::glinski_crazyhouse(), &fen).unwrap();
+        assert_eq!(restored.reserves.get(Color::White), [PieceType::Knight]);
+        assert_eq!(restored.position_hash(), game.position_hash());
+    }
+
+    #[test]
+    fn test_fen_errors() {
+        let start = Game::new(Variants::glinski_chess()).to_fen();
+        let read = |fen: &str| Game::from_fen(Variants::glinski_chess(), fen).map(|_| ());
+
+        assert_eq!(read("6 w - - 0"), Err(FenError::FieldCount(5)));
+        assert_eq!(read(&start.replacen('/', "", 1)), Err(FenError::RowCount { expected: 11, found: 10 }));
+        assert_eq!(read(&start.replace("/11/", "/10/")), Err(FenError::RowLength { row: 6, expected: 11, found: 10 }));
+        assert_eq!(read(&start.replace(" w ", " x ")), Err(FenError::SideToMove("x".to_string())));
+        assert_eq!(read(&start.replace(" - 0 1", " f6 0 1")), Err(FenError::EnPassant("f6".to_string())));
+        assert_eq!(read(&start.replace(" 0 1", " 0 one")), Err(FenError::Counter("one".to_string())));
+        assert!(matches!(read(&start.replacen('P', "X", 1)), Err(FenError::UnknownPiece('X'))));
+
+        // An empty Gliński board but for two kings
+        let kings = Game::from_fen(Variants::glinski_chess(), "6/7/8/9/10/5k5/10/9/8/7/K5 w - - 0 1").unwrap();
+        assert_eq!(kings.board.pieces.len(), 2);
+        assert!(kings.is_over());
+    }
+}
@@ -0,0 +1,111 @@
+//! What each side can see in fog-of-war variants
+
+use crate::coords::HexCoord;
+use crate::game::{Game, GameResult};
+use crate::pieces::{Color, Piece};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// One side's view of a game: the cells it can see and the pieces on them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FogView {
+    pub viewer: Color,
+    /// Cells the viewer can see, sorted
+    pub visible: Vec<HexCoord>,
+    /// Pieces on the visible cells, the viewer's own included, sorted by cell
+    pub pieces: Vec<(HexCoord, Piece)>,
+    pub to_move: Color,
+    /// The result once the game is over
+    pub result: Option<GameResult>,
+}
+
+impl FogView {
+    /// Whether the viewer can see `coord`
+    pub fn sees(&self, coord: HexCoord) -> bool {
+        self.visible
+            .binary_search_by_key(&(coord.q, coord.r), |cell| (cell.q, cell.r))
+            .is_ok()
+    }
+}
+
+impl Game {
+    /// Cells `color` can see: every cell outside fog-of-war variants or once
+    /// the game is over, otherwise those its pieces stand on or can move to
+    pub fn visible_cells(&self, color: Color) -> HashSet<HexCoord> {
+        if !self.variant.has_fog_of_war() || self.is_over() {
+            return self.board.valid_coords.clone();
+        }
+        let mut visible = HashSet::new();
+        for (coord, _) in self.board.get_pieces_by_color(color) {
+            visible.insert(coord);
+            visible.extend(self.board.get_valid_moves(coord));
+        }
+        visible
+    }
+
+    /// `color`'s view of the game, with nothing it can't see
+    pub fn fog_view(&self, color: Color) -> FogView {
+        let visible_cells = self.visible_cells(color);
+        let mut visible: Vec<_> = visible_cells.iter().copied().collect();
+        visible.sort_by_key(|cell| (cell.q, cell.r));
+        let mut pieces: Vec<_> = self
+            .board
+            .pieces
+            .iter()
+            .filter(|(coord, _)| visible_cells.contains(coord))
+            .map(|(&coord, &piece)| (coord, piece))
+            .collect();
+        pieces.sort_by_key(|(cell, _)| (cell.q, cell.r));
+
+        FogView {
+            viewer: color,
+            visible,
+            pieces,
+            to_move: self.current_player,
+            result: self.get_result(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{GameError, GameState};
+    use crate::pieces::PieceType;
+    use crate::variants::Variants;
+
+    #[test]
+    fn test_each_side_sees_only_its_reach() {
+        let mut game = Game::new(Variants::glinski_dark());
+        let view = game.fog_view(Color::White);
+        // White sees its own army and where it can move, but not Black's back ranks
+        assert!(view.pieces.iter().all(|(_, piece)| piece.color == Color::White));
+        assert!(view.sees(HexCoord::new(0, 0)));
+        assert!(!view.sees(HexCoord::new(0, 5)));
+        assert_eq!(view.to_move, Color::White);
+
+        // Outside the fog everything is in view
+        let open = Game::new(Variants::glinski_chess()).fog_view(Color::White);
+        assert_eq!(open.pieces.len(), game.board.pieces.len());
+
+        // No check in the dark: the king is simply taken, which ends the game
+        game.board.pieces.clear();
+        game.board.place_piece(HexCoord::new(0, -4), Piece::new(PieceType::King, Color::White)).unwrap();
+        game.board.place_piece(HexCoord::new(0, 4), Piece::new(PieceType::King, Color::Black)).unwrap();
+        game.board.place_piece(HexCoord::new(-1, 0), Piece::new(PieceType::Rook, Color::White)).unwrap();
+        game.board.place_piece(HexCoord::new(0, 0), Piece::new(PieceType::Rook, Color::Black)).unwrap();
+        assert!(game.explain_move(HexCoord::new(0, -4), HexCoord::new(0, -3)).is_legal());
+        game.make_move(HexCoord::new(-1, 0), HexCoord::new(-1, 1)).unwrap();
+        game.make_move(HexCoord::new(0, 0), HexCoord::new(0, -4)).unwrap();
+        assert_eq!(game.game_state, GameState::KingCaptured(Color::White));
+        assert_eq!(game.score(), Some((0.0, 1.0)));
+
+        // White has lost its king and can't play on to undo the result
+        assert!(matches!(game.make_move(HexCoord::new(-1, 1), HexCoord::new(-1, 2)), Err(GameError::GameOver)));
+        assert_eq!(game.game_state, GameState::KingCaptured(Color::White));
+
+        // The fog lifts once the game is over
+        assert_eq!(game.fog_view(Color::White).pieces.len(), game.board.pieces.len());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::coords::HexCoord;
-use crate::board::{Board, BoardError};
-use crate::pieces::{Piece, Color};
-use crate::variants::VariantConfig;
+use crate::board::{splitmix64, Board, BoardError, EnPassant};
+use crate::pieces::{Piece, PieceType, Color};
+use crate::variants::{VariantConfig, StalemateRule};
+use crate::ordering::piece_value;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -13,6 +15,111 @@ pub struct Game {
     pub move_history: VecDeque<Move>,
     pub game_state: GameState,
     pub variant: VariantConfig,
+    /// Hash of every position reached so far, starting with the initial one
+    #[serde(default)]
+    pub position_history: Vec<u64>,
+    /// Captured pieces each side holds in hand, in variants with drops
+    #[serde(default)]
+    pub reserves: Reserves,
+    /// Half-moves since the last capture or pawn move
+    #[serde(default)]
+    pub halfmove_clock: u32,
+    /// Half-move clock the game started from, as set up from a FEN
+    #[serde(default)]
+    pub starting_halfmove_clock: u32,
+    /// En passant chance the game started with, as set up from a FEN
+    #[serde(default)]
+    pub starting_en_passant: Option<EnPassant>,
+    /// Number of the move being played, starting at 1 and going up after Black moves
+    #[serde(default = "first_move")]
+    pub fullmove_number: u32,
+}
+
+fn first_move() -> u32 {
+    1
+}
+
+/// Pieces each side can drop, most valuable first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reserves {
+    pub white: Vec<PieceType>,
+    pub black: Vec<PieceType>,
+}
+
+impl Reserves {
+    pub fn get(&self, color: Color) -> &[PieceType] {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.white.is_empty() && self.black.is_empty()
+    }
+
+    /// Put a piece in `color`'s hand, keeping the hand in order
+    pub fn add(&mut self, color: Color, piece_type: PieceType) {
+        let hand = self.get_mut(color);
+        let at = hand.partition_point(|&held| hand_rank(held) <= hand_rank(piece_type));
+        hand.insert(at, piece_type);
+    }
+
+    /// Take a piece out of `color`'s hand; false if it holds none
+    pub fn take(&mut self, color: Color, piece_type: PieceType) -> bool {
+        let hand = self.get_mut(color);
+        match hand.iter().position(|&held| held == piece_type) {
+            Some(at) => {
+                hand.remove(at);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn get_mut(&mut self, color: Color) -> &mut Vec<PieceType> {
+        match color {
+            Color::White => &mut self.white,
+            Color::Black => &mut self.black,
+        }
+    }
+
+    /// Hash of both hands, zero when both are empty
+    fn hash(&self) -> u64 {
+        [(Color::White, &self.white), (Color::Black, &self.black)]
+            .into_iter()
+            .flat_map(|(color, hand)| {
+                hand.iter().enumerate().map(move |(nth, &piece_type)| {
+                    splitmix64((color as u64) << 40 | (piece_type as u64) << 32 | nth as u64)
+                })
+            })
+            .fold(0, |hash, key| hash ^ key)
+    }
+}
+
+/// Cells an atomic capture on `at` clears: the capturer and every non-pawn next to it
+fn blast_cells(board: &Board, at: HexCoord) -> Vec<HexCoord> {
+    let mut cells = vec![at];
+    cells.extend(
+        at.neighbors()
+            .into_iter()
+            .filter(|&cell| board.get_piece(cell).is_some_and(|piece| piece.piece_type != PieceType::Pawn)),
+    );
+    cells
+}
+
+/// Order pieces are kept in within a hand
+fn hand_rank(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Queen => 0,
+        PieceType::Chancellor => 1,
+        PieceType::Archbishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Bishop => 4,
+        PieceType::Knight => 5,
+        PieceType::Pawn => 6,
+        PieceType::King => 7,
+    }
 }
 
 /// Current state of the game
@@ -22,7 +129,108 @@ pub enum GameState {
     Check(Color),      // Which color is in check
     Checkmate(Color),  // Which color is checkmated
     Stalemate,
-    Draw,
+    Draw(DrawReason),
+    Resigned(Color),   // Which color resigned
+    TimedOut(Color),   // Which color ran out of time
+    Exploded(Color),   // Which color's king was caught in an atomic blast
+    KingCaptured(Color), // Which color's king was taken, where check isn't enforced
+}
+
+/// Why a game ended in a draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrawReason {
+    /// The same position occurred the variant's repetition count
+    Repetition,
+    /// Neither side has enough material left to mate
+    InsufficientMaterial,
+    /// The players agreed to a draw
+    Agreement,
+    /// The variant's half-move limit passed without a capture or pawn move
+    NoProgress,
+}
+
+/// How a finished game came out: each side's points and why the game ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GameResult {
+    pub white_score: f32,
+    pub black_score: f32,
+    /// How the game ended, e.g. "White wins ¾–¼ by stalemate"
+    pub reason: String,
+}
+
+impl GameResult {
+    /// The side that scored more, None for an even split
+    pub fn winner(&self) -> Option<Color> {
+        if self.white_score > self.black_score {
+            Some(Color::White)
+        } else if self.black_score > self.white_score {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+/// Why a move is or isn't legal, as reported by `Game::explain_move`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveLegality {
+    Legal,
+    /// The game has ended; no more moves are played
+    GameOver,
+    /// One of the coordinates is not on the board
+    OffBoard,
+    /// There is no piece on the source cell
+    NoPiece,
+    /// The piece belongs to the side not on move
+    WrongTurn,
+    /// The target holds a piece of the mover's own color
+    OwnPieceAtTarget,
+    /// The piece never moves that way
+    NotAMovePattern,
+    /// The piece moves that way, but another piece stands in the way
+    Blocked { at: HexCoord },
+    /// The move would leave the mover's king attacked by the piece at `by`
+    WouldExposeKing { by: HexCoord },
+    /// Kings cannot capture when captures explode
+    KingCannotCapture,
+    /// The capture's blast would take the mover's own king
+    ExplodesOwnKing,
+    /// The king may not castle out of check or across an attacked cell
+    CastlesThroughCheck,
+}
+
+impl MoveLegality {
+    pub fn is_legal(&self) -> bool {
+        matches!(self, MoveLegality::Legal)
+    }
+}
+
+impl std::fmt::Display for MoveLegality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let square = |coord: &HexCoord| coord.to_file_rank().unwrap_or_else(|| format!("{:?}", coord));
+        match self {
+            MoveLegality::Legal => write!(f, "Legal move"),
+            MoveLegality::GameOver => write!(f, "The game is over"),
+            MoveLegality::OffBoard => write!(f, "That cell is not on the board"),
+            MoveLegality::NoPiece => write!(f, "There is no piece there"),
+            MoveLegality::WrongTurn => write!(f, "It is not that side's turn"),
+            MoveLegality::OwnPieceAtTarget => write!(f, "A piece of the same color is on the target"),
+            MoveLegality::NotAMovePattern => write!(f, "The piece cannot move that way"),
+            MoveLegality::Blocked { at } => write!(f, "The path is blocked at {}", square(at)),
+            MoveLegality::WouldExposeKing { by } => {
+                write!(f, "The king would be attacked from {}", square(by))
+            }
+            MoveLegality::KingCannotCapture => write!(f, "The king cannot capture in atomic chess"),
+            MoveLegality::ExplodesOwnKing => write!(f, "The blast would take the mover's own king"),
+            MoveLegality::CastlesThroughCheck => write!(f, "The king cannot castle out of or through check"),
+        }
+    }
 }
 
 /// A move in the game
@@ -33,6 +241,66 @@ pub struct Move {
     pub piece: Piece,
     pub captured_piece: Option<Piece>,
     pub move_number: u32,
+    /// Clock reading when the move was made, if the game was timed
+    #[serde(default)]
+    pub clock: Option<ClockSnapshot>,
+    /// The piece came from the mover's reserve onto `to`; `from` is `to` as well
+    #[serde(default)]
+    pub dropped: bool,
+    /// Pieces cleared by an atomic capture's blast, the capturer included
+    #[serde(default)]
+    pub exploded: Vec<(HexCoord, Piece)>,
+    /// What the pawn became on reaching the far edge; `piece` is still the pawn
+    #[serde(default)]
+    pub promoted_to: Option<PieceType>,
+    /// Where a pawn taken en passant stood; `captured_piece` was there rather than on `to`
+    #[serde(default)]
+    pub en_passant: Option<HexCoord>,
+    /// The rook's (from, to) when the king castled
+    #[serde(default)]
+    pub castled: Option<(HexCoord, HexCoord)>,
+    /// Cells whose castling rights the move used up, restored on undo
+    #[serde(default)]
+    pub castling_lost: Vec<HexCoord>,
+}
+
+impl Move {
+    /// Whether the move starts the half-move clock over: a capture or any pawn move, drops included
+    pub fn resets_halfmove_clock(&self) -> bool {
+        self.piece.piece_type == PieceType::Pawn || self.captured_piece.is_some()
+    }
+
+    /// The en passant chance this move left, if it was a pawn's double step by `forward`
+    pub fn double_step(&self, forward: HexCoord) -> Option<EnPassant> {
+        (self.piece.piece_type == PieceType::Pawn && !self.dropped && self.to == self.from + forward + forward)
+            .then(|| EnPassant { target: self.from + forward, pawn: self.to })
+    }
+}
+
+/// Clock reading recorded with a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ClockSnapshot {
+    /// Time the mover spent thinking, in milliseconds
+    pub think_ms: u64,
+    /// Time left on the mover's clock after the move, in milliseconds
+    pub remaining_ms: u64,
+}
+
+/// What happened as a result of a move, as returned by `Game::make_move`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MoveOutcome {
+    /// The piece taken by the move, if any
+    pub captured: Option<Piece>,
+    /// The cell the captured piece stood on (differs from the target for en passant)
+    pub captured_at: Option<HexCoord>,
+    /// The piece type a pawn was promoted to, if any
+    pub promoted_to: Option<PieceType>,
+    /// Whether the move gives check
+    pub is_check: bool,
+    /// Whether the move gives checkmate
+    pub is_checkmate: bool,
+    /// Whether the move was an en passant capture
+    pub en_passant: bool,
 }
 
 impl Game {
@@ -40,40 +308,171 @@ impl Game {
     pub fn new(variant: VariantConfig) -> Self {
         let board = variant.create_board();
         
-        Self {
+        let mut game = Self {
             board,
             current_player: Color::White,
             move_history: VecDeque::new(),
             game_state: GameState::Playing,
             variant,
-        }
+            position_history: Vec::new(),
+            reserves: Reserves::default(),
+            halfmove_clock: 0,
+            starting_halfmove_clock: 0,
+            starting_en_passant: None,
+            fullmove_number: first_move(),
+        };
+        game.position_history.push(game.position_hash());
+        game
     }
 
-    /// Make a move
-    pub fn make_move(&mut self, from: HexCoord, to: HexCoord) -> Result<(), GameError> {
+    /// Hash of the current position, including the side to move
+    pub fn position_hash(&self) -> u64 {
+        let side_to_move = match self.current_player {
+            Color::White => 0,
+            Color::Black => splitmix64(u64::MAX),
+        };
+        self.board.position_hash() ^ side_to_move ^ self.reserves.hash()
+    }
+
+    /// Make a move that doesn't promote; see `make_move_with_promotion`
+    pub fn make_move(&mut self, from: HexCoord, to: HexCoord) -> Result<MoveOutcome, GameError> {
+        self.make_move_with_promotion(from, to, None)
+    }
+
+    /// Make a move, naming the piece a pawn promotes to when it reaches the far edge
+    ///
+    /// A promoting move must name one of the variant's promotion choices, and
+    /// any other move must name none.
+    pub fn make_move_with_promotion(
+        &mut self,
+        from: HexCoord,
+        to: HexCoord,
+        promotion: Option<PieceType>,
+    ) -> Result<MoveOutcome, GameError> {
         // Validate the move
         self.validate_move(from, to)?;
         
         // Get the piece being moved
-        let piece = self.board.get_piece(from)
-            .ok_or(GameError::NoPieceAtCoordinate)?
-            .clone();
+        let piece = *self.board.get_piece(from)
+            .ok_or(GameError::NoPieceAtCoordinate)?;
+        
+        match (self.is_promotion(from, to), promotion) {
+            (true, None) => return Err(GameError::PromotionRequired),
+            (true, Some(piece_type)) if !self.variant.promotion_choices().contains(&piece_type) => {
+                return Err(GameError::InvalidPromotion(piece_type));
+            }
+            (false, Some(piece_type)) => return Err(GameError::InvalidPromotion(piece_type)),
+            _ => {}
+        }
         
-        // Check if there's a piece to capture
-        let captured_piece = self.board.get_piece(to).cloned();
+        // Check if there's a piece to capture, beside the target for en passant
+        let en_passant = self.board.en_passant_capture(from, to);
+        let captured_at = en_passant.unwrap_or(to);
+        let captured_piece = self.board.get_piece(captured_at).cloned();
+        let castled = self.board.castling_rook(from, to);
+        let castling_lost = self.castling_lost(&[Some(from), Some(to), castled.map(|(rook, _)| rook)]);
         
-        // Make the move
+        // Make the move, then set off the blast if captures explode
         self.board.move_piece(from, to)?;
+        if let Some(piece_type) = promotion {
+            self.board.pieces.insert(to, Piece::new(piece_type, piece.color));
+        }
+        let mut exploded = Vec::new();
+        if captured_piece.is_some() && self.variant.has_atomic_captures() {
+            for cell in blast_cells(&self.board, to) {
+                if let Some(piece) = self.board.remove_piece(cell) {
+                    exploded.push((cell, piece));
+                }
+            }
+        }
+        
+        // Captured pieces change sides in variants with drops
+        if let Some(captured) = captured_piece.filter(|captured| captured.piece_type != PieceType::King) {
+            if self.variant.allows_drops() {
+                self.reserves.add(piece.color, captured.piece_type);
+            }
+        }
         
         // Record the move
-        let move_number = (self.move_history.len() / 2) as u32 + 1;
-        let game_move = Move {
+        self.finish_move(Move {
             from,
             to,
             piece,
             captured_piece,
-            move_number,
-        };
+            move_number: 0,
+            clock: None,
+            dropped: false,
+            exploded,
+            promoted_to: promotion,
+            en_passant,
+            castled,
+            castling_lost,
+        });
+        
+        Ok(MoveOutcome {
+            captured: captured_piece,
+            captured_at: captured_piece.map(|_| captured_at),
+            promoted_to: promotion,
+            is_check: matches!(self.game_state, GameState::Check(_) | GameState::Checkmate(_)),
+            is_checkmate: matches!(self.game_state, GameState::Checkmate(_)),
+            en_passant: en_passant.is_some(),
+        })
+    }
+
+    /// Drop a piece from the mover's reserve onto an empty cell
+    pub fn drop_piece(&mut self, piece_type: PieceType, to: HexCoord) -> Result<MoveOutcome, GameError> {
+        self.validate_drop(self.current_player, piece_type, to)?;
+        let piece = Piece::new(piece_type, self.current_player);
+        self.reserves.take(piece.color, piece_type);
+        let castling_lost = self.castling_lost(&[Some(to)]);
+        self.board.place_piece(to, piece)?;
+        self.board.en_passant = None;
+        self.board.castling_pieces.remove(&to);
+        self.finish_move(Move {
+            from: to,
+            to,
+            piece,
+            captured_piece: None,
+            move_number: 0,
+            clock: None,
+            dropped: true,
+            exploded: Vec::new(),
+            promoted_to: None,
+            en_passant: None,
+            castled: None,
+            castling_lost,
+        });
+
+        Ok(MoveOutcome {
+            captured: None,
+            captured_at: None,
+            promoted_to: None,
+            is_check: matches!(self.game_state, GameState::Check(_) | GameState::Checkmate(_)),
+            is_checkmate: matches!(self.game_state, GameState::Checkmate(_)),
+            en_passant: false,
+        })
+    }
+
+    /// Cells the side to move could drop a piece of this type on
+    pub fn legal_drops(&self, piece_type: PieceType) -> Vec<HexCoord> {
+        let mut cells: Vec<HexCoord> = self
+            .board
+            .valid_coords
+            .iter()
+            .copied()
+            .filter(|&to| self.validate_drop(self.current_player, piece_type, to).is_ok())
+            .collect();
+        cells.sort_by_key(|coord| (coord.q, coord.r));
+        cells
+    }
+
+    /// Record a move already made on the board and hand the turn over
+    fn finish_move(&mut self, mut game_move: Move) {
+        game_move.move_number = (self.move_history.len() / 2) as u32 + 1;
+        self.halfmove_clock = if game_move.resets_halfmove_clock() { 0 } else { self.halfmove_clock + 1 };
+        if self.current_player == Color::Black {
+            self.fullmove_number += 1;
+        }
         self.move_history.push_back(game_move);
         
         // Switch players
@@ -81,45 +480,209 @@ impl Game {
             Color::White => Color::Black,
             Color::Black => Color::White,
         };
+        self.position_history.push(self.position_hash());
         
         // Update game state
         self.update_game_state();
-        
+    }
+
+    /// Check that `color` may drop a piece of this type on `to`
+    ///
+    /// The cell must be empty, the drop must not leave the dropper's king
+    /// attacked, and a pawn may not land where it could never move forward.
+    fn validate_drop(&self, color: Color, piece_type: PieceType, to: HexCoord) -> Result<(), GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if !self.variant.allows_drops() {
+            return Err(GameError::DropsNotAllowed);
+        }
+        if !self.reserves.get(color).contains(&piece_type) {
+            return Err(GameError::NotInReserve(piece_type));
+        }
+        if !self.board.is_valid_coord(to) || self.board.is_occupied(to) {
+            return Err(GameError::InvalidMove);
+        }
+        if piece_type == PieceType::Pawn && self.in_promotion_zone(color, to) {
+            return Err(GameError::InvalidMove);
+        }
+        let mut test_board = self.board.clone();
+        test_board.pieces.insert(to, Piece::new(piece_type, color));
+        if self.is_king_in_check(&test_board, color) {
+            return Err(GameError::MoveWouldPutKingInCheck);
+        }
         Ok(())
     }
 
+    /// Whether a pawn of `color` on `coord` has reached the far edge, where it can't advance and promotes
+    pub fn in_promotion_zone(&self, color: Color, coord: HexCoord) -> bool {
+        self.board.is_valid_coord(coord) && !self.board.is_valid_coord(coord + self.board.pawn_forward(color))
+    }
+
+    /// Whether moving the piece on `from` to `to` is a pawn reaching the promotion zone
+    pub fn is_promotion(&self, from: HexCoord, to: HexCoord) -> bool {
+        self.board
+            .get_piece(from)
+            .is_some_and(|piece| piece.piece_type == PieceType::Pawn && self.in_promotion_zone(piece.color, to))
+    }
+
+    /// The strongest promotion choice if the move promotes, for callers that always queen
+    pub fn auto_promotion(&self, from: HexCoord, to: HexCoord) -> Option<PieceType> {
+        match self.is_promotion(from, to) {
+            true => self.variant.promotion_choices().first().copied(),
+            false => None,
+        }
+    }
+
+    /// Whether `color` has any legal drop
+    fn has_legal_drop(&self, color: Color) -> bool {
+        let hand = self.reserves.get(color);
+        hand.iter().enumerate().any(|(nth, &piece_type)| {
+            // Hands are kept in order, so each type is tried once
+            (nth == 0 || hand[nth - 1] != piece_type)
+                && self
+                    .board
+                    .valid_coords
+                    .iter()
+                    .any(|&to| self.validate_drop(color, piece_type, to).is_ok())
+        })
+    }
+
     /// Validate a move
     fn validate_move(&self, from: HexCoord, to: HexCoord) -> Result<(), GameError> {
-        // Check if there's a piece at the source
-        let piece = self.board.get_piece(from)
-            .ok_or(GameError::NoPieceAtCoordinate)?;
-        
-        // Check if it's the current player's piece
+        match self.explain_move(from, to) {
+            MoveLegality::Legal => Ok(()),
+            MoveLegality::GameOver => Err(GameError::GameOver),
+            MoveLegality::NoPiece => Err(GameError::NoPieceAtCoordinate),
+            MoveLegality::WrongTurn => Err(GameError::NotYourPiece),
+            MoveLegality::WouldExposeKing { .. } => Err(GameError::MoveWouldPutKingInCheck),
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+
+    /// Explain whether a move is legal for the side to move, and if not, why
+    pub fn explain_move(&self, from: HexCoord, to: HexCoord) -> MoveLegality {
+        if self.is_over() {
+            return MoveLegality::GameOver;
+        }
+        if !self.board.is_valid_coord(from) || !self.board.is_valid_coord(to) {
+            return MoveLegality::OffBoard;
+        }
+
+        let piece = match self.board.get_piece(from) {
+            Some(piece) => *piece,
+            None => return MoveLegality::NoPiece,
+        };
+
         if piece.color != self.current_player {
-            return Err(GameError::NotYourPiece);
+            return MoveLegality::WrongTurn;
         }
-        
-        // Check if the move is valid for the piece
-        let valid_moves = self.board.get_valid_moves(from);
-        if !valid_moves.contains(&to) {
-            return Err(GameError::InvalidMove);
+
+        if self.board.get_piece(to).is_some_and(|target| target.color == piece.color) {
+            return MoveLegality::OwnPieceAtTarget;
         }
-        
-        // Check if the move would put own king in check
-        let test_board = self.board.with_move(from, to)?;
-        if self.is_king_in_check(&test_board, self.current_player) {
-            return Err(GameError::MoveWouldPutKingInCheck);
+
+        if !self.board.get_valid_moves(from).contains(&to) {
+            if self.board.castling_rook(from, to).is_some() {
+                return MoveLegality::CastlesThroughCheck;
+            }
+
+            // Replay the move on an otherwise empty board to tell a blocked
+            // line apart from a target the piece can never reach
+            let mut empty = self.board.clone();
+            empty.pieces.clear();
+            empty.pieces.insert(from, piece);
+            if !piece.piece_type.get_moves(from, &empty).contains(&to) {
+                return MoveLegality::NotAMovePattern;
+            }
+
+            let tables = self.board.move_tables();
+            let blocker = tables
+                .orthogonal_rays(from)
+                .iter()
+                .chain(tables.diagonal_rays(from))
+                .find(|ray| ray.contains(&to))
+                .and_then(|ray| {
+                    ray.iter()
+                        .take_while(|&&cell| cell != to)
+                        .find(|&&cell| self.board.is_occupied(cell))
+                        .copied()
+                });
+
+            // Pawns are stopped by the piece on the target cell itself
+            return MoveLegality::Blocked { at: blocker.unwrap_or(to) };
+        }
+
+        let capture = self.is_capture(from, to);
+        if capture && piece.piece_type == PieceType::King && self.variant.has_atomic_captures() {
+            return MoveLegality::KingCannotCapture;
+        }
+
+        match self.board_after(from, to) {
+            Ok(test_board) => self.king_safety(&test_board, self.current_player),
+            Err(_) => MoveLegality::OffBoard,
+        }
+    }
+
+    /// Which of these cells still hold castling rights, and so lose them to the move
+    fn castling_lost(&self, cells: &[Option<HexCoord>]) -> Vec<HexCoord> {
+        cells
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|cell| self.board.castling_pieces.contains(cell))
+            .collect()
+    }
+
+    /// Whether moving from `from` to `to` takes a piece, en passant included
+    pub fn is_capture(&self, from: HexCoord, to: HexCoord) -> bool {
+        self.board.is_occupied(to) || self.board.en_passant_capture(from, to).is_some()
+    }
+
+    /// The board after a move, with the blast applied if captures explode
+    fn board_after(&self, from: HexCoord, to: HexCoord) -> Result<Board, BoardError> {
+        let mut board = self.board.with_move(from, to)?;
+        if self.is_capture(from, to) && self.variant.has_atomic_captures() {
+            for cell in blast_cells(&board, to) {
+                board.remove_piece(cell);
+            }
+        }
+        Ok(board)
+    }
+
+    /// Whether `color`'s king comes through a move that led to `board`
+    fn king_safety(&self, board: &Board, color: Color) -> MoveLegality {
+        if self.variant.has_atomic_captures() {
+            let opponent = match color {
+                Color::White => Color::Black,
+                Color::Black => Color::White,
+            };
+            if board.get_king(color).is_none() {
+                return MoveLegality::ExplodesOwnKing;
+            }
+            // Blowing up the other king wins, whatever else is attacked
+            if board.get_king(opponent).is_none() {
+                return MoveLegality::Legal;
+            }
+        }
+        match self.king_attacker(board, color) {
+            Some(by) => MoveLegality::WouldExposeKing { by },
+            None => MoveLegality::Legal,
         }
-        
-        Ok(())
     }
 
     /// Check if a king is in check
     fn is_king_in_check(&self, board: &Board, color: Color) -> bool {
-        let king_pos = match board.get_king(color) {
-            Some(pos) => pos,
-            None => return false, // No king found
-        };
+        self.king_attacker(board, color).is_some()
+    }
+
+    /// Find an opponent piece attacking the king of `color`, if any
+    fn king_attacker(&self, board: &Board, color: Color) -> Option<HexCoord> {
+        // Nobody is in check in the dark: a king may walk into attack and be taken
+        if self.variant.has_fog_of_war() {
+            return None;
+        }
+        let king_pos = board.get_king(color)?;
         
         // Check if any opponent piece can attack the king
         let opponent_color = match color {
@@ -127,13 +690,21 @@ impl Game {
             Color::Black => Color::White,
         };
         
-        for (coord, piece) in board.get_pieces_by_color(opponent_color) {
-            if piece.piece_type.get_moves(coord, board).contains(&king_pos) {
-                return true;
-            }
+        // Touching kings are safe when captures explode: taking one would blow up the other
+        if self.variant.has_atomic_captures()
+            && board.get_king(opponent_color).is_some_and(|king| king_pos.neighbors().contains(&king))
+        {
+            return None;
         }
         
-        false
+        if !board.is_attacked(king_pos, opponent_color) {
+            return None;
+        }
+        let (white, black) = board.attackers(king_pos);
+        match opponent_color {
+            Color::White => white.first().copied(),
+            Color::Black => black.first().copied(),
+        }
     }
 
     /// Check if a player is in checkmate
@@ -143,17 +714,12 @@ impl Game {
         }
         
         // Check if any move can get out of check
-        for (coord, _piece) in self.board.get_pieces_by_color(color) {
-            let valid_moves = self.board.get_valid_moves(coord);
-            for target in valid_moves {
-                let test_board = self.board.with_move(coord, target).unwrap();
-                if !self.is_king_in_check(&test_board, color) {
-                    return false; // Found a move that gets out of check
-                }
-            }
+        if self.has_legal_move(color) {
+            return false; // Found a move that gets out of check
         }
         
-        true
+        // A drop can block the check
+        !self.has_legal_drop(color)
     }
 
     /// Check if a player is in stalemate
@@ -163,22 +729,43 @@ impl Game {
         }
         
         // Check if any move is possible
-        for (coord, _piece) in self.board.get_pieces_by_color(color) {
-            let valid_moves = self.board.get_valid_moves(coord);
-            for target in valid_moves {
-                let test_board = self.board.with_move(coord, target).unwrap();
-                if !self.is_king_in_check(&test_board, color) {
-                    return false; // Found a valid move
-                }
-            }
+        if self.has_legal_move(color) {
+            return false; // Found a valid move
         }
         
-        true
+        !self.has_legal_drop(color)
+    }
+
+    /// Check if the current position has occurred the variant's repetition count
+    fn is_repetition(&self) -> bool {
+        let Some(limit) = self.variant.draw_rules.repetition_count else {
+            return false;
+        };
+        let Some(&current) = self.position_history.last() else {
+            return false;
+        };
+
+        let occurrences = self.position_history.iter().filter(|&&hash| hash == current).count();
+        occurrences >= limit as usize
+    }
+
+    /// Check if the variant's half-move limit has passed without a capture or pawn move
+    fn is_no_progress(&self) -> bool {
+        self.variant.draw_rules.halfmove_limit.is_some_and(|limit| self.halfmove_clock >= limit)
     }
 
     /// Update the game state based on current position
-    fn update_game_state(&mut self) {
-        if self.is_checkmate(self.current_player) {
+    pub(crate) fn update_game_state(&mut self) {
+        // Judge the position afresh: moves are only looked for while the game is on
+        self.game_state = GameState::Playing;
+        let kingless = [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| self.board.get_king(color).is_none());
+        if let Some(color) = kingless.filter(|_| self.variant.has_atomic_captures()) {
+            self.game_state = GameState::Exploded(color);
+        } else if let Some(color) = kingless.filter(|_| self.variant.has_fog_of_war()) {
+            self.game_state = GameState::KingCaptured(color);
+        } else if self.is_checkmate(self.current_player) {
             let winner = match self.current_player {
                 Color::White => Color::Black,
                 Color::Black => Color::White,
@@ -186,6 +773,14 @@ impl Game {
             self.game_state = GameState::Checkmate(winner);
         } else if self.is_stalemate(self.current_player) {
             self.game_state = GameState::Stalemate;
+        } else if self.is_repetition() {
+            self.game_state = GameState::Draw(DrawReason::Repetition);
+        } else if self.is_no_progress() {
+            self.game_state = GameState::Draw(DrawReason::NoProgress);
+        } else if self.reserves.is_empty()
+            && self.variant.draw_rules.insufficient_material.applies_to(self.board.pieces.values())
+        {
+            self.game_state = GameState::Draw(DrawReason::InsufficientMaterial);
         } else if self.is_king_in_check(&self.board, self.current_player) {
             self.game_state = GameState::Check(self.current_player);
         } else {
@@ -193,7 +788,39 @@ impl Game {
         }
     }
 
-    /// Get all valid moves for the current player
+    /// Legal moves for the piece on `coord`, which must belong to the side to move
+    ///
+    /// Unlike `Board::get_valid_moves`, this leaves out moves that break king
+    /// safety or any of the variant's rules.
+    pub fn legal_moves(&self, coord: HexCoord) -> Vec<HexCoord> {
+        self.board
+            .get_valid_moves(coord)
+            .into_iter()
+            .filter(|&target| self.explain_move(coord, target).is_legal())
+            .collect()
+    }
+
+    /// Every legal move for the side to move, in a stable order
+    pub fn all_legal_moves(&self) -> Vec<(HexCoord, HexCoord)> {
+        let mut moves: Vec<_> = self
+            .board
+            .get_pieces_by_color(self.current_player)
+            .into_iter()
+            .flat_map(|(from, _)| self.legal_moves(from).into_iter().map(move |to| (from, to)))
+            .collect();
+        moves.sort_by_key(|(from, to)| (from.q, from.r, to.q, to.r));
+        moves
+    }
+
+    /// Whether `color`, which must be on move, has any legal move on the board
+    fn has_legal_move(&self, color: Color) -> bool {
+        self.board
+            .get_pieces_by_color(color)
+            .into_iter()
+            .any(|(coord, _)| !self.legal_moves(coord).is_empty())
+    }
+
+    /// Get all pseudo-legal moves for the current player, by piece
     pub fn get_valid_moves(&self) -> Vec<(HexCoord, Vec<HexCoord>)> {
         let mut moves = Vec::new();
         
@@ -207,17 +834,94 @@ impl Game {
         moves
     }
 
+    /// Attach a clock reading to the last move made
+    pub fn record_clock(&mut self, clock: ClockSnapshot) -> Result<(), GameError> {
+        let last = self.move_history.back_mut().ok_or(GameError::NoMoveMade)?;
+        last.clock = Some(clock);
+        Ok(())
+    }
+
+    /// Clock readings for one side's moves, in order; `None` for untimed moves
+    pub fn clock_history(&self, color: Color) -> Vec<Option<ClockSnapshot>> {
+        self.move_history
+            .iter()
+            .filter(|game_move| game_move.piece.color == color)
+            .map(|game_move| game_move.clock)
+            .collect()
+    }
+
+    /// White's material lead in centipawns before the first move and after every move
+    pub fn material_history(&self) -> Vec<i32> {
+        // A capture takes material off the board, a drop puts it back and a promotion trades up
+        let promotion = |game_move: &Move| match (game_move.promoted_to, game_move.piece.color) {
+            (Some(piece_type), Color::White) => piece_value(piece_type) - piece_value(PieceType::Pawn),
+            (Some(piece_type), Color::Black) => piece_value(PieceType::Pawn) - piece_value(piece_type),
+            (None, _) => 0,
+        };
+        let swing = |game_move: &Move| promotion(game_move) + match (game_move.dropped, game_move.captured_piece) {
+            (true, _) => match game_move.piece.color {
+                Color::White => piece_value(game_move.piece.piece_type),
+                Color::Black => -piece_value(game_move.piece.piece_type),
+            },
+            (false, captured) => captured
+                .into_iter()
+                .chain(game_move.exploded.iter().map(|&(_, piece)| piece))
+                .filter(|piece| piece.piece_type != PieceType::King)
+                .map(|piece| match piece.color {
+                    Color::White => -piece_value(piece.piece_type),
+                    Color::Black => piece_value(piece.piece_type),
+                })
+                .sum(),
+        };
+        let current: i32 = self.board.pieces.values()
+            .filter(|piece| piece.piece_type != PieceType::King)
+            .map(|piece| match piece.color {
+                Color::White => piece_value(piece.piece_type),
+                Color::Black => -piece_value(piece.piece_type),
+            })
+            .sum();
+
+        // Work back to the starting balance, then forward again move by move
+        let mut balance = current - self.move_history.iter().map(swing).sum::<i32>();
+        let mut history = vec![balance];
+        for game_move in &self.move_history {
+            balance += swing(game_move);
+            history.push(balance);
+        }
+        history
+    }
+
     /// Undo the last move
     pub fn undo_move(&mut self) -> Result<(), GameError> {
         let last_move = self.move_history.pop_back()
             .ok_or(GameError::NoMovesToUndo)?;
         
-        // Move the piece back
-        self.board.move_piece(last_move.to, last_move.from)?;
-        
-        // Restore captured piece if any
-        if let Some(captured) = last_move.captured_piece {
-            self.board.place_piece(last_move.to, captured)?;
+        if last_move.dropped {
+            // Return the dropped piece to its owner's hand
+            self.board.remove_piece(last_move.to);
+            self.reserves.add(last_move.piece.color, last_move.piece.piece_type);
+        } else {
+            // Rebuild what the blast cleared, the capturer included
+            for &(cell, piece) in &last_move.exploded {
+                self.board.place_piece(cell, piece)?;
+            }
+            
+            // Move the piece back, a promoted piece as the pawn it was, and the rook too after castling
+            self.board.move_piece(last_move.to, last_move.from)?;
+            if let Some((rook_from, rook_to)) = last_move.castled {
+                self.board.move_piece(rook_to, rook_from)?;
+            }
+            if last_move.promoted_to.is_some() {
+                self.board.pieces.insert(last_move.from, last_move.piece);
+            }
+            
+            // Restore captured piece if any, taking it back out of the capturer's hand
+            if let Some(captured) = last_move.captured_piece {
+                self.board.place_piece(last_move.en_passant.unwrap_or(last_move.to), captured)?;
+                if self.variant.allows_drops() {
+                    self.reserves.take(last_move.piece.color, captured.piece_type);
+                }
+            }
         }
         
         // Switch players back
@@ -225,6 +929,20 @@ impl Game {
             Color::White => Color::Black,
             Color::Black => Color::White,
         };
+        self.position_history.pop();
+        if self.current_player == Color::Black {
+            self.fullmove_number -= 1;
+        }
+        let quiet = self.move_history.iter().rev().take_while(|m| !m.resets_halfmove_clock()).count() as u32;
+        self.halfmove_clock = if quiet as usize == self.move_history.len() { self.starting_halfmove_clock + quiet } else { quiet };
+        
+        // Give back the castling rights the move used up, and reopen en passant if the move before was a
+        // double step, or the game was set up with the chance open
+        self.board.castling_pieces.extend(last_move.castling_lost.iter().copied());
+        self.board.en_passant = match self.move_history.back() {
+            Some(m) => m.double_step(self.board.pawn_forward(m.piece.color)),
+            None => self.starting_en_passant,
+        };
         
         // Update game state
         self.update_game_state();
@@ -232,8 +950,38 @@ impl Game {
         Ok(())
     }
 
-    /// Get the game result as a string
-    pub fn get_result(&self) -> Option<String> {
+    /// Whether the game has ended, by any means
+    pub fn is_over(&self) -> bool {
+        !matches!(self.game_state, GameState::Playing | GameState::Check(_))
+    }
+
+    /// End the game with `color` resigning
+    pub fn resign(&mut self, color: Color) -> Result<(), GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        self.game_state = GameState::Resigned(color);
+        Ok(())
+    }
+
+    /// End the game in a draw both players agreed to
+    pub fn agree_draw(&mut self) -> Result<(), GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        self.game_state = GameState::Draw(DrawReason::Agreement);
+        Ok(())
+    }
+
+    /// Get the scores and reason once the game is over, scoring stalemate by the variant's rule
+    pub fn get_result(&self) -> Option<GameResult> {
+        let (white_score, black_score) = self.score()?;
+        let reason = self.result_reason()?;
+        Some(GameResult { white_score, black_score, reason })
+    }
+
+    /// Describe how the game ended
+    fn result_reason(&self) -> Option<String> {
         match self.game_state {
             GameState::Checkmate(winner) => {
                 let winner_name = match winner {
@@ -242,8 +990,74 @@ impl Game {
                 };
                 Some(format!("{} wins by checkmate", winner_name))
             }
-            GameState::Stalemate => Some("Draw by stalemate".to_string()),
-            GameState::Draw => Some("Draw".to_string()),
+            GameState::Stalemate => {
+                // The side left without moves is the one to play
+                let winner_name = match self.current_player {
+                    Color::White => "Black",
+                    Color::Black => "White",
+                };
+                Some(match self.variant.stalemate_rule {
+                    StalemateRule::Draw => "Draw by stalemate".to_string(),
+                    StalemateRule::PartialWin => format!("{} wins ¾–¼ by stalemate", winner_name),
+                    StalemateRule::Win => format!("{} wins by stalemate", winner_name),
+                })
+            }
+            GameState::Draw(DrawReason::Repetition) => Some("Draw by repetition".to_string()),
+            GameState::Draw(DrawReason::InsufficientMaterial) => {
+                Some("Draw by insufficient material".to_string())
+            }
+            GameState::Draw(DrawReason::Agreement) => Some("Draw by agreement".to_string()),
+            GameState::Draw(DrawReason::NoProgress) => {
+                let limit = self.variant.draw_rules.halfmove_limit.unwrap_or(100);
+                Some(format!("Draw by the {}-move rule", limit / 2))
+            }
+            GameState::Resigned(loser) => {
+                let winner_name = match loser {
+                    Color::White => "Black",
+                    Color::Black => "White",
+                };
+                Some(format!("{} wins by resignation", winner_name))
+            }
+            GameState::Exploded(loser) => {
+                let winner_name = match loser {
+                    Color::White => "Black",
+                    Color::Black => "White",
+                };
+                Some(format!("{} wins by exploding the king", winner_name))
+            }
+            GameState::KingCaptured(loser) => {
+                let winner_name = match loser {
+                    Color::White => "Black",
+                    Color::Black => "White",
+                };
+                Some(format!("{} wins by capturing the king", winner_name))
+            }
+            GameState::TimedOut(loser) => {
+                let winner_name = match loser {
+                    Color::White => "Black",
+                    Color::Black => "White",
+                };
+                Some(format!("{} wins on time", winner_name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the final (white, black) score, or None while the game is in progress
+    pub fn score(&self) -> Option<(f32, f32)> {
+        match self.game_state {
+            GameState::Checkmate(Color::White) => Some((1.0, 0.0)),
+            GameState::Checkmate(Color::Black) => Some((0.0, 1.0)),
+            GameState::Stalemate => Some(self.variant.stalemate_rule.score(self.current_player)),
+            GameState::Draw(_) => Some((0.5, 0.5)),
+            GameState::Resigned(Color::White) => Some((0.0, 1.0)),
+            GameState::Resigned(Color::Black) => Some((1.0, 0.0)),
+            GameState::TimedOut(Color::White) | GameState::Exploded(Color::White) | GameState::KingCaptured(Color::White) => {
+                Some((0.0, 1.0))
+            }
+            GameState::TimedOut(Color::Black) | GameState::Exploded(Color::Black) | GameState::KingCaptured(Color::Black) => {
+                Some((1.0, 0.0))
+            }
             _ => None,
         }
     }
@@ -261,6 +1075,18 @@ pub enum GameError {
     MoveWouldPutKingInCheck,
     #[error("No moves to undo")]
     NoMovesToUndo,
+    #[error("No move has been made yet")]
+    NoMoveMade,
+    #[error("The game is already over")]
+    GameOver,
+    #[error("This variant has no drops")]
+    DropsNotAllowed,
+    #[error("No {0:?} in reserve")]
+    NotInReserve(PieceType),
+    #[error("Choose a piece to promote to")]
+    PromotionRequired,
+    #[error("Can't promote to {0:?} here")]
+    InvalidPromotion(PieceType),
     #[error("Board error: {0}")]
     BoardError(#[from] BoardError),
 }
@@ -268,7 +1094,7 @@ pub enum GameError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::variants::Variants;
+    use crate::variants::{PawnMovement, SpecialRule, Variants};
 
     #[test]
     fn test_game_creation() {
@@ -278,6 +1104,400 @@ mod tests {
         assert_eq!(game.game_state, GameState::Playing);
     }
 
+    #[test]
+    fn test_stalemate_result_follows_variant_rule() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.current_player = Color::Black;
+        game.game_state = GameState::Stalemate;
+        assert_eq!(game.score(), Some((0.75, 0.25)));
+        assert_eq!(
+            game.get_result(),
+            Some(GameResult { white_score: 0.75, black_score: 0.25, reason: "White wins ¾–¼ by stalemate".to_string() })
+        );
+        assert_eq!(game.get_result().and_then(|result| result.winner()), Some(Color::White));
+
+        let mut game = Game::new(Variants::mccooey_chess());
+        game.current_player = Color::White;
+        game.game_state = GameState::Stalemate;
+        assert_eq!(game.score(), Some((0.0, 1.0)));
+        assert_eq!(game.get_result().map(|result| result.reason).as_deref(), Some("Black wins by stalemate"));
+
+        let mut game = Game::new(Variants::mini_hexchess());
+        game.game_state = GameState::Stalemate;
+        assert_eq!(game.score(), Some((0.5, 0.5)));
+        assert_eq!(game.get_result().map(|result| result.reason).as_deref(), Some("Draw by stalemate"));
+        assert_eq!(game.get_result().and_then(|result| result.winner()), None);
+    }
+
+    /// Find a legal knight move for the side to move
+    fn knight_move(game: &Game) -> (HexCoord, HexCoord) {
+        game.get_valid_moves()
+            .into_iter()
+            .filter(|(from, _)| {
+                game.board.get_piece(*from).map(|p| p.piece_type) == Some(crate::pieces::PieceType::Knight)
+            })
+            .find_map(|(from, targets)| {
+                targets.into_iter()
+                    .find(|to| !game.board.is_occupied(*to))
+                    .map(|to| (from, to))
+            })
+            .expect("a knight move should be available")
+    }
+
+    #[test]
+    fn test_threefold_repetition_draws() {
+        let mut game = Game::new(Variants::glinski_chess());
+        let white = knight_move(&game);
+        game.make_move(white.0, white.1).unwrap();
+        let black = knight_move(&game);
+        game.make_move(black.0, black.1).unwrap();
+
+        // Shuffle both knights back and forth: the start position recurs after 4 and 8 plies
+        let shuffle = [
+            (white.1, white.0),
+            (black.1, black.0),
+            (white.0, white.1),
+            (black.0, black.1),
+            (white.1, white.0),
+        ];
+        for (from, to) in shuffle {
+            game.make_move(from, to).unwrap();
+            assert_eq!(game.game_state, GameState::Playing);
+        }
+        game.make_move(black.1, black.0).unwrap();
+
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::Repetition));
+        assert_eq!(game.score(), Some((0.5, 0.5)));
+        assert!(matches!(game.make_move(white.0, white.1), Err(GameError::GameOver)));
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::Repetition));
+
+        game.undo_move().unwrap();
+        assert_eq!(game.game_state, GameState::Playing);
+        assert_eq!(game.position_history.len(), game.move_history.len() + 1);
+    }
+
+    #[test]
+    fn test_halfmove_limit_draws() {
+        let mut variant = Variants::glinski_chess();
+        variant.draw_rules.halfmove_limit = Some(6);
+        variant.draw_rules.repetition_count = None;
+        let mut game = Game::new(variant);
+
+        // f5-f6 starts the clock at zero, then the knights shuffle
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        assert_eq!((game.halfmove_clock, game.fullmove_number), (0, 1));
+        let black = knight_move(&game);
+        game.make_move(black.0, black.1).unwrap();
+        let white = knight_move(&game);
+        let shuffle = [
+            white,
+            (black.1, black.0),
+            (white.1, white.0),
+            (black.0, black.1),
+            (white.0, white.1),
+        ];
+        for (from, to) in shuffle {
+            assert_eq!(game.game_state, GameState::Playing);
+            game.make_move(from, to).unwrap();
+        }
+
+        assert_eq!((game.halfmove_clock, game.fullmove_number), (6, 4));
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::NoProgress));
+        assert_eq!(game.get_result().map(|result| result.reason).as_deref(), Some("Draw by the 3-move rule"));
+        let (from, to) = knight_move(&game);
+        assert!(matches!(game.make_move(from, to), Err(GameError::GameOver)));
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::NoProgress));
+
+        game.undo_move().unwrap();
+        assert_eq!((game.halfmove_clock, game.fullmove_number), (5, 4));
+        assert_eq!(game.game_state, GameState::Playing);
+    }
+
+    #[test]
+    fn test_bare_kings_draw() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.retain(|_, piece| piece.piece_type == crate::pieces::PieceType::King);
+        game.update_game_state();
+
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::InsufficientMaterial));
+    }
+
+    #[test]
+    fn test_explain_move_reasons() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.clear();
+        for (q, r, piece_type, color) in [
+            (0, -5, PieceType::King, Color::White),
+            (0, 5, PieceType::King, Color::Black),
+            (-3, 0, PieceType::Rook, Color::White),
+            (-1, 0, PieceType::Pawn, Color::White),
+            (0, -3, PieceType::Bishop, Color::White),
+            (0, 3, PieceType::Rook, Color::Black),
+        ] {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+
+        let rook = HexCoord::new(-3, 0);
+        assert_eq!(game.explain_move(rook, HexCoord::new(-2, 0)), MoveLegality::Legal);
+        assert_eq!(
+            game.explain_move(rook, HexCoord::new(1, 0)),
+            MoveLegality::Blocked { at: HexCoord::new(-1, 0) }
+        );
+        assert_eq!(game.explain_move(rook, HexCoord::new(-1, 0)), MoveLegality::OwnPieceAtTarget);
+        assert_eq!(game.explain_move(rook, HexCoord::new(-2, 1)), MoveLegality::NotAMovePattern);
+        assert_eq!(game.explain_move(rook, HexCoord::new(10, 10)), MoveLegality::OffBoard);
+        assert_eq!(game.explain_move(HexCoord::new(2, 2), HexCoord::new(2, 3)), MoveLegality::NoPiece);
+        assert_eq!(
+            game.explain_move(HexCoord::new(0, 5), HexCoord::new(1, 4)),
+            MoveLegality::WrongTurn
+        );
+
+        // The bishop is pinned against its king by the black rook
+        assert_eq!(
+            game.explain_move(HexCoord::new(0, -3), HexCoord::new(2, -4)),
+            MoveLegality::WouldExposeKing { by: HexCoord::new(0, 3) }
+        );
+        assert!(matches!(
+            game.make_move(HexCoord::new(0, -3), HexCoord::new(2, -4)),
+            Err(GameError::MoveWouldPutKingInCheck)
+        ));
+        // so it has pseudo-legal moves but no legal ones
+        assert!(!game.board.get_valid_moves(HexCoord::new(0, -3)).is_empty());
+        assert!(game.legal_moves(HexCoord::new(0, -3)).is_empty());
+        assert!(game.all_legal_moves().iter().all(|&(from, _)| from != HexCoord::new(0, -3)));
+
+        // A pawn cannot step forward onto an occupied cell
+        game.board.place_piece(HexCoord::new(-1, 1), Piece::new(PieceType::Knight, Color::Black)).unwrap();
+        assert_eq!(
+            game.explain_move(HexCoord::new(-1, 0), HexCoord::new(-1, 1)),
+            MoveLegality::Blocked { at: HexCoord::new(-1, 1) }
+        );
+    }
+
+    #[test]
+    fn test_make_move_reports_outcome() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.clear();
+        for (q, r, piece_type, color) in [
+            (0, -5, PieceType::King, Color::White),
+            (0, 5, PieceType::King, Color::Black),
+            (-3, 0, PieceType::Rook, Color::White),
+            (0, 0, PieceType::Knight, Color::Black),
+        ] {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+
+        let outcome = game.make_move(HexCoord::new(-3, 0), HexCoord::new(0, 0)).unwrap();
+        assert_eq!(outcome.captured, Some(Piece::new(PieceType::Knight, Color::Black)));
+        assert_eq!(outcome.captured_at, Some(HexCoord::new(0, 0)));
+        assert_eq!(outcome.promoted_to, None);
+        assert!(!outcome.en_passant);
+        // The rook now looks straight up the file at the black king
+        assert!(outcome.is_check);
+        assert!(!outcome.is_checkmate);
+
+        let outcome = game.make_move(HexCoord::new(0, 5), HexCoord::new(1, 4)).unwrap();
+        assert_eq!(outcome.captured, None);
+        assert_eq!(outcome.captured_at, None);
+        assert!(!outcome.is_check);
+    }
+
+    #[test]
+    fn test_pawn_promotion() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.clear();
+        for (q, r, piece_type, color) in [
+            (3, -5, PieceType::King, Color::White),
+            (-4, 5, PieceType::King, Color::Black),
+            (0, 4, PieceType::Pawn, Color::White),
+            (2, -2, PieceType::Rook, Color::White),
+        ] {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+        let (f10, f11) = (HexCoord::new(0, 4), HexCoord::new(0, 5));
+        assert!(game.is_promotion(f10, f11));
+        assert_eq!(game.auto_promotion(f10, f11), Some(PieceType::Queen));
+
+        // A promotion must name a piece the variant has, and only a promotion may
+        assert!(matches!(game.make_move(f10, f11), Err(GameError::PromotionRequired)));
+        for piece_type in [PieceType::King, PieceType::Pawn, PieceType::Chancellor] {
+            assert!(matches!(
+                game.make_move_with_promotion(f10, f11, Some(piece_type)),
+                Err(GameError::InvalidPromotion(_))
+            ));
+        }
+        assert!(matches!(
+            game.make_move_with_promotion(HexCoord::new(2, -2), HexCoord::new(2, -1), Some(PieceType::Queen)),
+            Err(GameError::InvalidPromotion(_))
+        ));
+
+        let before = game.clone();
+        let outcome = game.make_move_with_promotion(f10, f11, Some(PieceType::Knight)).unwrap();
+        assert_eq!(outcome.promoted_to, Some(PieceType::Knight));
+        assert_eq!(game.board.get_piece(f11), Some(&Piece::new(PieceType::Knight, Color::White)));
+        let material = game.material_history();
+        assert_eq!(material[1] - material[0], piece_value(PieceType::Knight) - piece_value(PieceType::Pawn));
+
+        game.undo_move().unwrap();
+        assert_eq!(game.board.pieces, before.board.pieces);
+    }
+
+    #[test]
+    fn test_en_passant() {
+        let mut game = Game::new(Variants::glinski_chess());
+        let (f5, f6) = (HexCoord::new(0, -1), HexCoord::new(0, 0));
+        let (e8, e7, e6) = (HexCoord::new(-1, 2), HexCoord::new(-1, 1), HexCoord::new(-1, 0));
+        // A double step can't jump the pawn in front
+        assert!(!game.board.get_valid_moves(f5).contains(&HexCoord::new(0, 1)));
+
+        game.make_move(f5, f6).unwrap();
+        assert!(game.board.get_valid_moves(e8).contains(&e6));
+        game.make_move(e8, e6).unwrap();
+        assert_eq!(game.board.en_passant, Some(EnPassant { target: e7, pawn: e6 }));
+        assert!(game.is_capture(f6, e7));
+
+        let before = game.clone();
+        let outcome = game.make_move(f6, e7).unwrap();
+        assert!(outcome.en_passant);
+        assert_eq!(outcome.captured_at, Some(e6));
+        assert!(game.board.get_piece(e6).is_none());
+        assert_eq!(game.board.en_passant, None);
+
+        game.undo_move().unwrap();
+        assert_eq!(game.board.pieces, before.board.pieces);
+        assert_eq!(game.board.en_passant, before.board.en_passant);
+        assert_eq!(game.position_hash(), before.position_hash());
+    }
+
+    #[test]
+    fn test_custom_pawn_direction() {
+        // White pawns advance along +q here, Black's along -q
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.clear();
+        game.board.pawn_homes.clear();
+        game.board.pawn_movement = PawnMovement::Custom(vec![HexCoord::new(1, 0)]);
+        for (q, r, piece_type, color) in [
+            (-5, 3, PieceType::King, Color::White),
+            (5, -3, PieceType::King, Color::Black),
+            (-2, 0, PieceType::Pawn, Color::White),
+            (-1, 1, PieceType::Pawn, Color::Black),
+        ] {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+        game.board.pawn_homes.insert(HexCoord::new(-2, 0), Color::White);
+
+        // The far edge lies along the pawns' own direction, not the ranks
+        assert!(game.in_promotion_zone(Color::White, HexCoord::new(5, -2)));
+        assert!(!game.in_promotion_zone(Color::White, HexCoord::new(-3, 5)));
+        assert!(game.in_promotion_zone(Color::Black, HexCoord::new(-5, 2)));
+        assert!(!game.in_promotion_zone(Color::Black, HexCoord::new(3, -5)));
+
+        let (home, passed, landed) = (HexCoord::new(-2, 0), HexCoord::new(-1, 0), HexCoord::new(0, 0));
+        game.make_move(home, landed).unwrap();
+        let double_step = Some(EnPassant { target: passed, pawn: landed });
+        assert_eq!(game.board.en_passant, double_step);
+
+        let outcome = game.make_move(HexCoord::new(-1, 1), passed).unwrap();
+        assert!(outcome.en_passant);
+        assert_eq!(outcome.captured_at, Some(landed));
+        game.undo_move().unwrap();
+        assert_eq!(game.board.en_passant, double_step);
+    }
+
+    #[test]
+    fn test_chevron_opening_moves() {
+        let mut game = Game::new(Variants::chevron_chess());
+        let board_type = game.board.board_type.clone();
+        let square = |name: &str| crate::notation::parse_square(&board_type, name).unwrap();
+
+        // Every pawn can step or double-step up its file, and nothing can be taken yet
+        for color in [Color::White, Color::Black] {
+            game.current_player = color;
+            let moves = game.all_legal_moves();
+            assert_eq!(moves.len(), 33);
+            assert!(moves.iter().all(|&(_, to)| !game.board.is_occupied(to)));
+            let pawn_moves = moves
+                .iter()
+                .filter(|&&(from, _)| game.board.get_piece(from).unwrap().piece_type == PieceType::Pawn)
+                .count();
+            assert_eq!(pawn_moves, 18);
+        }
+        game.current_player = Color::White;
+        assert!(game.make_move(square("e1"), square("c5")).is_err());
+
+        // En passant works along Shafran's files as on Gliński's
+        game.make_move(square("e4"), square("e6")).unwrap();
+        game.make_move(square("d8"), square("d6")).unwrap();
+        assert_eq!(game.board.en_passant, Some(EnPassant { target: square("d7"), pawn: square("d6") }));
+        let outcome = game.make_move(square("e6"), square("d7")).unwrap();
+        assert!(outcome.en_passant);
+        assert!(game.board.get_piece(square("d6")).is_none());
+    }
+
+    #[test]
+    fn test_rhombus_opening_moves() {
+        let mut game = Game::new(Variants::rhombus_chess());
+        let board_type = game.board.board_type.clone();
+        let square = |name: &str| crate::notation::parse_square(&board_type, name).unwrap();
+
+        // The queen and bishops have lines out of the camp, but nothing can be taken yet
+        for color in [Color::White, Color::Black] {
+            game.current_player = color;
+            let moves = game.all_legal_moves();
+            assert_eq!(moves.len(), 33);
+            assert!(moves.iter().all(|&(_, to)| !game.board.is_occupied(to)));
+        }
+        game.current_player = Color::White;
+
+        // Once the knights are out each king castles toward its nearer rook
+        game.make_move(square("f1"), square("g3")).unwrap();
+        game.make_move(square("d9"), square("c7")).unwrap();
+        game.make_move(square("g1"), square("h3")).unwrap();
+        game.make_move(square("c9"), square("b7")).unwrap();
+        game.make_move(square("e1"), square("g1")).unwrap();
+        assert_eq!(game.move_history.back().unwrap().castled, Some((square("h1"), square("f1"))));
+        game.make_move(square("e9"), square("c9")).unwrap();
+        assert_eq!(game.move_history.back().unwrap().castled, Some((square("b9"), square("d9"))));
+    }
+
+    #[test]
+    fn test_castling() {
+        let mut variant = Variants::glinski_chess();
+        variant.special_rules.push(SpecialRule::Castling);
+        let mut game = Game::new(variant);
+        game.board.pieces.clear();
+        let (king, rook) = (HexCoord::new(0, -3), HexCoord::new(3, -3));
+        let (king_to, rook_to) = (HexCoord::new(2, -3), HexCoord::new(1, -3));
+        for (coord, piece_type, color) in [
+            (king, PieceType::King, Color::White),
+            (rook, PieceType::Rook, Color::White),
+            (HexCoord::new(0, 4), PieceType::King, Color::Black),
+            (HexCoord::new(1, 2), PieceType::Rook, Color::Black),
+        ] {
+            game.board.place_piece(coord, Piece::new(piece_type, color)).unwrap();
+        }
+        game.board.castling_pieces = [king, rook].into_iter().collect();
+
+        // The black rook watches the cell the king would cross
+        assert_eq!(game.board.castling_rook(king, king_to), Some((rook, rook_to)));
+        assert_eq!(game.explain_move(king, king_to), MoveLegality::CastlesThroughCheck);
+        game.board.remove_piece(HexCoord::new(1, 2));
+        assert!(game.explain_move(king, king_to).is_legal());
+
+        let before = game.clone();
+        game.make_move(king, king_to).unwrap();
+        assert_eq!(game.board.get_piece(rook_to), Some(&Piece::new(PieceType::Rook, Color::White)));
+        assert!(game.board.get_piece(rook).is_none());
+        let castled = game.move_history.back().unwrap();
+        assert_eq!(castled.castled, Some((rook, rook_to)));
+        assert!(game.board.castling_pieces.is_empty());
+
+        game.undo_move().unwrap();
+        assert_eq!(game.board.pieces, before.board.pieces);
+        assert_eq!(game.board.castling_pieces, before.board.castling_pieces);
+        assert_eq!(game.position_hash(), before.position_hash());
+    }
+
     #[test]
     fn test_move_validation() {
         let variant = Variants::mini_hexchess();
@@ -287,4 +1507,151 @@ mod tests {
         let result = game.validate_move(HexCoord::new(0, 0), HexCoord::new(1, 0));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_clock_snapshots_follow_moves() {
+        let mut game = Game::new(Variants::glinski_chess());
+        let snapshot = ClockSnapshot { think_ms: 4_200, remaining_ms: 595_800 };
+        assert!(matches!(game.record_clock(snapshot), Err(GameError::NoMoveMade)));
+
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        game.record_clock(snapshot).unwrap();
+        game.make_move(HexCoord::new(-1, 2), HexCoord::new(-1, 1)).unwrap();
+
+        assert_eq!(game.clock_history(Color::White), vec![Some(snapshot)]);
+        assert_eq!(game.clock_history(Color::Black), vec![None]);
+
+        // Histories saved before clocks existed still load
+        let json = r#"{"from":{"q":0,"r":-1},"to":{"q":0,"r":0},"piece":{"piece_type":"Pawn","color":"White"},"captured_piece":null,"move_number":1}"#;
+        let old: Move = serde_json::from_str(json).unwrap();
+        assert_eq!(old.clock, None);
+    }
+
+    #[test]
+    fn test_material_history() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        game.make_move(HexCoord::new(-1, 2), HexCoord::new(-1, 1)).unwrap();
+        game.make_move(HexCoord::new(0, 0), HexCoord::new(-1, 1)).unwrap();
+        assert_eq!(game.material_history(), vec![0, 0, 0, 100]);
+    }
+
+    #[test]
+    fn test_resignation_and_agreed_draw() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        game.resign(Color::Black).unwrap();
+        assert!(game.is_over());
+        assert_eq!(game.score(), Some((1.0, 0.0)));
+        assert_eq!(game.get_result().map(|result| result.reason).as_deref(), Some("White wins by resignation"));
+        assert!(matches!(game.agree_draw(), Err(GameError::GameOver)));
+
+        // No more moves once someone resigns, so the resignation stands
+        let reply = knight_move(&game);
+        assert!(matches!(game.make_move(reply.0, reply.1), Err(GameError::GameOver)));
+        assert_eq!(game.explain_move(reply.0, reply.1), MoveLegality::GameOver);
+        assert!(game.all_legal_moves().is_empty());
+        assert_eq!(game.game_state, GameState::Resigned(Color::Black));
+
+        let mut game = Game::new(Variants::glinski_chess());
+        assert!(!game.is_over());
+        game.agree_draw().unwrap();
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::Agreement));
+        assert_eq!(game.score(), Some((0.5, 0.5)));
+        assert!(matches!(game.resign(Color::White), Err(GameError::GameOver)));
+        let opening = knight_move(&game);
+        assert!(matches!(game.make_move(opening.0, opening.1), Err(GameError::GameOver)));
+        assert_eq!(game.game_state, GameState::Draw(DrawReason::Agreement));
+    }
+
+    #[test]
+    fn test_captured_pieces_can_be_dropped() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        game.make_move(HexCoord::new(-1, 2), HexCoord::new(-1, 1)).unwrap();
+        game.make_move(HexCoord::new(0, 0), HexCoord::new(-1, 1)).unwrap();
+        assert!(game.reserves.is_empty());
+
+        // f5-f6, e8-e7, f6xe7 puts a pawn in White's hand
+        let mut game = Game::new(Variants::glinski_crazyhouse());
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        game.make_move(HexCoord::new(-1, 2), HexCoord::new(-1, 1)).unwrap();
+        let before_capture = game.clone();
+        game.make_move(HexCoord::new(0, 0), HexCoord::new(-1, 1)).unwrap();
+        assert_eq!(game.reserves.get(Color::White), &[PieceType::Pawn]);
+        let (from, to) = knight_move(&game);
+        game.make_move(from, to).unwrap();
+
+        assert!(matches!(game.drop_piece(PieceType::Knight, HexCoord::new(0, 0)), Err(GameError::NotInReserve(_))));
+        assert!(matches!(game.drop_piece(PieceType::Pawn, HexCoord::new(-1, 1)), Err(GameError::InvalidMove)));
+        let drops = game.legal_drops(PieceType::Pawn);
+        assert!(drops.contains(&HexCoord::new(0, 0)));
+        // Not on White's far edge, where a pawn could never move
+        assert!(!drops.contains(&HexCoord::new(0, 5)));
+
+        let before_drop = game.clone();
+        game.drop_piece(PieceType::Pawn, HexCoord::new(0, 0)).unwrap();
+        assert!(game.reserves.is_empty());
+        assert_eq!(game.board.get_piece(HexCoord::new(0, 0)), Some(&Piece::new(PieceType::Pawn, Color::White)));
+        assert_eq!(game.current_player, Color::Black);
+        assert!(game.move_history.back().unwrap().dropped);
+        assert_eq!(game.material_history().last(), Some(&200));
+
+        game.undo_move().unwrap();
+        assert_eq!(game.reserves, before_drop.reserves);
+        assert_eq!(game.position_hash(), before_drop.position_hash());
+
+        // A resigned game takes no drops either
+        let mut resigned = game.clone();
+        resigned.resign(Color::White).unwrap();
+        assert!(matches!(resigned.drop_piece(PieceType::Pawn, HexCoord::new(0, 0)), Err(GameError::GameOver)));
+        assert!(resigned.legal_drops(PieceType::Pawn).is_empty());
+        game.undo_move().unwrap();
+        game.undo_move().unwrap();
+        assert!(game.reserves.is_empty());
+        assert_eq!(game.position_hash(), before_capture.position_hash());
+
+        let mut game = Game::new(Variants::glinski_chess());
+        assert!(matches!(game.drop_piece(PieceType::Pawn, HexCoord::new(0, 0)), Err(GameError::DropsNotAllowed)));
+    }
+
+    #[test]
+    fn test_atomic_captures() {
+        let mut game = Game::new(Variants::glinski_atomic());
+        game.board.pieces.clear();
+        let place = |game: &mut Game, q, r, piece_type, color| {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        };
+        place(&mut game, -3, 0, PieceType::King, Color::White);
+        place(&mut game, -3, 1, PieceType::Queen, Color::Black);
+        place(&mut game, -3, 4, PieceType::Rook, Color::White);
+        place(&mut game, 2, -4, PieceType::Rook, Color::White);
+        place(&mut game, 3, 0, PieceType::King, Color::Black);
+        place(&mut game, 2, 0, PieceType::Pawn, Color::Black);
+        place(&mut game, 2, 1, PieceType::Pawn, Color::White);
+
+        // The king may not take, and the rook's blast would reach its own king
+        assert_eq!(game.explain_move(HexCoord::new(-3, 0), HexCoord::new(-3, 1)), MoveLegality::KingCannotCapture);
+        assert_eq!(game.explain_move(HexCoord::new(-3, 4), HexCoord::new(-3, 1)), MoveLegality::ExplodesOwnKing);
+
+        // Taking the pawn next to Black's king blows the king up, check or no check;
+        // the capturer goes with it but the neighbouring pawn survives
+        let before = game.clone();
+        assert!(game.explain_move(HexCoord::new(2, -4), HexCoord::new(2, 0)).is_legal());
+        game.make_move(HexCoord::new(2, -4), HexCoord::new(2, 0)).unwrap();
+        assert_eq!(game.game_state, GameState::Exploded(Color::Black));
+        assert_eq!(game.score(), Some((1.0, 0.0)));
+        assert!(game.board.get_piece(HexCoord::new(2, 0)).is_none());
+        assert!(game.board.get_piece(HexCoord::new(2, 1)).is_some());
+        assert_eq!(game.move_history.back().unwrap().exploded.len(), 2);
+
+        game.undo_move().unwrap();
+        assert_eq!(game.board.pieces, before.board.pieces);
+
+        // Touching kings can't give check: taking one would take the other
+        game.board.remove_piece(HexCoord::new(-3, 1));
+        game.board.move_piece(HexCoord::new(3, 0), HexCoord::new(-2, 0)).unwrap();
+        place(&mut game, -3, -2, PieceType::Rook, Color::Black);
+        assert!(!game.is_king_in_check(&game.board, Color::White));
+    }
 }
@@ -0,0 +1,459 @@
+use std::fmt;
+
+use crate::coords::HexCoord;
+use crate::fen::FenError;
+use crate::game::{DrawReason, Game, GameError, GameState, Move};
+use crate::notation::{parse_square, piece_from_letter, piece_letter, NotationError};
+use crate::pieces::Color;
+use crate::variants::VariantRegistry;
+
+/// Movetext lines are wrapped before this many characters, as in PGN
+const LINE_WIDTH: usize = 80;
+
+/// Problems reading an HPGN record
+#[derive(Debug, thiserror::Error)]
+pub enum HpgnError {
+    #[error("Malformed tag {0}")]
+    Tag(String),
+    #[error("No Variant tag")]
+    MissingVariant,
+    #[error("Unknown variant {0}")]
+    UnknownVariant(String),
+    #[error("Comment doesn't close with }}")]
+    UnclosedComment,
+    #[error("Can't read move {ply} ({text}): {source}")]
+    Notation { ply: usize, text: String, source: NotationError },
+    #[error("Illegal move at ply {ply}: {source}")]
+    IllegalMove { ply: usize, source: GameError },
+    #[error("Can't set up the FEN tag: {0}")]
+    Fen(#[from] FenError),
+    #[error("Result {result} doesn't fit how the game ended")]
+    Result { result: String },
+}
+
+/// A game record in HPGN, PGN for hexagonal boards
+///
+/// Tag pairs for the variant, players, date, result and, for games that didn't end
+/// on the board, how they ended; then the numbered moves in standard algebraic
+/// notation with `{comments}`, and the result again:
+///
+/// ```text
+/// [Variant "Gliński's Chess"]
+/// [White "Anna"]
+/// [Black "Ben"]
+/// [Date "2026.10.15"]
+/// [Result "0-1"]
+/// [Termination "resignation"]
+///
+/// 1. f6 {Into the centre} e6 2. Ni3 0-1
+/// ```
+///
+/// Promotions are written `f11=Q` and drops `N@f6`; moves are read with
+/// `Game::parse_move`, so long algebraic works too. Moves are played from the
+/// variant's starting position, or from the position in a `[FEN "..."]` tag when
+/// the game was set up some other way.
+#[derive(Debug, Clone)]
+pub struct Hpgn {
+    pub white: Option<String>,
+    pub black: Option<String>,
+    /// Date in PGN's `YYYY.MM.DD` form
+    pub date: Option<String>,
+    pub game: Game,
+    /// Comment after each ply, indexed like `game.move_history`
+    pub comments: Vec<Option<String>>,
+}
+
+impl Hpgn {
+    /// A record of `game` with no players, date or comments
+    pub fn new(game: Game) -> Self {
+        Self {
+            white: None,
+            black: None,
+            date: None,
+            game,
+            comments: Vec::new(),
+        }
+    }
+
+    /// The result as written in the record: "1-0", "0-1", "1/2-1/2", or "*" while
+    /// the game goes on; stalemates some variants score unevenly come out like "3/4-1/4"
+    pub fn result(&self) -> String {
+        match self.game.score() {
+            Some((white, black)) => format!("{}-{}", format_points(white), format_points(black)),
+            None => "*".to_string(),
+        }
+    }
+
+    /// Read a record whose variant is one of `variants`
+    ///
+    /// Comments before the first move are skipped.
+    pub fn parse(text: &str, variants: &VariantRegistry) -> Result<Self, HpgnError> {
+        let mut tags = Vec::new();
+        let mut lines = text.lines().map(str::trim).peekable();
+        while let Some(line) = lines.next_if(|line| line.is_empty() || line.starts_with('[')) {
+            if !line.is_empty() {
+                tags.push(parse_tag(line)?);
+            }
+        }
+        let movetext: Vec<&str> = lines.collect();
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|(tag, _)| tag == name)
+                .map(|(_, value)| value.clone())
+                .filter(|value| !value.is_empty() && !value.starts_with('?'))
+        };
+
+        let variant = tag("Variant").ok_or(HpgnError::MissingVariant)?;
+        let (_, config) = variants.find_by_name(&variant).ok_or(HpgnError::UnknownVariant(variant))?;
+        let game = match tag("FEN") {
+            Some(fen) => Game::from_fen(config.clone(), &fen)?,
+            None => Game::new(config.clone()),
+        };
+        let mut record = Self {
+            white: tag("White"),
+            black: tag("Black"),
+            date: tag("Date"),
+            game,
+            comments: Vec::new(),
+        };
+
+        let mut result = tag("Result");
+        let movetext = movetext.join("\n");
+        let mut rest = movetext.trim_start();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('{') {
+                let (comment, after) = after.split_once('}').ok_or(HpgnError::UnclosedComment)?;
+                if let Some(last) = record.comments.last_mut() {
+                    *last = Some(comment.split_whitespace().collect::<Vec<_>>().join(" "));
+                }
+                rest = after.trim_start();
+                continue;
+            }
+            let end = rest.find(|c: char| c.is_whitespace() || c == '{').unwrap_or(rest.len());
+            let token = &rest[..end];
+            rest = rest[end..].trim_start();
+
+            if is_result(token) {
+                result = Some(token.to_string());
+                continue;
+            }
+            // "12." and "12..." number the moves that follow
+            let token = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+            if token.is_empty() {
+                continue;
+            }
+            record.play(token)?;
+        }
+
+        record.end(result, tag("Termination"))?;
+        Ok(record)
+    }
+
+    /// Play one move or drop read from the movetext
+    fn play(&mut self, token: &str) -> Result<(), HpgnError> {
+        let game = &mut self.game;
+        let ply = game.move_history.len() + 1;
+        let notation_error = |source| HpgnError::Notation { ply, text: token.to_string(), source };
+        let malformed = || notation_error(NotationError::Malformed(token.to_string()));
+
+        let text = token.trim_end_matches(['+', '#']);
+        let played = match text.split_once('@') {
+            Some((letter, square)) => {
+                let mut letters = letter.chars();
+                let piece_type = match (letters.next(), letters.next()) {
+                    (Some(c), None) if c.is_ascii_uppercase() => piece_from_letter(c).ok_or_else(malformed)?,
+                    _ => return Err(malformed()),
+                };
+                let to = parse_square(&game.board.board_type, square).map_err(notation_error)?;
+                game.drop_piece(piece_type, to)
+            }
+            None => {
+                let parsed = game.parse_move(token).map_err(notation_error)?;
+                game.make_move_with_promotion(parsed.from, parsed.to, parsed.promotion)
+            }
+        };
+        played.map_err(|source| HpgnError::IllegalMove { ply, source })?;
+        self.comments.push(None);
+        Ok(())
+    }
+
+    /// Settle a game that ended off the board by its result and termination tags
+    fn end(&mut self, result: Option<String>, termination: Option<String>) -> Result<(), HpgnError> {
+        let Some(result) = result.filter(|result| result != "*") else {
+            return Ok(());
+        };
+        if self.game.is_over() {
+            return if self.result() == result { Ok(()) } else { Err(HpgnError::Result { result }) };
+        }
+        let loser = match result.as_str() {
+            "1-0" => Some(Color::Black),
+            "0-1" => Some(Color::White),
+            _ => None,
+        };
+        self.game.game_state = match (termination.as_deref(), loser) {
+            (Some("time forfeit"), Some(loser)) => GameState::TimedOut(loser),
+            (_, Some(loser)) => GameState::Resigned(loser),
+            (_, None) if result == "1/2-1/2" => GameState::Draw(DrawReason::Agreement),
+            _ => return Err(HpgnError::Result { result }),
+        };
+        Ok(())
+    }
+
+    /// The position before the first move, found by taking every move back
+    fn starting_position(&self) -> Game {
+        let mut start = self.game.clone();
+        while start.undo_move().is_ok() {}
+        start
+    }
+
+    /// How the game ended, when that isn't plain from the moves
+    fn termination(&self) -> Option<&'static str> {
+        match self.game.game_state {
+            GameState::Resigned(_) => Some("resignation"),
+            GameState::TimedOut(_) => Some("time forfeit"),
+            GameState::Draw(DrawReason::Agreement) => Some("agreement"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Hpgn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unknown = |value: &Option<String>, placeholder: &str| value.clone().unwrap_or_else(|| placeholder.to_string());
+        let result = self.result();
+        write_tag(f, "Variant", &self.game.variant.name)?;
+        write_tag(f, "White", &unknown(&self.white, "?"))?;
+        write_tag(f, "Black", &unknown(&self.black, "?"))?;
+        write_tag(f, "Date", &unknown(&self.date, "????.??.??"))?;
+        write_tag(f, "Result", &result)?;
+        if let Some(termination) = self.termination() {
+            write_tag(f, "Termination", termination)?;
+        }
+        let mut replay = self.starting_position();
+        let fen = replay.to_fen();
+        if fen != Game::new(self.game.variant.clone()).to_fen() {
+            write_tag(f, "SetUp", "1")?;
+            write_tag(f, "FEN", &fen)?;
+        }
+        writeln!(f)?;
+
+        // Replay the game to write each move against the position it was played in; should
+        // the replay ever go astray the rest of the moves are written as plain coordinates
+        let mut tokens = Vec::new();
+        let mut numbered = false;
+        let mut in_step = true;
+        for (ply, game_move) in self.game.move_history.iter().enumerate() {
+            if replay.current_player == Color::White {
+                tokens.push(format!("{}.", replay.fullmove_number));
+            } else if !numbered {
+                tokens.push(format!("{}...", replay.fullmove_number));
+            }
+            let text = if in_step { game_move.to_algebraic(&replay).ok() } else { None };
+            in_step = text.is_some();
+            tokens.push(text.unwrap_or_else(|| coordinates(&replay, game_move)));
+
+            if in_step {
+                let played = if game_move.dropped {
+                    replay.drop_piece(game_move.piece.piece_type, game_move.to)
+                } else {
+                    replay.make_move_with_promotion(game_move.from, game_move.to, game_move.promoted_to)
+                };
+                in_step = played.is_ok();
+            }
+
+            // Black's move after a comment takes its number again
+            numbered = true;
+            if let Some(Some(comment)) = self.comments.get(ply) {
+                tokens.push(format!("{{{}}}", comment.replace('}', ")")));
+                numbered = false;
+            }
+        }
+        tokens.push(result);
+
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() >= LINE_WIDTH {
+                writeln!(f, "{}", line)?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line += &token;
+        }
+        writeln!(f, "{}", line)
+    }
+}
+
+impl Game {
+    /// The game as an HPGN record (see `Hpgn`), with no players or date
+    pub fn to_hpgn(&self) -> String {
+        Hpgn::new(self.clone()).to_string()
+    }
+
+    /// Replay an HPGN record of a game in one of the built-in variants
+    pub fn from_hpgn(text: &str) -> Result<Self, HpgnError> {
+        Ok(Hpgn::parse(text, &VariantRegistry::new())?.game)
+    }
+}
+
+fn write_tag(f: &mut fmt::Formatter, name: &str, value: &str) -> fmt::Result {
+    writeln!(f, "[{} \"{}\"]", name, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_tag(line: &str) -> Result<(String, String), HpgnError> {
+    let malformed = || HpgnError::Tag(line.to_string());
+    let inner = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')).ok_or_else(malformed)?;
+    let (name, value) = inner.split_once(' ').ok_or_else(malformed)?;
+    let value = value.trim().strip_prefix('"').and_then(|value| value.strip_suffix('"')).ok_or_else(malformed)?;
+
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(if c == '\\' { chars.next().ok_or_else(malformed)? } else { c });
+    }
+    Ok((name.to_string(), unescaped))
+}
+
+/// A move as bare coordinates, e.g. "f5f6" or "N@f6", for when it can't be written
+/// against the position
+fn coordinates(game: &Game, game_move: &Move) -> String {
+    let square = |coord: HexCoord| {
+        game.board.board_type.square_name(coord).unwrap_or_else(|| format!("({},{})", coord.q, coord.r))
+    };
+    let promotion = game_move.promoted_to.map(|piece_type| format!("={}", piece_letter(piece_type))).unwrap_or_default();
+    if game_move.dropped {
+        format!("{}@{}", piece_letter(game_move.piece.piece_type), square(game_move.to))
+    } else {
+        format!("{}{}{}", square(game_move.from), square(game_move.to), promotion)
+    }
+}
+
+/// Whether a movetext token is a game result: "1-0", "0-1", "1/2-1/2", "*", or
+/// an uneven score in quarters like "3/4-1/4"
+fn is_result(token: &str) -> bool {
+    const POINTS: [&str; 5] = ["0", "1/4", "1/2", "3/4", "1"];
+    let quarters = |points: &str| POINTS.iter().position(|&written| written == points);
+    token == "*"
+        || token.split_once('-').is_some_and(|(white, black)| {
+            matches!((quarters(white), quarters(black)), (Some(white), Some(black)) if white + black == 4)
+        })
+}
+
+/// Points as PGN writes them: "1", "0", "1/2", or quarters like "3/4"
+fn format_points(points: f32) -> String {
+    match (points * 4.0).round() as u32 {
+        0 => "0".to_string(),
+        2 => "1/2".to_string(),
+        4 => "1".to_string(),
+        quarters => format!("{}/4", quarters),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::HexCoord;
+    use crate::library::GameLibrary;
+    use crate::variants::Variants;
+
+    #[test]
+    fn test_hpgn_round_trip() {
+        let game = GameLibrary::load("queen-raid").unwrap().replay().unwrap();
+        let mut record = Hpgn::new(game.clone());
+        record.white = Some("Anna \"the Hex\"".to_string());
+        record.date = Some("2026.10.15".to_string());
+        record.comments = vec![None; game.move_history.len()];
+        record.comments[0] = Some("A quiet start".to_string());
+
+        let text = record.to_string();
+        assert!(text.starts_with("[Variant \"Gliński's Chess\"]\n[White \"Anna \\\"the Hex\\\"\"]\n[Black \"?\"]\n"), "{}", text);
+        assert!(text.contains("\n1. "));
+        assert!(text.contains("{A quiet start} 1... "), "{}", text);
+        assert!(text.trim_end().ends_with("1-0"));
+        assert!(text.lines().all(|line| line.len() < LINE_WIDTH));
+
+        let read = Hpgn::parse(&text, &VariantRegistry::new()).unwrap();
+        assert_eq!(read.white, record.white);
+        assert_eq!(read.black, None);
+        assert_eq!(read.date, record.date);
+        assert_eq!(read.comments, record.comments);
+        assert_eq!(read.game.position_hash(), game.position_hash());
+        assert_eq!(read.game.game_state, game.game_state);
+        assert_eq!(read.to_string(), text);
+    }
+
+    #[test]
+    fn test_hpgn_drops_and_resignations() {
+        // Crazyhouse: capture whatever can be taken until someone can drop it back in
+        let mut game = Game::new(Variants::glinski_crazyhouse());
+        while game.reserves.get(game.current_player).is_empty() {
+            let moves = game.all_legal_moves();
+            let (from, to) = moves.iter().copied().find(|&(from, to)| game.is_capture(from, to)).unwrap_or(moves[0]);
+            game.make_move(from, to).unwrap();
+        }
+        let piece_type = game.reserves.get(game.current_player)[0];
+        let mut cells: Vec<HexCoord> = game.board.valid_coords.iter().copied().collect();
+        cells.sort_by_key(|coord| (coord.q, coord.r));
+        assert!(cells.into_iter().any(|cell| game.drop_piece(piece_type, cell).is_ok()));
+        game.resign(game.current_player).unwrap();
+        let text = game.to_hpgn();
+        assert!(text.contains(&format!("{}@", piece_letter(piece_type))), "{}", text);
+        assert!(text.contains("[Termination \"resignation\"]"));
+        let read = Game::from_hpgn(&text).unwrap();
+        assert_eq!(read.position_hash(), game.position_hash());
+        assert_eq!(read.game_state, game.game_state);
+
+        let mut game = Game::new(Variants::glinski_chess());
+        game.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        game.resign(Color::Black).unwrap();
+        let read = Game::from_hpgn(&game.to_hpgn()).unwrap();
+        assert_eq!(read.game_state, GameState::Resigned(Color::Black));
+        assert_eq!(read.move_history.len(), 1);
+    }
+
+    #[test]
+    fn test_hpgn_from_a_set_up_position() {
+        // Start from the position after 1. f6, so Black moves first and the record says so
+        let mut opening = Game::new(Variants::glinski_chess());
+        opening.make_move(HexCoord::new(0, -1), HexCoord::new(0, 0)).unwrap();
+        let fen = opening.to_fen();
+        let mut game = Game::from_fen(Variants::glinski_chess(), &fen).unwrap();
+        for _ in 0..3 {
+            let (from, to) = game.all_legal_moves()[0];
+            game.make_move(from, to).unwrap();
+        }
+
+        let text = game.to_hpgn();
+        assert!(text.contains(&format!("[SetUp \"1\"]\n[FEN \"{}\"]\n", fen)), "{}", text);
+        assert!(text.contains("\n1... "), "{}", text);
+        let read = Game::from_hpgn(&text).unwrap();
+        assert_eq!(read.position_hash(), game.position_hash());
+        assert_eq!(read.move_history.len(), 3);
+        assert_eq!(read.to_hpgn(), text);
+
+        // The standard start needs no tag
+        assert!(!Game::new(Variants::glinski_chess()).to_hpgn().contains("[FEN"));
+    }
+
+    #[test]
+    fn test_hpgn_errors() {
+        let read = |text: &str| Game::from_hpgn(text).map(|_| ());
+        assert!(matches!(read("1. f6"), Err(HpgnError::MissingVariant)));
+        assert!(matches!(read("[Variant \"Square Chess\"]"), Err(HpgnError::UnknownVariant(_))));
+        assert!(matches!(read("[Variant Gliński's Chess]"), Err(HpgnError::Tag(_))));
+        assert!(matches!(read("[Variant \"Gliński's Chess\"]\n\n1. f6 {unfinished"), Err(HpgnError::UnclosedComment)));
+        assert!(matches!(
+            read("[Variant \"Gliński's Chess\"]\n\n1. f6 f6"),
+            Err(HpgnError::Notation { ply: 2, .. })
+        ));
+        assert!(matches!(read("[Variant \"Gliński's Chess\"]\n\n1. f6 3/4-1/4"), Err(HpgnError::Result { .. })));
+        assert!(matches!(
+            read("[Variant \"Gliński's Chess\"]\n[FEN \"not a position\"]"),
+            Err(HpgnError::Fen(_))
+        ));
+        assert_eq!(format_points(0.75), "3/4");
+        assert!(is_result("1/4-3/4"));
+        assert!(!is_result("3/4") && !is_result("f6/4") && !is_result("1-1"));
+    }
+}
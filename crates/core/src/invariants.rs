@@ -0,0 +1,185 @@
+use crate::board::splitmix64;
+use crate::coords::HexCoord;
+use crate::game::Game;
+use crate::pieces::{Color, PieceType};
+
+/// A broken rules-engine invariant, as found by `check_game`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvariantViolation {
+    #[error("{color:?} has {count} kings")]
+    KingCount { color: Color, count: usize },
+    #[error("Piece at {0:?} is off the board")]
+    PieceOffBoard(HexCoord),
+    #[error("Stored position hash {stored:#x} does not match recomputed {recomputed:#x}")]
+    HashMismatch { stored: u64, recomputed: u64 },
+    #[error("Making and undoing {from:?} -> {to:?} did not restore the game")]
+    UndoMismatch { from: HexCoord, to: HexCoord },
+    #[error("Legal move {from:?} -> {to:?} leaves the mover's king attacked")]
+    KingLeftAttacked { from: HexCoord, to: HexCoord },
+    #[error("Legal move {from:?} -> {to:?} was rejected: {reason}")]
+    LegalMoveRejected { from: HexCoord, to: HexCoord, reason: String },
+}
+
+/// Check every invariant on a game, returning the first violation
+///
+/// Meant for tests and fuzz targets: it makes and undoes every legal move, so
+/// it is far too slow to run during play.
+pub fn check_game(game: &Game) -> Result<(), InvariantViolation> {
+    check_kings(game)?;
+    check_pieces_on_board(game)?;
+    check_hash(game)?;
+
+    for (from, to) in game.all_legal_moves() {
+        check_move(game, from, to)?;
+    }
+
+    Ok(())
+}
+
+/// Each side has exactly one king, unless a variant where kings can be taken has just ended
+pub fn check_kings(game: &Game) -> Result<(), InvariantViolation> {
+    for color in [Color::White, Color::Black] {
+        let count = game
+            .board
+            .pieces
+            .values()
+            .filter(|piece| piece.color == color && piece.piece_type == PieceType::King)
+            .count();
+        if count != 1 && !(count == 0 && game.is_over()) {
+            return Err(InvariantViolation::KingCount { color, count });
+        }
+    }
+    Ok(())
+}
+
+/// Every piece stands on a cell of the board
+pub fn check_pieces_on_board(game: &Game) -> Result<(), InvariantViolation> {
+    let mut coords: Vec<_> = game.board.pieces.keys().copied().collect();
+    coords.sort_by_key(|coord| (coord.q, coord.r));
+    match coords.into_iter().find(|&coord| !game.board.is_valid_coord(coord)) {
+        Some(coord) => Err(InvariantViolation::PieceOffBoard(coord)),
+        None => Ok(()),
+    }
+}
+
+/// The last recorded position hash matches one computed from scratch
+pub fn check_hash(game: &Game) -> Result<(), InvariantViolation> {
+    let recomputed = game.position_hash();
+    match game.position_history.last() {
+        Some(&stored) if stored != recomputed => Err(InvariantViolation::HashMismatch { stored, recomputed }),
+        _ => Ok(()),
+    }
+}
+
+/// A legal move can be made, does not leave the king attacked, and undoes cleanly
+pub fn check_move(game: &Game, from: HexCoord, to: HexCoord) -> Result<(), InvariantViolation> {
+    let mover = game.current_player;
+    let mut after = game.clone();
+    if let Err(e) = after.make_move_with_promotion(from, to, game.auto_promotion(from, to)) {
+        return Err(InvariantViolation::LegalMoveRejected { from, to, reason: e.to_string() });
+    }
+
+    // Only where check applies: not in the dark, nor for touching kings when captures explode
+    let opponent = match mover {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    };
+    let kings = (after.board.get_king(mover), after.board.get_king(opponent));
+    let touching = matches!(kings, (Some(king), Some(other)) if king.neighbors().contains(&other));
+    let unchecked = game.variant.has_fog_of_war() || (touching && game.variant.has_atomic_captures());
+    if let (Some(king), false) = (kings.0, unchecked) {
+        let (white, black) = after.board.attackers(king);
+        let attackers = match mover {
+            Color::White => black,
+            Color::Black => white,
+        };
+        if !attackers.is_empty() {
+            return Err(InvariantViolation::KingLeftAttacked { from, to });
+        }
+    }
+
+    check_hash(&after)?;
+
+    if after.undo_move().is_err()
+        || after.board.pieces != game.board.pieces
+        || after.reserves != game.reserves
+        || after.current_player != game.current_player
+        || after.game_state != game.game_state
+        || after.position_history != game.position_history
+        || after.move_history.len() != game.move_history.len()
+    {
+        return Err(InvariantViolation::UndoMismatch { from, to });
+    }
+
+    Ok(())
+}
+
+impl Game {
+    /// Play up to `max_plies` random legal moves, stopping early if the game ends
+    ///
+    /// The same seed from the same position always plays the same moves.
+    /// Returns the number of plies played.
+    pub fn play_random(&mut self, seed: u64, max_plies: usize) -> usize {
+        let mut state = seed;
+        for ply in 0..max_plies {
+            let moves = self.all_legal_moves();
+            if self.is_over() || moves.is_empty() {
+                return ply;
+            }
+            state = splitmix64(state);
+            let (from, to) = moves[(state % moves.len() as u64) as usize];
+            self.make_move_with_promotion(from, to, self.auto_promotion(from, to))
+                .expect("all_legal_moves only returns legal moves");
+        }
+        max_plies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pieces::Piece;
+    use crate::variants::Variants;
+
+    #[test]
+    fn test_random_games_keep_invariants() {
+        for (seed, variant) in [
+            Variants::glinski_chess(),
+            Variants::mccooey_chess(),
+            Variants::mini_hexchess(),
+            Variants::glinski_crazyhouse(),
+            Variants::glinski_atomic(),
+            Variants::glinski_dark(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut game = Game::new(variant);
+            let plies = game.play_random(seed as u64, 30);
+
+            // The same seed plays the same game
+            let mut again = Game::new(game.variant.clone());
+            assert_eq!(again.play_random(seed as u64, 30), plies);
+            assert_eq!(again.position_hash(), game.position_hash());
+
+            // Walk back through every position reached
+            for _ in 0..plies {
+                check_game(&game).unwrap_or_else(|e| panic!("{}: {}", game.variant.name, e));
+                game.undo_move().unwrap();
+            }
+            check_game(&game).unwrap_or_else(|e| panic!("{}: {}", game.variant.name, e));
+        }
+    }
+
+    #[test]
+    fn test_violations_are_reported() {
+        let mut game = Game::new(Variants::mini_hexchess());
+        game.board.place_piece(HexCoord::new(0, 0), Piece::new(PieceType::King, Color::White)).unwrap();
+        assert_eq!(check_kings(&game), Err(InvariantViolation::KingCount { color: Color::White, count: 2 }));
+        assert!(matches!(check_hash(&game), Err(InvariantViolation::HashMismatch { .. })));
+
+        let mut game = Game::new(Variants::mini_hexchess());
+        game.board.pieces.insert(HexCoord::new(5, 5), Piece::new(PieceType::Pawn, Color::Black));
+        assert_eq!(check_pieces_on_board(&game), Err(InvariantViolation::PieceOffBoard(HexCoord::new(5, 5))));
+    }
+}
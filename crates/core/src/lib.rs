@@ -3,9 +3,35 @@ pub mod board;
 pub mod pieces;
 pub mod game;
 pub mod variants;
+pub mod move_tables;
+pub mod library;
+mod pawn_structure;
+pub mod ordering;
+pub mod tablebase;
+pub mod opening;
+pub mod invariants;
+pub mod notation;
+pub mod drills;
+pub mod session;
+pub mod fog;
+pub mod thumbnail;
+pub mod fen;
+pub mod hpgn;
 
 pub use coords::*;
 pub use board::*;
 pub use pieces::*;
 pub use game::*;
 pub use variants::*;
+pub use move_tables::*;
+pub use library::*;
+pub use ordering::*;
+pub use tablebase::*;
+pub use opening::*;
+pub use notation::*;
+pub use drills::*;
+pub use session::*;
+pub use fog::*;
+pub use thumbnail::*;
+pub use fen::*;
+pub use hpgn::*;
@@ -0,0 +1,315 @@
+use crate::coords::HexCoord;
+use crate::game::{ClockSnapshot, Game, GameError};
+use crate::notation::{parse_san, NotationError};
+use crate::pieces::PieceType;
+use crate::variants::{VariantConfig, Variants};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Bundled sample games, one JSON document each
+const SAMPLE_GAMES: &[&str] = &[
+    include_str!("../data/games/opening-principles.json"),
+    include_str!("../data/games/knight-shuffle-draw.json"),
+    include_str!("../data/games/queen-raid.json"),
+];
+
+/// A single move of a sample game, with an optional annotation
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SampleMove {
+    pub from: HexCoord,
+    pub to: HexCoord,
+    /// Piece a pawn promoted to with this move
+    #[serde(default)]
+    pub promotion: Option<PieceType>,
+    /// Piece dropped from the reserve onto `to`, for drops; `from` is `to` as well
+    #[serde(default)]
+    pub drop: Option<PieceType>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Clock reading when the move was made, for games recorded with clocks
+    #[serde(default)]
+    pub clock: Option<ClockSnapshot>,
+}
+
+/// An annotated game shipped with the library
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SampleGame {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    /// Name of the variant, as in `VariantConfig::name`
+    pub variant: String,
+    pub moves: Vec<SampleMove>,
+}
+
+/// Summary of a sample game for menus
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SampleGameInfo {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub variant: String,
+    pub plies: usize,
+}
+
+impl SampleGame {
+    /// Look up the variant this game was played in
+    pub fn variant_config(&self) -> Result<VariantConfig, LibraryError> {
+        Variants::all()
+            .into_iter()
+            .find(|variant| variant.name == self.variant)
+            .ok_or_else(|| LibraryError::UnknownVariant(self.variant.clone()))
+    }
+
+    /// Record a game played from its variant's starting position, so it can be replayed like a bundled one
+    pub fn from_game(game: &Game, id: &str, title: &str, description: &str) -> Self {
+        let moves = game
+            .move_history
+            .iter()
+            .map(|game_move| SampleMove {
+                from: game_move.from,
+                to: game_move.to,
+                promotion: game_move.promoted_to,
+                drop: game_move.dropped.then_some(game_move.piece.piece_type),
+                comment: None,
+                clock: game_move.clock,
+            })
+            .collect();
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            variant: game.variant.name.clone(),
+            moves,
+        }
+    }
+
+    /// Read a game the player pasted or dropped in: either one exported as JSON,
+    /// or a list of moves in standard algebraic notation played in `variant`
+    ///
+    /// Move numbers ("12." or "12...") and a trailing result are skipped.
+    pub fn import(text: &str, variant: VariantConfig) -> Result<Self, LibraryError> {
+        let text = text.trim();
+        if text.starts_with('{') {
+            let game: SampleGame = serde_json::from_str(text)?;
+            game.replay()?;
+            return Ok(game);
+        }
+
+        let mut game = Game::new(variant);
+        for token in text.split_whitespace() {
+            if ["1-0", "0-1", "½-½", "1/2-1/2", "*"].contains(&token) {
+                continue;
+            }
+            // "12.f6" carries its move number along
+            let token = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+            if token.is_empty() {
+                continue;
+            }
+            let ply = game.move_history.len() + 1;
+            let import_error = |source| LibraryError::Notation { ply, text: token.to_string(), source };
+            let parsed = parse_san(&game, token).map_err(import_error)?;
+            let promotion = parsed.promotion.or_else(|| game.auto_promotion(parsed.from, parsed.to));
+            game.make_move_with_promotion(parsed.from, parsed.to, promotion)
+                .map_err(|source| LibraryError::IllegalMove { ply, source })?;
+        }
+        if game.move_history.is_empty() {
+            return Err(LibraryError::Empty);
+        }
+        Ok(Self::from_game(&game, "imported", "Imported game", ""))
+    }
+
+    /// Replay the first `plies` moves from the starting position
+    pub fn replay_to(&self, plies: usize) -> Result<Game, LibraryError> {
+        let mut game = Game::new(self.variant_config()?);
+
+        for (ply, sample_move) in self.moves.iter().take(plies).enumerate() {
+            match sample_move.drop {
+                Some(piece_type) => game.drop_piece(piece_type, sample_move.to),
+                None => game.make_move_with_promotion(sample_move.from, sample_move.to, sample_move.promotion),
+            }
+            .map_err(|source| LibraryError::IllegalMove { ply: ply + 1, source })?;
+            if let Some(clock) = sample_move.clock {
+                game.record_clock(clock)?;
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Remaining (white, black) clock time in milliseconds after the first
+    /// `plies` moves, or `None` if any move lacks a clock reading
+    pub fn clocks_at(&self, plies: usize) -> Option<(u64, u64)> {
+        let clocks: Vec<ClockSnapshot> = self.moves.iter().map(|sample_move| sample_move.clock).collect::<Option<_>>()?;
+
+        let remaining = |first: usize| {
+            let opening = clocks.get(first)?;
+            // Before its first move a side has what it was left with plus the time it spent
+            let mut left = opening.remaining_ms + opening.think_ms;
+            for clock in clocks.iter().take(plies).skip(first).step_by(2) {
+                left = clock.remaining_ms;
+            }
+            Some(left)
+        };
+        Some((remaining(0)?, remaining(1)?))
+    }
+
+    /// Replay the whole game
+    pub fn replay(&self) -> Result<Game, LibraryError> {
+        self.replay_to(self.moves.len())
+    }
+
+    pub fn info(&self) -> SampleGameInfo {
+        SampleGameInfo {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            variant: self.variant.clone(),
+            plies: self.moves.len(),
+        }
+    }
+}
+
+/// The bundled collection of sample games
+pub struct GameLibrary;
+
+impl GameLibrary {
+    /// List the bundled games in display order
+    pub fn list() -> Vec<SampleGameInfo> {
+        SAMPLE_GAMES
+            .iter()
+            .filter_map(|data| serde_json::from_str::<SampleGame>(data).ok())
+            .map(|game| game.info())
+            .collect()
+    }
+
+    /// Load a bundled game by id
+    pub fn load(id: &str) -> Result<SampleGame, LibraryError> {
+        for data in SAMPLE_GAMES {
+            let game: SampleGame = serde_json::from_str(data)?;
+            if game.id == id {
+                return Ok(game);
+            }
+        }
+
+        Err(LibraryError::UnknownGame(id.to_string()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LibraryError {
+    #[error("No sample game with id {0}")]
+    UnknownGame(String),
+    #[error("Unknown variant {0}")]
+    UnknownVariant(String),
+    #[error("Illegal move at ply {ply}: {source}")]
+    IllegalMove { ply: usize, source: GameError },
+    #[error("Can't read move {ply} ({text}): {source}")]
+    Notation { ply: usize, text: String, source: NotationError },
+    #[error("No moves found")]
+    Empty,
+    #[error("Invalid game: {0}")]
+    InvalidGame(#[from] GameError),
+    #[error("Malformed game data: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_sample_game_parses() {
+        assert_eq!(GameLibrary::list().len(), SAMPLE_GAMES.len());
+        for data in SAMPLE_GAMES {
+            serde_json::from_str::<SampleGame>(data).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_every_sample_game_replays() {
+        for info in GameLibrary::list() {
+            let game = GameLibrary::load(&info.id).unwrap();
+            let replayed = game.replay().unwrap_or_else(|e| panic!("{}: {}", info.id, e));
+            assert_eq!(replayed.move_history.len(), info.plies);
+        }
+    }
+
+    #[test]
+    fn test_sample_game_clocks() {
+        let raid = GameLibrary::load("queen-raid").unwrap();
+        assert_eq!(raid.clocks_at(0), Some((300_000, 300_000)));
+        assert_eq!(raid.clocks_at(1), Some((296_800, 300_000)));
+        assert_eq!(raid.clocks_at(2), Some((296_800, 258_500)));
+        assert_eq!(raid.clocks_at(5), Some((275_400, 231_200)));
+
+        let replayed = raid.replay().unwrap();
+        assert!(replayed.clock_history(crate::pieces::Color::White).iter().all(Option::is_some));
+
+        let untimed = GameLibrary::load("opening-principles").unwrap();
+        assert_eq!(untimed.clocks_at(0), None);
+    }
+
+    #[test]
+    fn test_sample_game_endings() {
+        use crate::game::{DrawReason, GameState};
+
+        let raid = GameLibrary::load("queen-raid").unwrap().replay().unwrap();
+        assert!(matches!(raid.game_state, GameState::Checkmate(_)));
+        assert_eq!(raid.score(), Some((1.0, 0.0)));
+
+        let shuffle = GameLibrary::load("knight-shuffle-draw").unwrap().replay().unwrap();
+        assert_eq!(shuffle.game_state, GameState::Draw(DrawReason::Repetition));
+
+        let opening = GameLibrary::load("opening-principles").unwrap();
+        assert_eq!(opening.replay_to(2).unwrap().move_history.len(), 2);
+    }
+
+    #[test]
+    fn test_recorded_game_replays() {
+        let mut game = Game::new(Variants::glinski_crazyhouse());
+        // f5-f6, e8-e7, f6xe7, any quiet black move, then drop the pawn on f6
+        for (from, to) in [((0, -1), (0, 0)), ((-1, 2), (-1, 1)), ((0, 0), (-1, 1))] {
+            game.make_move(HexCoord::new(from.0, from.1), HexCoord::new(to.0, to.1)).unwrap();
+        }
+        let (from, to) = game
+            .all_legal_moves()
+            .into_iter()
+            .find(|&(_, to)| !game.board.is_occupied(to) && to != HexCoord::new(0, 0))
+            .unwrap();
+        game.make_move(from, to).unwrap();
+        game.drop_piece(PieceType::Pawn, HexCoord::new(0, 0)).unwrap();
+
+        let recorded = SampleGame::from_game(&game, "local-1", "Practice", "");
+        assert_eq!(recorded.info().plies, 5);
+        assert_eq!(recorded.moves[4].drop, Some(PieceType::Pawn));
+        let json = serde_json::to_string(&recorded).unwrap();
+        let replayed = serde_json::from_str::<SampleGame>(&json).unwrap().replay().unwrap();
+        assert_eq!(replayed.position_hash(), game.position_hash());
+    }
+
+    #[test]
+    fn test_import() {
+        // A recorded game comes back whole
+        let raid = GameLibrary::load("queen-raid").unwrap();
+        let exported = serde_json::to_string(&raid).unwrap();
+        let imported = SampleGame::import(&exported, Variants::glinski_chess()).unwrap();
+        assert_eq!(imported.moves.len(), raid.moves.len());
+
+        // f5-f6, e8-e7, f6xe7 as a numbered move list
+        let imported = SampleGame::import("1. f6 e7 2.fxe7 *", Variants::glinski_chess()).unwrap();
+        assert_eq!(imported.moves.len(), 3);
+        assert_eq!(imported.moves[2].to, HexCoord::new(-1, 1));
+
+        assert!(matches!(
+            SampleGame::import("1. f6 Qz9", Variants::glinski_chess()),
+            Err(LibraryError::Notation { ply: 2, .. })
+        ));
+        assert!(matches!(SampleGame::import("  1-0 ", Variants::glinski_chess()), Err(LibraryError::Empty)));
+    }
+
+    #[test]
+    fn test_load_unknown_game() {
+        assert!(matches!(GameLibrary::load("no-such-game"), Err(LibraryError::UnknownGame(_))));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::coords::{HexCoord, BoardType};
+use crate::pieces::{DIAGONAL_DIRECTIONS, KNIGHT_OFFSETS, ORTHOGONAL_DIRECTIONS};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+/// Most board shapes whose tables are kept at once; custom shapes can come from anywhere
+const CACHED_SHAPES: usize = 16;
+
+/// Precomputed, occupancy-independent move geometry for one board shape
+///
+/// Knight leaps, king steps and sliding rays from a cell only depend on the
+/// board's cells, so they are derived once per `BoardType` and shared.
+#[derive(Debug, Default)]
+pub struct MoveTables {
+    king: HashMap<HexCoord, Vec<HexCoord>>,
+    knight: HashMap<HexCoord, Vec<HexCoord>>,
+    orthogonal_rays: HashMap<HexCoord, Vec<Vec<HexCoord>>>,
+    diagonal_rays: HashMap<HexCoord, Vec<Vec<HexCoord>>>,
+}
+
+impl MoveTables {
+    /// Build the tables for an arbitrary set of cells
+    pub fn new(valid_coords: &HashSet<HexCoord>) -> Self {
+        let mut tables = Self::default();
+
+        for &from in valid_coords {
+            let steps = ORTHOGONAL_DIRECTIONS
+                .iter()
+                .chain(DIAGONAL_DIRECTIONS.iter())
+                .map(|&direction| from + direction)
+                .filter(|coord| valid_coords.contains(coord))
+                .collect();
+            tables.king.insert(from, steps);
+
+            let leaps = KNIGHT_OFFSETS
+                .iter()
+                .map(|&offset| from + offset)
+                .filter(|coord| valid_coords.contains(coord))
+                .collect();
+            tables.knight.insert(from, leaps);
+
+            tables.orthogonal_rays.insert(from, Self::rays(from, &ORTHOGONAL_DIRECTIONS, valid_coords));
+            tables.diagonal_rays.insert(from, Self::rays(from, &DIAGONAL_DIRECTIONS, valid_coords));
+        }
+
+        tables
+    }
+
+    /// Get the shared tables for a board type, building them on first use
+    ///
+    /// At most `CACHED_SHAPES` shapes are kept; past that, an older shape is
+    /// dropped and rebuilt if it comes back.
+    pub fn for_board_type(board_type: &BoardType) -> Arc<MoveTables> {
+        let mut cache = Self::cache();
+
+        // Look up before inserting, so custom boards aren't cloned on every call
+        if let Some(tables) = cache.get(board_type) {
+            return Arc::clone(tables);
+        }
+        if cache.len() >= CACHED_SHAPES {
+            if let Some(evicted) = cache.keys().next().cloned() {
+                cache.remove(&evicted);
+            }
+        }
+        let tables = Arc::new(MoveTables::new(&board_type.valid_coords()));
+        cache.insert(board_type.clone(), Arc::clone(&tables));
+        tables
+    }
+
+    fn cache() -> MutexGuard<'static, HashMap<BoardType, Arc<MoveTables>>> {
+        static CACHE: OnceLock<Mutex<HashMap<BoardType, Arc<MoveTables>>>> = OnceLock::new();
+
+        CACHE
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Cells a king can step to from `from` (orthogonal and diagonal neighbours)
+    pub fn king_steps(&self, from: HexCoord) -> &[HexCoord] {
+        self.king.get(&from).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Cells a knight can leap to from `from`
+    pub fn knight_leaps(&self, from: HexCoord) -> &[HexCoord] {
+        self.knight.get(&from).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Rook rays from `from`, each ordered from the nearest cell outward
+    pub fn orthogonal_rays(&self, from: HexCoord) -> &[Vec<HexCoord>] {
+        self.orthogonal_rays.get(&from).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Bishop rays from `from`, each ordered from the nearest cell outward
+    pub fn diagonal_rays(&self, from: HexCoord) -> &[Vec<HexCoord>] {
+        self.diagonal_rays.get(&from).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    fn rays(from: HexCoord, directions: &[HexCoord], valid_coords: &HashSet<HexCoord>) -> Vec<Vec<HexCoord>> {
+        directions
+            .iter()
+            .map(|&direction| {
+                let mut ray = Vec::new();
+                let mut current = from + direction;
+                while valid_coords.contains(&current) {
+                    ray.push(current);
+                    current = current + direction;
+                }
+                ray
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_are_cached_per_board_type() {
+        let first = MoveTables::for_board_type(&BoardType::Regular { radius: 5 });
+        let second = MoveTables::for_board_type(&BoardType::Regular { radius: 5 });
+        let other = MoveTables::for_board_type(&BoardType::Regular { radius: 2 });
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn test_cache_stays_bounded() {
+        let shape = |far: i32| BoardType::custom([HexCoord::new(0, 0), HexCoord::new(1, 0), HexCoord::new(far, 100)]);
+        let first = MoveTables::for_board_type(&shape(0));
+        for far in 1..=CACHED_SHAPES as i32 * 2 {
+            MoveTables::for_board_type(&shape(far));
+        }
+        assert!(MoveTables::cache().len() <= CACHED_SHAPES);
+
+        // Tables handed out before an eviction stay usable, and come back on request
+        assert_eq!(first.king_steps(HexCoord::new(0, 0)), [HexCoord::new(1, 0)]);
+        assert_eq!(MoveTables::for_board_type(&shape(0)).king_steps(HexCoord::new(0, 0)), [HexCoord::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_king_steps_respect_board_edges() {
+        let tables = MoveTables::for_board_type(&BoardType::Regular { radius: 2 });
+
+        assert_eq!(tables.king_steps(HexCoord::new(0, 0)).len(), 12);
+        // A corner cell keeps 3 orthogonal neighbours and 2 diagonal ones
+        assert_eq!(tables.king_steps(HexCoord::new(2, 0)).len(), 5);
+        assert!(tables.king_steps(HexCoord::new(7, 7)).is_empty());
+    }
+
+    #[test]
+    fn test_rays_run_outward_to_the_edge() {
+        let tables = MoveTables::for_board_type(&BoardType::Regular { radius: 5 });
+        let rays = tables.orthogonal_rays(HexCoord::new(0, 0));
+
+        assert_eq!(rays.len(), 6);
+        for (ray, direction) in rays.iter().zip(ORTHOGONAL_DIRECTIONS) {
+            assert_eq!(ray.len(), 5);
+            for (i, coord) in ray.iter().enumerate() {
+                let steps = i as i32 + 1;
+                assert_eq!(*coord, HexCoord::new(direction.q * steps, direction.r * steps));
+            }
+        }
+    }
+}
@@ -0,0 +1,591 @@
+use crate::coords::{BoardType, HexCoord};
+use crate::game::{Game, Move, MoveLegality};
+use crate::pieces::{Color, Piece, PieceType};
+
+/// A move read from text, not yet checked against a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedMove {
+    pub from: HexCoord,
+    pub to: HexCoord,
+    pub promotion: Option<PieceType>,
+}
+
+/// Upper-case letter used for a piece type in notation
+pub fn piece_letter(piece_type: PieceType) -> char {
+    Piece::new(piece_type, Color::White).symbol()
+}
+
+/// Unicode chess symbol for a piece, e.g. '♘' for a white knight
+///
+/// The chancellor and archbishop have no symbol of their own, so they fall back
+/// to their letters, upper case for White and lower case for Black.
+pub fn piece_figurine(piece: Piece) -> char {
+    let (white, black) = match piece.piece_type {
+        PieceType::King => ('♔', '♚'),
+        PieceType::Queen => ('♕', '♛'),
+        PieceType::Rook => ('♖', '♜'),
+        PieceType::Bishop => ('♗', '♝'),
+        PieceType::Knight => ('♘', '♞'),
+        PieceType::Pawn => ('♙', '♟'),
+        PieceType::Chancellor | PieceType::Archbishop => return piece.symbol(),
+    };
+    match piece.color {
+        Color::White => white,
+        Color::Black => black,
+    }
+}
+
+/// Piece type for a notation letter, in either case
+pub fn piece_from_letter(letter: char) -> Option<PieceType> {
+    [
+        PieceType::King,
+        PieceType::Queen,
+        PieceType::Rook,
+        PieceType::Bishop,
+        PieceType::Knight,
+        PieceType::Pawn,
+        PieceType::Chancellor,
+        PieceType::Archbishop,
+    ]
+    .into_iter()
+    .find(|&piece_type| piece_letter(piece_type) == letter.to_ascii_uppercase())
+}
+
+/// Name of a cell in the board's labelling, e.g. "f5"
+pub fn square_name(board: &BoardType, coord: HexCoord) -> Result<String, NotationError> {
+    board.square_name(coord).ok_or(NotationError::NoSquareName(coord))
+}
+
+/// Parse a cell name such as "f5" or "k10"
+pub fn parse_square(board: &BoardType, text: &str) -> Result<HexCoord, NotationError> {
+    let (square, rest) = split_square(board, text)?;
+    if !rest.is_empty() {
+        return Err(NotationError::InvalidSquare(text.to_string()));
+    }
+    Ok(square)
+}
+
+/// Engine-style coordinate move: from, to and an optional lower-case promotion letter, e.g. "g4g5q"
+pub fn format_coordinate(
+    board: &BoardType,
+    from: HexCoord,
+    to: HexCoord,
+    promotion: Option<PieceType>,
+) -> Result<String, NotationError> {
+    let mut text = square_name(board, from)? + &square_name(board, to)?;
+    if let Some(piece_type) = promotion {
+        text.push(piece_letter(piece_type).to_ascii_lowercase());
+    }
+    Ok(text)
+}
+
+/// Parse an engine-style coordinate move such as "g4g5" or "f10f11q"
+pub fn parse_coordinate(board: &BoardType, text: &str) -> Result<ParsedMove, NotationError> {
+    let (from, rest) = split_square(board, text)?;
+    let (to, rest) = split_square(board, rest)?;
+    let promotion = parse_promotion_letter(rest, text)?;
+    Ok(ParsedMove { from, to, promotion })
+}
+
+/// Drop of a piece from the reserve, e.g. "N@f6"
+pub fn format_drop(board: &BoardType, piece_type: PieceType, to: HexCoord) -> Result<String, NotationError> {
+    Ok(format!("{}@{}", piece_letter(piece_type), square_name(board, to)?))
+}
+
+/// Long algebraic notation for a move in the game's current position,
+/// e.g. "g4-g5", "e4xf5" or "Nd3-e5"
+pub fn format_long_algebraic(game: &Game, from: HexCoord, to: HexCoord) -> Result<String, NotationError> {
+    let piece = game.board.get_piece(from).ok_or(NotationError::Illegal(MoveLegality::NoPiece))?;
+    let mut text = String::new();
+    if piece.piece_type != PieceType::Pawn {
+        text.push(piece_letter(piece.piece_type));
+    }
+    let board = &game.board.board_type;
+    text += &square_name(board, from)?;
+    text.push(if game.is_capture(from, to) { 'x' } else { '-' });
+    text += &square_name(board, to)?;
+    Ok(text)
+}
+
+/// Parse long algebraic notation against the game's current position
+///
+/// The piece letter and capture marker must agree with the position and the
+/// move must be legal. A trailing `+` or `#` is accepted and ignored.
+pub fn parse_long_algebraic(game: &Game, text: &str) -> Result<ParsedMove, NotationError> {
+    let trimmed = text.trim_end_matches(['+', '#']);
+    let letter = match trimmed.chars().next() {
+        Some(c) if c.is_ascii_uppercase() => {
+            Some(piece_from_letter(c).ok_or_else(|| NotationError::Malformed(text.to_string()))?)
+        }
+        _ => None,
+    };
+    let body = if letter.is_some() { &trimmed[1..] } else { trimmed };
+
+    let board = &game.board.board_type;
+    let (from, rest) = split_square(board, body)?;
+    let capture = match rest.chars().next() {
+        Some('-') => false,
+        Some('x') => true,
+        _ => return Err(NotationError::Malformed(text.to_string())),
+    };
+    let (to, rest) = split_square(board, &rest[1..])?;
+    let promotion = match rest.strip_prefix('=') {
+        Some(promotion) => parse_promotion_letter(&promotion.to_ascii_lowercase(), text)?,
+        None if rest.is_empty() => None,
+        None => return Err(NotationError::Malformed(text.to_string())),
+    };
+
+    let legality = game.explain_move(from, to);
+    if !legality.is_legal() {
+        return Err(NotationError::Illegal(legality));
+    }
+    let piece = game.board.get_piece(from).map(|piece| piece.piece_type);
+    if piece != Some(letter.unwrap_or(PieceType::Pawn)) || capture != game.is_capture(from, to) {
+        return Err(NotationError::Mismatch(text.to_string()));
+    }
+
+    Ok(ParsedMove { from, to, promotion })
+}
+
+/// Standard algebraic notation for a legal move in the game's current position,
+/// e.g. "f6", "Ni3", "exf5", "Q3f6" or "Qcf6#"; a pawn reaching the far edge promotes
+/// as the variant does by default (see `format_san_with_promotion`)
+pub fn format_san(game: &Game, from: HexCoord, to: HexCoord) -> Result<String, NotationError> {
+    format_san_with_promotion(game, from, to, game.auto_promotion(from, to))
+}
+
+/// Standard algebraic notation for a legal move, promoting to `promotion`, e.g. "f11=Q+"
+///
+/// Another piece of the same type reaching the same cell is told apart by
+/// file, then by rank, then by the full origin square.
+pub fn format_san_with_promotion(
+    game: &Game,
+    from: HexCoord,
+    to: HexCoord,
+    promotion: Option<PieceType>,
+) -> Result<String, NotationError> {
+    let legality = game.explain_move(from, to);
+    if !legality.is_legal() {
+        return Err(NotationError::Illegal(legality));
+    }
+    let piece = game.board.get_piece(from).ok_or(NotationError::Illegal(MoveLegality::NoPiece))?;
+    let capture = game.is_capture(from, to);
+    let board = &game.board.board_type;
+    let (file, rank) = split_name(&square_name(board, from)?);
+
+    let mut text = String::new();
+    if piece.piece_type == PieceType::Pawn {
+        if capture {
+            text.push(file);
+        }
+    } else {
+        text.push(piece_letter(piece.piece_type));
+
+        let mut rivals = Vec::new();
+        for (&other, &other_piece) in &game.board.pieces {
+            if other != from && other_piece == *piece && game.explain_move(other, to).is_legal() {
+                rivals.push(split_name(&square_name(board, other)?));
+            }
+        }
+        text += &disambiguation(file, &rank, &rivals);
+    }
+    if capture {
+        text.push('x');
+    }
+    text += &square_name(board, to)?;
+
+    let mut after = game.clone();
+    let outcome = after
+        .make_move_with_promotion(from, to, promotion)
+        .map_err(|_| NotationError::Illegal(legality))?;
+    if let Some(promoted_to) = outcome.promoted_to {
+        text.push('=');
+        text.push(piece_letter(promoted_to));
+    }
+    if outcome.is_checkmate {
+        text.push('#');
+    } else if outcome.is_check {
+        text.push('+');
+    }
+    Ok(text)
+}
+
+/// Figurine algebraic notation: SAN with the moving piece drawn as its Unicode
+/// symbol, e.g. "♘i3" for "Ni3"; chancellor and archbishop moves keep their letters
+pub fn format_figurine_san(game: &Game, from: HexCoord, to: HexCoord) -> Result<String, NotationError> {
+    let san = format_san(game, from, to)?;
+    let piece = game.board.get_piece(from).ok_or(NotationError::Illegal(MoveLegality::NoPiece))?;
+    Ok(san
+        .chars()
+        .map(|c| match piece_from_letter(c) {
+            Some(piece_type) if c.is_ascii_uppercase() => piece_figurine(Piece::new(piece_type, piece.color)),
+            _ => c,
+        })
+        .collect())
+}
+
+/// Parse standard algebraic notation against the game's current position
+///
+/// The text must name exactly one legal move; the `+`/`#` suffix is optional.
+pub fn parse_san(game: &Game, text: &str) -> Result<ParsedMove, NotationError> {
+    let wanted = without_promotion(text.trim_end_matches(['+', '#']));
+    if wanted.is_empty() {
+        return Err(NotationError::Malformed(text.to_string()));
+    }
+
+    let mut found = None;
+    for (from, to) in game.all_legal_moves() {
+        // Only format moves whose destination ends the text
+        if !square_name(&game.board.board_type, to).is_ok_and(|name| wanted.ends_with(&name)) {
+            continue;
+        }
+        let san = format_san(game, from, to)?;
+        if without_promotion(san.trim_end_matches(['+', '#'])) == wanted {
+            found = Some(ParsedMove { from, to, promotion: None });
+            break;
+        }
+    }
+    found.ok_or_else(|| NotationError::NoSuchMove(text.to_string()))
+}
+
+impl Move {
+    /// Standard algebraic notation for this move in `game`, the position it was played
+    /// in, e.g. "Nf6", "exf5", "f11=Q+", "Qcf6#" or "N@f6"
+    pub fn to_algebraic(&self, game: &Game) -> Result<String, NotationError> {
+        if self.dropped {
+            return format_drop(&game.board.board_type, self.piece.piece_type, self.to);
+        }
+        format_san_with_promotion(game, self.from, self.to, self.promoted_to)
+    }
+}
+
+impl Game {
+    /// Read a move typed in any of the notations this module writes: standard
+    /// algebraic ("Nxg8", "f11=Q+"), long algebraic ("Nf6xg8") or coordinates ("f6g8")
+    ///
+    /// The move must be legal in the current position. A pawn reaching the far
+    /// edge without a promotion piece named promotes as the variant does by default.
+    pub fn parse_move(&self, text: &str) -> Result<ParsedMove, NotationError> {
+        let board = &self.board.board_type;
+        let mut parsed = parse_long_algebraic(self, text)
+            .or_else(|_| parse_coordinate(board, text).and_then(|parsed| self.check_parsed(parsed, text)))
+            .or_else(|_| {
+                let trimmed = text.trim_end_matches(['+', '#']);
+                let (san, promotion) = match trimmed.split_once('=') {
+                    Some((san, letter)) => (san, parse_promotion_letter(&letter.to_ascii_lowercase(), text)?),
+                    None => (trimmed, None),
+                };
+                let parsed = parse_san(self, san)?;
+                Ok(ParsedMove { promotion, ..parsed })
+            })?;
+        parsed.promotion = parsed.promotion.or_else(|| self.auto_promotion(parsed.from, parsed.to));
+        Ok(parsed)
+    }
+
+    /// A coordinate move, which names no piece, still has to be legal
+    fn check_parsed(&self, parsed: ParsedMove, text: &str) -> Result<ParsedMove, NotationError> {
+        let legality = self.explain_move(parsed.from, parsed.to);
+        if !legality.is_legal() {
+            return Err(NotationError::NoSuchMove(text.to_string()));
+        }
+        Ok(parsed)
+    }
+}
+
+/// SAN without its `=X` promotion suffix, which `parse_san` leaves to the caller
+fn without_promotion(san: &str) -> &str {
+    san.split_once('=').map_or(san, |(san, _)| san)
+}
+
+/// What SAN adds after the piece letter to tell it from `rivals` reaching the same cell:
+/// nothing, the file, the rank, or the whole origin square
+fn disambiguation(file: char, rank: &str, rivals: &[(char, String)]) -> String {
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|&(other_file, _)| other_file != file) {
+        file.to_string()
+    } else if rivals.iter().all(|(_, other_rank)| other_rank != rank) {
+        rank.to_string()
+    } else {
+        format!("{}{}", file, rank)
+    }
+}
+
+/// Split a cell name into its file letter and rank digits
+fn split_name(name: &str) -> (char, String) {
+    let mut chars = name.chars();
+    let file = chars.next().unwrap_or_default();
+    (file, chars.collect())
+}
+
+/// Split a leading cell name off `text`
+fn split_square<'a>(board: &BoardType, text: &'a str) -> Result<(HexCoord, &'a str), NotationError> {
+    let invalid = || NotationError::InvalidSquare(text.to_string());
+    let file = text.chars().next().filter(char::is_ascii_lowercase).ok_or_else(invalid)?;
+    let digits = text[1..].chars().take_while(char::is_ascii_digit).count();
+    let rank = text[1..1 + digits].parse().map_err(|_| invalid())?;
+    let coord = board.cell_at(file, rank).ok_or_else(invalid)?;
+    Ok((coord, &text[1 + digits..]))
+}
+
+fn parse_promotion_letter(rest: &str, text: &str) -> Result<Option<PieceType>, NotationError> {
+    let mut chars = rest.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Ok(None),
+        (Some(c), None) if c.is_ascii_lowercase() => match piece_from_letter(c) {
+            Some(PieceType::King | PieceType::Pawn) | None => Err(NotationError::Malformed(text.to_string())),
+            promotion => Ok(promotion),
+        },
+        _ => Err(NotationError::Malformed(text.to_string())),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NotationError {
+    #[error("Invalid square in {0:?}")]
+    InvalidSquare(String),
+    #[error("Cell {0:?} has no square name")]
+    NoSquareName(HexCoord),
+    #[error("Malformed move {0:?}")]
+    Malformed(String),
+    #[error("Move {0:?} does not match the position")]
+    Mismatch(String),
+    #[error("No legal move matches {0:?}")]
+    NoSuchMove(String),
+    #[error("Illegal move: {0}")]
+    Illegal(MoveLegality),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::GameLibrary;
+    use crate::variants::Variants;
+
+    #[test]
+    fn test_squares() {
+        let glinski = &Variants::glinski_chess().board_type;
+        assert_eq!(parse_square(glinski, "f6"), Ok(HexCoord::new(0, 0)));
+        assert_eq!(square_name(glinski, HexCoord::new(0, 5)).as_deref(), Ok("f11"));
+        assert_eq!(parse_square(glinski, "f11"), Ok(HexCoord::new(0, 5)));
+        assert!(parse_square(glinski, "j5").is_err());
+        assert!(parse_square(glinski, "f").is_err());
+        assert!(parse_square(glinski, "f5x").is_err());
+
+        // Mini Hexchess is lettered a-g with ranks 1-7 around its own centre
+        let mini = &Variants::mini_hexchess().board_type;
+        assert_eq!(square_name(mini, HexCoord::new(0, 0)).as_deref(), Ok("d4"));
+        assert_eq!(parse_square(mini, "g4"), Ok(HexCoord::new(3, 0)));
+        assert!(parse_square(mini, "h4").is_err());
+        assert!(square_name(mini, HexCoord::new(0, 4)).is_err());
+    }
+
+    #[test]
+    fn test_coordinate_moves() {
+        let board = &Variants::glinski_chess().board_type;
+        let pawn = parse_coordinate(board, "f5f6").unwrap();
+        assert_eq!(pawn, ParsedMove { from: HexCoord::new(0, -1), to: HexCoord::new(0, 0), promotion: None });
+
+        let promotion = parse_coordinate(board, "f10f11q").unwrap();
+        assert_eq!(promotion.to, HexCoord::new(0, 5));
+        assert_eq!(promotion.promotion, Some(PieceType::Queen));
+        assert_eq!(
+            format_coordinate(board, promotion.from, promotion.to, promotion.promotion).as_deref(),
+            Ok("f10f11q")
+        );
+        assert_eq!(format_drop(board, PieceType::Knight, HexCoord::new(0, 0)).as_deref(), Ok("N@f6"));
+
+        assert!(parse_coordinate(board, "f5f6k").is_err());
+        assert!(parse_coordinate(board, "f5f6qq").is_err());
+    }
+
+    #[test]
+    fn test_long_algebraic() {
+        let mut game = Game::new(Variants::glinski_chess());
+        assert_eq!(format_long_algebraic(&game, HexCoord::new(0, -1), HexCoord::new(0, 0)).as_deref(), Ok("f5-f6"));
+
+        let knight = parse_long_algebraic(&game, "Nh1-i3").unwrap();
+        assert_eq!((knight.from, knight.to), (HexCoord::new(2, -5), HexCoord::new(3, -3)));
+
+        // Wrong piece letter, wrong capture marker, illegal move
+        assert!(matches!(parse_long_algebraic(&game, "Bh1-i3"), Err(NotationError::Mismatch(_))));
+        assert!(matches!(parse_long_algebraic(&game, "Nh1xi3"), Err(NotationError::Mismatch(_))));
+        assert!(matches!(parse_long_algebraic(&game, "f5-f7"), Err(NotationError::Illegal(_))));
+
+        // Play into a capture and check the `x` form
+        for text in ["f5-f6", "e8-e7"] {
+            let mv = parse_long_algebraic(&game, text).unwrap();
+            game.make_move(mv.from, mv.to).unwrap();
+        }
+        let capture = (HexCoord::new(0, 0), HexCoord::new(-1, 1));
+        assert_eq!(format_long_algebraic(&game, capture.0, capture.1).as_deref(), Ok("f6xe7"));
+        assert_eq!(parse_long_algebraic(&game, "f6xe7").map(|mv| (mv.from, mv.to)), Ok(capture));
+        assert!(matches!(parse_long_algebraic(&game, "f6-e7"), Err(NotationError::Mismatch(_))));
+    }
+
+    #[test]
+    fn test_round_trip_every_opening_move() {
+        for variant in [Variants::glinski_chess(), Variants::mccooey_chess(), Variants::mini_hexchess()] {
+            let game = Game::new(variant);
+            for (from, to) in game.all_legal_moves() {
+                let long = format_long_algebraic(&game, from, to).unwrap();
+                assert_eq!(parse_long_algebraic(&game, &long).map(|mv| (mv.from, mv.to)), Ok((from, to)));
+
+                let board = &game.board.board_type;
+                let coordinate = format_coordinate(board, from, to, None).unwrap();
+                assert_eq!(parse_coordinate(board, &coordinate).map(|mv| (mv.from, mv.to)), Ok((from, to)));
+            }
+        }
+    }
+
+    fn crowded_position() -> Game {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.clear();
+        for (q, r, piece_type, color) in [
+            (5, -5, PieceType::King, Color::White),
+            (-1, 5, PieceType::King, Color::Black),
+            (0, -3, PieceType::Queen, Color::White),
+            (0, 3, PieceType::Queen, Color::White),
+            (-3, 3, PieceType::Queen, Color::White),
+            (-2, -1, PieceType::Knight, Color::White),
+            (2, -3, PieceType::Knight, Color::White),
+        ] {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_san_disambiguation_and_suffixes() {
+        let game = crowded_position();
+        let san = |from: (i32, i32), to: (i32, i32)| {
+            format_san(&game, HexCoord::new(from.0, from.1), HexCoord::new(to.0, to.1)).unwrap()
+        };
+
+        // Three queens reach f6: by file, by rank, and by full square
+        assert_eq!(san((-3, 3), (0, 0)), "Qcf6#");
+        assert_eq!(san((0, -3), (0, 0)), "Q3f6#");
+        assert_eq!(san((0, 3), (0, 0)), "Qf9f6+");
+        // Only the f-file queens reach f4
+        assert_eq!(san((0, 3), (0, -2)), "Q9f4+");
+        // Knights on different files
+        assert_eq!(san((-2, -1), (0, 0)), "Ndf6#");
+        assert_eq!(san((2, -3), (1, 0)), "Ng6#");
+
+        assert_eq!(parse_san(&game, "Qf9f6").map(|mv| mv.from), Ok(HexCoord::new(0, 3)));
+        assert_eq!(parse_san(&game, "Qcf6#").map(|mv| mv.from), Ok(HexCoord::new(-3, 3)));
+        assert!(matches!(parse_san(&game, "Qf6"), Err(NotationError::NoSuchMove(_))));
+        assert!(matches!(parse_san(&game, "Nf6"), Err(NotationError::NoSuchMove(_))));
+
+        let start = Game::new(Variants::glinski_chess());
+        assert_eq!(format_san(&start, HexCoord::new(0, -1), HexCoord::new(0, 0)).as_deref(), Ok("f6"));
+        assert_eq!(format_san(&start, HexCoord::new(2, -5), HexCoord::new(3, -3)).as_deref(), Ok("Ni3"));
+    }
+
+    #[test]
+    fn test_san_names_the_promotion() {
+        let mut game = Game::new(Variants::glinski_chess());
+        game.board.pieces.clear();
+        for (q, r, piece_type, color) in [
+            (0, -5, PieceType::King, Color::White),
+            (0, 5, PieceType::King, Color::Black),
+            (2, 2, PieceType::Pawn, Color::White),
+        ] {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+        let (from, to) = (HexCoord::new(2, 2), HexCoord::new(2, 3));
+        let promoted = |piece_type| format_san_with_promotion(&game, from, to, Some(piece_type)).unwrap();
+
+        // The suffix follows the piece chosen: a queen checks along the edge, a knight doesn't
+        assert_eq!(promoted(PieceType::Queen), "h9=Q+");
+        assert_eq!(promoted(PieceType::Knight), "h9=N");
+        assert_eq!(format_san(&game, from, to).unwrap(), promoted(PieceType::Queen));
+        let parsed = game.parse_move("h9=N").unwrap();
+        assert_eq!((parsed.from, parsed.to, parsed.promotion), (from, to, Some(PieceType::Knight)));
+        assert_eq!(parse_san(&game, "h9=Q+").map(|mv| mv.from), Ok(from));
+    }
+
+    #[test]
+    fn test_figurine_notation() {
+        assert_eq!(piece_figurine(Piece::new(PieceType::Knight, Color::White)), '♘');
+        assert_eq!(piece_figurine(Piece::new(PieceType::Queen, Color::Black)), '♛');
+        // Fairy pieces have no figurines and keep their letters
+        assert_eq!(piece_figurine(Piece::new(PieceType::Chancellor, Color::White)), 'C');
+        assert_eq!(piece_figurine(Piece::new(PieceType::Archbishop, Color::Black)), 'a');
+
+        let start = Game::new(Variants::glinski_chess());
+        assert_eq!(format_figurine_san(&start, HexCoord::new(0, -1), HexCoord::new(0, 0)).as_deref(), Ok("f6"));
+        assert_eq!(format_figurine_san(&start, HexCoord::new(2, -5), HexCoord::new(3, -3)).as_deref(), Ok("♘i3"));
+        assert_eq!(PieceType::Archbishop.name(), "Archbishop");
+    }
+
+    #[test]
+    fn test_san_marks_checkmate_in_sample_game() {
+        let sample = GameLibrary::load("queen-raid").unwrap();
+        let last = sample.moves.last().unwrap();
+        let game = sample.replay_to(sample.moves.len() - 1).unwrap();
+        assert!(format_san(&game, last.from, last.to).unwrap().ends_with('#'));
+    }
+
+    #[test]
+    fn test_san_round_trips_in_random_games() {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        for variant in [Variants::glinski_chess(), Variants::mccooey_chess(), Variants::mini_hexchess()] {
+            let mut game = Game::new(variant);
+            for _ in 0..16 {
+                let moves = game.all_legal_moves();
+                if moves.is_empty() {
+                    break;
+                }
+                for &(from, to) in &moves {
+                    let san = format_san(&game, from, to).unwrap();
+                    assert_eq!(parse_san(&game, &san).map(|mv| (mv.from, mv.to)), Ok((from, to)), "{}", san);
+                }
+
+                seed = crate::board::splitmix64(seed);
+                let (from, to) = moves[(seed % moves.len() as u64) as usize];
+                game.make_move(from, to).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_to_algebraic_matches_san() {
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut game = Game::new(Variants::glinski_chess());
+        for _ in 0..40 {
+            let moves = game.all_legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            seed = crate::board::splitmix64(seed);
+            let (from, to) = moves[(seed % moves.len() as u64) as usize];
+            let san = format_san(&game, from, to).unwrap();
+            let before = game.clone();
+            game.make_move(from, to).unwrap();
+            let played = game.move_history.back().unwrap();
+            assert_eq!(played.to_algebraic(&before).unwrap(), san);
+        }
+
+        let sample = GameLibrary::load("queen-raid").unwrap();
+        let before = sample.replay_to(sample.moves.len() - 1).unwrap();
+        let after = sample.replay().unwrap();
+        assert!(after.move_history.back().unwrap().to_algebraic(&before).unwrap().ends_with('#'));
+    }
+
+    #[test]
+    fn test_parse_move_in_any_notation() {
+        let game = crowded_position();
+        let f9_to_f6 = Ok((HexCoord::new(0, 3), HexCoord::new(0, 0)));
+        for text in ["Qf9f6+", "Qf9-f6", "f9f6", "Qf9f6"] {
+            assert_eq!(game.parse_move(text).map(|mv| (mv.from, mv.to)), f9_to_f6, "{}", text);
+        }
+        assert!(game.parse_move("Qf6").is_err());
+
+        let start = Game::new(Variants::glinski_chess());
+        assert_eq!(start.parse_move("Ni3").map(|mv| mv.to), Ok(HexCoord::new(3, -3)));
+        assert_eq!(start.parse_move("Nh1-i3").map(|mv| mv.to), Ok(HexCoord::new(3, -3)));
+        // Coordinates are checked against the position too: f5-f7 is blocked
+        assert!(matches!(start.parse_move("f5f7"), Err(NotationError::NoSuchMove(_))));
+    }
+}
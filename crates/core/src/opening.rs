@@ -0,0 +1,189 @@
+use crate::coords::HexCoord;
+use crate::game::{Game, GameError};
+use crate::library::{LibraryError, SampleGame};
+use crate::variants::VariantConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How often a move was played from a position, and how those games ended
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MoveStats {
+    pub games: u32,
+    pub white_wins: u32,
+    pub draws: u32,
+    pub black_wins: u32,
+    /// Games that had not finished, or whose result is unknown
+    pub unfinished: u32,
+}
+
+impl MoveStats {
+    fn record(&mut self, score: Option<(f32, f32)>) {
+        self.games += 1;
+        match score {
+            Some((white, black)) if white > black => self.white_wins += 1,
+            Some((white, black)) if white < black => self.black_wins += 1,
+            Some(_) => self.draws += 1,
+            None => self.unfinished += 1,
+        }
+    }
+}
+
+/// A move leading out of a position in the opening tree
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OpeningMove {
+    pub from: HexCoord,
+    pub to: HexCoord,
+    pub stats: MoveStats,
+}
+
+/// Opening tree keyed by position hash, so transpositions share their statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpeningTree {
+    /// Plies of each game that are added to the tree
+    max_plies: usize,
+    positions: HashMap<u64, HashMap<(HexCoord, HexCoord), MoveStats>>,
+}
+
+impl OpeningTree {
+    /// Create an empty tree that records the first `max_plies` moves of each game
+    pub fn new(max_plies: usize) -> Self {
+        Self {
+            max_plies,
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Add a game given as a move list and its (white, black) score, if it finished
+    pub fn add_game(
+        &mut self,
+        variant: &VariantConfig,
+        moves: &[(HexCoord, HexCoord)],
+        score: Option<(f32, f32)>,
+    ) -> Result<(), GameError> {
+        let mut game = Game::new(variant.clone());
+
+        // Validate the whole opening before touching the tree
+        let mut visited = Vec::new();
+        for &(from, to) in moves.iter().take(self.max_plies) {
+            visited.push((game.position_hash(), (from, to)));
+            game.make_move(from, to)?;
+        }
+
+        // A game that repeats a position still counts once for it
+        visited.sort_by_key(|&(hash, (from, to))| (hash, from.q, from.r, to.q, to.r));
+        visited.dedup();
+        for (hash, mv) in visited {
+            self.positions.entry(hash).or_default().entry(mv).or_default().record(score);
+        }
+        Ok(())
+    }
+
+    /// Add one of the bundled sample games, scored by its final position
+    pub fn add_sample_game(&mut self, sample: &SampleGame) -> Result<(), LibraryError> {
+        let score = sample.replay()?.score();
+        let moves: Vec<_> = sample.moves.iter().map(|mv| (mv.from, mv.to)).collect();
+        Ok(self.add_game(&sample.variant_config()?, &moves, score)?)
+    }
+
+    /// Moves played from a position, most frequent first
+    pub fn moves_from(&self, position_hash: u64) -> Vec<OpeningMove> {
+        let mut moves: Vec<_> = self
+            .positions
+            .get(&position_hash)
+            .into_iter()
+            .flatten()
+            .map(|(&(from, to), &stats)| OpeningMove { from, to, stats })
+            .collect();
+        moves.sort_by_key(|mv| (std::cmp::Reverse(mv.stats.games), mv.from.q, mv.from.r, mv.to.q, mv.to.r));
+        moves
+    }
+
+    /// Moves played from the game's current position, most frequent first
+    pub fn explore(&self, game: &Game) -> Vec<OpeningMove> {
+        self.moves_from(game.position_hash())
+    }
+
+    /// Number of distinct positions in the tree
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::GameLibrary;
+    use crate::variants::Variants;
+
+    fn mv(from: (i32, i32), to: (i32, i32)) -> (HexCoord, HexCoord) {
+        (HexCoord::new(from.0, from.1), HexCoord::new(to.0, to.1))
+    }
+
+    #[test]
+    fn test_frequencies_and_results() {
+        let variant = Variants::glinski_chess();
+        let mut tree = OpeningTree::new(4);
+        let pawn = mv((0, -1), (0, 0));
+        let knight = mv((2, -5), (3, -3));
+        let reply = mv((-1, 2), (-1, 1));
+
+        tree.add_game(&variant, &[pawn, reply], Some((1.0, 0.0))).unwrap();
+        tree.add_game(&variant, &[pawn, reply], Some((0.5, 0.5))).unwrap();
+        tree.add_game(&variant, &[knight, reply], None).unwrap();
+
+        let start = Game::new(variant.clone());
+        let moves = tree.explore(&start);
+        assert_eq!(moves.len(), 2);
+        assert_eq!((moves[0].from, moves[0].to), pawn);
+        assert_eq!(moves[0].stats, MoveStats { games: 2, white_wins: 1, draws: 1, black_wins: 0, unfinished: 0 });
+        assert_eq!(moves[1].stats.unfinished, 1);
+    }
+
+    #[test]
+    fn test_transpositions_share_a_node() {
+        let variant = Variants::glinski_chess();
+        let mut tree = OpeningTree::new(10);
+        let a = mv((0, -1), (0, 0));
+        let b = mv((2, -5), (3, -3));
+        let x = mv((-1, 2), (-1, 1));
+        let y = mv((2, 3), (4, 0));
+        let next = mv((1, -2), (1, -1));
+
+        tree.add_game(&variant, &[a, x, b, y, next], None).unwrap();
+        tree.add_game(&variant, &[b, y, a, x], None).unwrap();
+
+        let mut game = Game::new(variant);
+        for (from, to) in [b, x, a, y] {
+            game.make_move(from, to).unwrap();
+        }
+        let moves = tree.explore(&game);
+        assert_eq!(moves.len(), 1);
+        assert_eq!((moves[0].from, moves[0].to), next);
+    }
+
+    #[test]
+    fn test_illegal_games_are_rejected_whole() {
+        let variant = Variants::glinski_chess();
+        let mut tree = OpeningTree::new(10);
+        let result = tree.add_game(&variant, &[mv((0, -1), (0, 0)), mv((0, 0), (0, 1))], None);
+
+        assert!(result.is_err());
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn test_build_from_sample_games() {
+        let mut tree = OpeningTree::new(6);
+        for info in GameLibrary::list() {
+            tree.add_sample_game(&GameLibrary::load(&info.id).unwrap()).unwrap();
+        }
+
+        let start = Game::new(Variants::glinski_chess());
+        let total: u32 = tree.explore(&start).iter().map(|mv| mv.stats.games).sum();
+        assert_eq!(total, GameLibrary::list().len() as u32);
+    }
+}
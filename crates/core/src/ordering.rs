@@ -0,0 +1,235 @@
+use crate::board::Board;
+use crate::coords::HexCoord;
+use crate::pieces::PieceType;
+use std::collections::HashMap;
+
+/// Killer moves kept per ply
+const KILLERS_PER_PLY: usize = 2;
+
+/// Score offsets keeping the ordering classes apart: captures, then killers, then history
+const CAPTURE_BASE: i32 = 1_000_000;
+const KILLER_BASE: i32 = 500_000;
+const HISTORY_CAP: i32 = KILLER_BASE - 1;
+
+/// Material value of a piece type in centipawns, as used for MVV-LVA
+pub fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 300,
+        PieceType::Bishop => 325,
+        PieceType::Rook => 500,
+        PieceType::Archbishop => 775,
+        PieceType::Chancellor => 850,
+        PieceType::Queen => 900,
+        PieceType::King => 10_000,
+    }
+}
+
+/// Most valuable victim, least valuable attacker: higher is searched first
+pub fn mvv_lva(victim: PieceType, attacker: PieceType) -> i32 {
+    piece_value(victim) * 16 - piece_value(attacker) / 100
+}
+
+/// Counters showing how well the ordering works
+///
+/// A well-ordered search finds most of its beta cutoffs on the first move tried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OrderingStats {
+    /// Move lists passed through `order_moves`
+    pub orderings: u64,
+    /// Beta cutoffs reported through `record_cutoff`
+    pub cutoffs: u64,
+    /// Cutoffs caused by the first move in the ordered list
+    pub first_move_cutoffs: u64,
+    /// Cutoffs caused by a killer move
+    pub killer_cutoffs: u64,
+}
+
+impl OrderingStats {
+    /// Fraction of cutoffs produced by the first move tried
+    pub fn first_move_rate(&self) -> f64 {
+        if self.cutoffs == 0 {
+            0.0
+        } else {
+            self.first_move_cutoffs as f64 / self.cutoffs as f64
+        }
+    }
+}
+
+/// Move ordering state for an alpha-beta search: killer moves, history
+/// heuristic and MVV-LVA capture ordering
+#[derive(Debug, Clone, Default)]
+pub struct MoveOrdering {
+    killers: Vec<[Option<(HexCoord, HexCoord)>; KILLERS_PER_PLY]>,
+    history: HashMap<(HexCoord, HexCoord), i32>,
+    stats: OrderingStats,
+}
+
+impl MoveOrdering {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort `moves` so the most promising are tried first at `ply`
+    pub fn order_moves(&mut self, board: &Board, moves: &mut [(HexCoord, HexCoord)], ply: usize) {
+        self.stats.orderings += 1;
+        moves.sort_by_cached_key(|&mv| std::cmp::Reverse(self.score(board, mv, ply)));
+    }
+
+    /// Ordering score of a single move at `ply`
+    pub fn score(&self, board: &Board, (from, to): (HexCoord, HexCoord), ply: usize) -> i32 {
+        if let (Some(attacker), Some(victim)) = (board.get_piece(from), board.get_piece(to)) {
+            return CAPTURE_BASE + mvv_lva(victim.piece_type, attacker.piece_type);
+        }
+
+        if let Some(slot) = self.killers(ply).iter().position(|&killer| killer == Some((from, to))) {
+            return KILLER_BASE - slot as i32;
+        }
+
+        self.history.get(&(from, to)).copied().unwrap_or(0)
+    }
+
+    /// Killer moves remembered for `ply`, most recent first
+    pub fn killers(&self, ply: usize) -> &[Option<(HexCoord, HexCoord)>] {
+        self.killers.get(ply).map(|slots| &slots[..]).unwrap_or(&[])
+    }
+
+    /// Record that `mv`, the `index`-th move tried, caused a beta cutoff
+    /// at `ply` with `depth` plies remaining
+    pub fn record_cutoff(&mut self, board: &Board, mv: (HexCoord, HexCoord), ply: usize, depth: u32, index: usize) {
+        self.stats.cutoffs += 1;
+        if index == 0 {
+            self.stats.first_move_cutoffs += 1;
+        }
+
+        // Captures are already ordered by MVV-LVA
+        if board.is_occupied(mv.1) {
+            return;
+        }
+
+        if self.killers(ply).contains(&Some(mv)) {
+            self.stats.killer_cutoffs += 1;
+        } else {
+            if self.killers.len() <= ply {
+                self.killers.resize(ply + 1, [None; KILLERS_PER_PLY]);
+            }
+            let slots = &mut self.killers[ply];
+            slots.rotate_right(1);
+            slots[0] = Some(mv);
+        }
+
+        let bonus = (depth * depth) as i32;
+        let entry = self.history.entry(mv).or_insert(0);
+        *entry = (*entry + bonus).min(HISTORY_CAP);
+    }
+
+    /// Halve the history scores, e.g. between iterative deepening iterations
+    pub fn age_history(&mut self) {
+        self.history.retain(|_, score| {
+            *score /= 2;
+            *score > 0
+        });
+    }
+
+    /// Forget killers and history, e.g. between games
+    pub fn clear(&mut self) {
+        self.killers.clear();
+        self.history.clear();
+    }
+
+    pub fn stats(&self) -> OrderingStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = OrderingStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::BoardType;
+    use crate::pieces::{Color, Piece};
+
+    fn test_board() -> Board {
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        for (q, r, piece_type, color) in [
+            (0, 0, PieceType::Queen, Color::White),
+            (-1, 0, PieceType::Pawn, Color::White),
+            (0, 3, PieceType::Rook, Color::Black),
+            (3, -3, PieceType::Pawn, Color::Black),
+            (-1, 1, PieceType::Knight, Color::Black),
+        ] {
+            board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn test_mvv_lva_prefers_valuable_victims_and_cheap_attackers() {
+        assert!(mvv_lva(PieceType::Queen, PieceType::Pawn) > mvv_lva(PieceType::Rook, PieceType::Pawn));
+        assert!(mvv_lva(PieceType::Rook, PieceType::Pawn) > mvv_lva(PieceType::Rook, PieceType::Queen));
+        assert!(mvv_lva(PieceType::Pawn, PieceType::Queen) > 0);
+    }
+
+    #[test]
+    fn test_captures_then_killers_then_history() {
+        let board = test_board();
+        let mut ordering = MoveOrdering::new();
+
+        let queen_takes_rook = (HexCoord::new(0, 0), HexCoord::new(0, 3));
+        let queen_takes_pawn = (HexCoord::new(0, 0), HexCoord::new(3, -3));
+        let pawn_takes_knight = (HexCoord::new(-1, 0), HexCoord::new(-1, 1));
+        let killer = (HexCoord::new(0, 0), HexCoord::new(1, 0));
+        let history = (HexCoord::new(0, 0), HexCoord::new(2, -1));
+        let quiet = (HexCoord::new(0, 0), HexCoord::new(-2, 1));
+
+        ordering.record_cutoff(&board, history, 3, 2, 4);
+        ordering.record_cutoff(&board, killer, 1, 1, 0);
+
+        let mut moves = [quiet, history, killer, queen_takes_pawn, pawn_takes_knight, queen_takes_rook];
+        ordering.order_moves(&board, &mut moves, 1);
+
+        assert_eq!(moves, [queen_takes_rook, pawn_takes_knight, queen_takes_pawn, killer, history, quiet]);
+    }
+
+    #[test]
+    fn test_killer_slots_and_stats() {
+        let board = test_board();
+        let mut ordering = MoveOrdering::new();
+        let first = (HexCoord::new(0, 0), HexCoord::new(1, 0));
+        let second = (HexCoord::new(0, 0), HexCoord::new(2, 0));
+        let third = (HexCoord::new(0, 0), HexCoord::new(3, 0));
+
+        ordering.record_cutoff(&board, first, 2, 1, 0);
+        ordering.record_cutoff(&board, second, 2, 1, 3);
+        ordering.record_cutoff(&board, third, 2, 1, 0);
+        assert_eq!(ordering.killers(2), &[Some(third), Some(second)]);
+
+        ordering.record_cutoff(&board, second, 2, 1, 1);
+        // Captures count towards the stats but never become killers
+        ordering.record_cutoff(&board, (HexCoord::new(0, 0), HexCoord::new(0, 3)), 2, 1, 0);
+        assert_eq!(ordering.killers(2), &[Some(third), Some(second)]);
+
+        let stats = ordering.stats();
+        assert_eq!(stats.cutoffs, 5);
+        assert_eq!(stats.first_move_cutoffs, 3);
+        assert_eq!(stats.killer_cutoffs, 1);
+        assert!((stats.first_move_rate() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_history_ages_out() {
+        let board = test_board();
+        let mut ordering = MoveOrdering::new();
+        let quiet = (HexCoord::new(0, 0), HexCoord::new(1, 0));
+
+        ordering.record_cutoff(&board, quiet, 0, 3, 0);
+        assert_eq!(ordering.score(&board, quiet, 5), 9);
+        ordering.age_history();
+        assert_eq!(ordering.score(&board, quiet, 5), 4);
+        ordering.clear();
+        assert_eq!(ordering.score(&board, quiet, 5), 0);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::board::Board;
+use crate::coords::HexCoord;
+use crate::pieces::{Color, PieceType};
+use std::collections::BTreeMap;
+
+/// Map a coordinate into White's frame of reference
+///
+/// Black's side is the reflection of White's across the centre rank, so
+/// reflecting Black's pawns lets every check be written once, with pawns
+/// advancing towards +r and capturing towards (-1, +1) and (+1, 0).
+fn relative(coord: HexCoord, color: Color) -> HexCoord {
+    match color {
+        Color::White => coord,
+        Color::Black => HexCoord::new(coord.q, -coord.r - coord.q),
+    }
+}
+
+impl Board {
+    /// Pawns of `color`, sorted by file and then rank
+    fn pawns(&self, color: Color) -> Vec<HexCoord> {
+        let mut pawns: Vec<_> = self
+            .get_pieces_by_color(color)
+            .into_iter()
+            .filter(|(_, piece)| piece.piece_type == PieceType::Pawn)
+            .map(|(coord, _)| coord)
+            .collect();
+        pawns.sort_by_key(|coord| (coord.q, coord.r));
+        pawns
+    }
+
+    /// Pawns of `color` grouped by file (the q axis pawns advance along)
+    fn pawn_files(&self, color: Color) -> BTreeMap<i32, Vec<HexCoord>> {
+        let mut files: BTreeMap<i32, Vec<HexCoord>> = BTreeMap::new();
+        for pawn in self.pawns(color) {
+            files.entry(pawn.q).or_default().push(pawn);
+        }
+        files
+    }
+
+    /// Groups of pawns on runs of neighbouring files
+    pub fn pawn_islands(&self, color: Color) -> Vec<Vec<HexCoord>> {
+        let mut islands: Vec<Vec<HexCoord>> = Vec::new();
+        let mut last_file = None;
+
+        for (file, pawns) in self.pawn_files(color) {
+            match (islands.last_mut(), last_file) {
+                (Some(island), Some(last)) if file == last + 1 => island.extend(pawns),
+                _ => islands.push(pawns),
+            }
+            last_file = Some(file);
+        }
+
+        islands
+    }
+
+    /// Pawns that share their file with another pawn of the same color
+    pub fn doubled_pawns(&self, color: Color) -> Vec<HexCoord> {
+        self.pawn_files(color)
+            .into_values()
+            .filter(|pawns| pawns.len() > 1)
+            .flatten()
+            .collect()
+    }
+
+    /// Pawns with no enemy pawn able to block or capture them on the way to promotion
+    ///
+    /// From White's side, an enemy pawn stops (q, r) if it stands further up the
+    /// same file, on file q + 1 at rank r or beyond, or on file q - 1 beyond rank r.
+    pub fn passed_pawns(&self, color: Color) -> Vec<HexCoord> {
+        let opponent = match color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        let enemies: Vec<_> = self
+            .pawns(opponent)
+            .into_iter()
+            .map(|coord| relative(coord, color))
+            .collect();
+
+        self.pawns(color)
+            .into_iter()
+            .filter(|&pawn| {
+                let own = relative(pawn, color);
+                !enemies.iter().any(|enemy| {
+                    (enemy.q == own.q && enemy.r > own.r)
+                        || (enemy.q == own.q + 1 && enemy.r >= own.r)
+                        || (enemy.q == own.q - 1 && enemy.r > own.r)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::BoardType;
+    use crate::pieces::Piece;
+
+    fn board_with_pawns(white: &[(i32, i32)], black: &[(i32, i32)]) -> Board {
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        for &(q, r) in white {
+            board.place_piece(HexCoord::new(q, r), Piece::new(PieceType::Pawn, Color::White)).unwrap();
+        }
+        for &(q, r) in black {
+            board.place_piece(HexCoord::new(q, r), Piece::new(PieceType::Pawn, Color::Black)).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn test_pawn_islands() {
+        let board = board_with_pawns(&[(-3, 0), (-2, -1), (0, -2), (2, -3), (3, -3)], &[]);
+        let islands = board.pawn_islands(Color::White);
+
+        assert_eq!(
+            islands,
+            vec![
+                vec![HexCoord::new(-3, 0), HexCoord::new(-2, -1)],
+                vec![HexCoord::new(0, -2)],
+                vec![HexCoord::new(2, -3), HexCoord::new(3, -3)],
+            ]
+        );
+        assert!(board.pawn_islands(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_doubled_pawns() {
+        let board = board_with_pawns(&[(0, -2), (0, 0), (1, -1)], &[(2, 1), (2, 2), (2, 3)]);
+
+        assert_eq!(board.doubled_pawns(Color::White), vec![HexCoord::new(0, -2), HexCoord::new(0, 0)]);
+        assert_eq!(board.doubled_pawns(Color::Black).len(), 3);
+    }
+
+    #[test]
+    fn test_passed_pawns() {
+        // The white f-pawn faces a black pawn further up its own file, the
+        // white pawn on q = 3 can be captured by the pawn on q = 4, and the
+        // pawn on q = -3 is free
+        let board = board_with_pawns(&[(0, -1), (3, -2), (-3, 0)], &[(0, 2), (4, -1)]);
+        assert_eq!(board.passed_pawns(Color::White), vec![HexCoord::new(-3, 0)]);
+
+        // A pawn the opponent has already gone past is no obstacle
+        let board = board_with_pawns(&[(0, 1)], &[(0, -1), (1, 0)]);
+        assert_eq!(board.passed_pawns(Color::White), vec![HexCoord::new(0, 1)]);
+    }
+
+    #[test]
+    fn test_passed_pawns_are_symmetric() {
+        // The same structure reflected for Black gives the reflected answer
+        let white = [(0, -1), (3, -2), (-3, 0)];
+        let black = [(0, 2), (4, -1)];
+        let mirror = |cells: &[(i32, i32)]| -> Vec<(i32, i32)> {
+            cells.iter().map(|&(q, r)| (q, -r - q)).collect()
+        };
+        let board = board_with_pawns(&mirror(&black), &mirror(&white));
+
+        assert_eq!(board.passed_pawns(Color::Black), vec![HexCoord::new(-3, 3)]);
+    }
+}
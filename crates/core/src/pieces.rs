@@ -1,9 +1,11 @@
 use crate::coords::HexCoord;
 use crate::board::Board;
+use crate::variants::PawnMovement;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Chess piece types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum PieceType {
     King,
     Queen,
@@ -17,14 +19,14 @@ pub enum PieceType {
 }
 
 /// Piece colors
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum Color {
     White,
     Black,
 }
 
 /// A chess piece
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub struct Piece {
     pub piece_type: PieceType,
     pub color: Color,
@@ -55,7 +57,59 @@ impl Piece {
     }
 }
 
+/// The 6 orthogonal (rook) directions, matching `HexCoord::neighbors`
+pub const ORTHOGONAL_DIRECTIONS: [HexCoord; 6] = [
+    HexCoord { q: 1, r: 0 },   // East
+    HexCoord { q: 1, r: -1 },  // Northeast
+    HexCoord { q: 0, r: -1 },  // Northwest
+    HexCoord { q: -1, r: 0 },  // West
+    HexCoord { q: -1, r: 1 },  // Southwest
+    HexCoord { q: 0, r: 1 },   // Southeast
+];
+
+/// The 6 diagonal (bishop) directions, matching `HexCoord::diagonal_neighbors`
+pub const DIAGONAL_DIRECTIONS: [HexCoord; 6] = [
+    HexCoord { q: 2, r: -1 },  // Northeast diagonal
+    HexCoord { q: 1, r: -2 },  // Northwest diagonal
+    HexCoord { q: -1, r: -1 }, // West diagonal
+    HexCoord { q: -2, r: 1 },  // Southwest diagonal
+    HexCoord { q: -1, r: 2 },  // Southeast diagonal
+    HexCoord { q: 1, r: 1 },   // East diagonal
+];
+
+/// Gliński knight leaps: one orthogonal step followed by one diagonal step
+/// away from the starting cell. These are exactly the 12 cells at distance 3
+/// that do not lie on an orthogonal line through the origin.
+pub const KNIGHT_OFFSETS: [HexCoord; 12] = [
+    HexCoord { q: 1, r: 2 },
+    HexCoord { q: 2, r: 1 },
+    HexCoord { q: 3, r: -1 },
+    HexCoord { q: 3, r: -2 },
+    HexCoord { q: 2, r: -3 },
+    HexCoord { q: 1, r: -3 },
+    HexCoord { q: -1, r: -2 },
+    HexCoord { q: -2, r: -1 },
+    HexCoord { q: -3, r: 1 },
+    HexCoord { q: -3, r: 2 },
+    HexCoord { q: -2, r: 3 },
+    HexCoord { q: -1, r: 3 },
+];
+
 impl PieceType {
+    /// The piece's name in English, e.g. "Chancellor"
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceType::King => "King",
+            PieceType::Queen => "Queen",
+            PieceType::Rook => "Rook",
+            PieceType::Bishop => "Bishop",
+            PieceType::Knight => "Knight",
+            PieceType::Pawn => "Pawn",
+            PieceType::Chancellor => "Chancellor",
+            PieceType::Archbishop => "Archbishop",
+        }
+    }
+
     /// Get all possible moves for this piece type from a given position
     pub fn get_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
         match self {
@@ -70,113 +124,64 @@ impl PieceType {
         }
     }
 
-    /// King moves: one step in any of the 6 directions (like a rook, but only one step)
-    /// In Gliński's Chess, the king moves to the 6 adjacent hexes, not diagonals
-    fn king_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
-        let mut moves = Vec::new();
-        
-        // All 6 adjacent hexes (rook-like movement, but only one step)
-        for neighbor in from.neighbors() {
-            if board.is_valid_coord(neighbor) {
-                moves.push(neighbor);
+    /// Cells this piece attacks from `from`, whether empty or occupied by either color
+    ///
+    /// Only pawns differ from `get_moves`: they attack their two capture cells
+    /// and never the cell straight ahead.
+    pub fn attacks(&self, from: HexCoord, color: Color, board: &Board) -> Vec<HexCoord> {
+        match self {
+            PieceType::Pawn => {
+                Self::pawn_captures(color, &board.pawn_movement)
+                    .into_iter()
+                    .map(|direction| from + direction)
+                    .filter(|&target| board.is_valid_coord(target))
+                    .collect()
             }
+            _ => self.get_moves(from, board),
         }
-        
-        moves
+    }
+
+    /// King moves: one step to any of the 12 surrounding hexes
+    /// In Gliński's Chess, the king steps one cell orthogonally (6 adjacent hexes)
+    /// or diagonally (6 hexes across the corners)
+    fn king_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
+        board.move_tables().king_steps(from).to_vec()
     }
 
     /// Queen moves: combination of rook and bishop
     fn queen_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
-        let mut moves = Vec::new();
-        moves.extend(self.rook_moves(from, board));
-        moves.extend(self.bishop_moves(from, board));
-        moves
+        Self::combine(self.rook_moves(from, board), self.bishop_moves(from, board))
     }
 
     /// Rook moves: straight lines in 6 directions
     fn rook_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
-        let mut moves = Vec::new();
-        
-        // 6 directions for hexagonal rook
-        let directions = [
-            HexCoord::new(1, 0),      // East
-            HexCoord::new(1, -1),     // Northeast
-            HexCoord::new(0, -1),     // Northwest
-            HexCoord::new(-1, 0),     // West
-            HexCoord::new(-1, 1),     // Southwest
-            HexCoord::new(0, 1),      // Southeast
-        ];
-        
-        for direction in directions {
-            let mut current = from + direction;
-            while board.is_valid_coord(current) {
-                moves.push(current);
-                if board.is_occupied(current) {
-                    break; // Can't move through pieces
-                }
-                current = current + direction;
-            }
-        }
-        
-        moves
+        Self::slide(board.move_tables().orthogonal_rays(from), board)
     }
 
     /// Bishop moves: diagonal lines in 6 directions
     fn bishop_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
+        Self::slide(board.move_tables().diagonal_rays(from), board)
+    }
+
+    /// Walk precomputed rays, stopping at (and including) the first occupied cell
+    fn slide(rays: &[Vec<HexCoord>], board: &Board) -> Vec<HexCoord> {
         let mut moves = Vec::new();
-        
-        // 6 diagonal directions for hexagonal bishop
-        let directions = [
-            HexCoord::new(2, -1),     // Northeast diagonal
-            HexCoord::new(1, -2),     // Northwest diagonal
-            HexCoord::new(-1, -1),    // West diagonal
-            HexCoord::new(-2, 1),     // Southwest diagonal
-            HexCoord::new(-1, 2),     // Southeast diagonal
-            HexCoord::new(1, 1),      // East diagonal
-        ];
-        
-        for direction in directions {
-            let mut current = from + direction;
-            while board.is_valid_coord(current) {
+
+        for ray in rays {
+            for &current in ray {
                 moves.push(current);
                 if board.is_occupied(current) {
                     break; // Can't move through pieces
                 }
-                current = current + direction;
             }
         }
-        
+
         moves
     }
 
     /// Knight moves: L-shaped moves adapted for hex geometry
     fn knight_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
-        let mut moves = Vec::new();
-        
-        // Hexagonal knight moves (L-shaped in hex coordinates)
-        let knight_moves = [
-            HexCoord::new(2, -1),     // 2 east, 1 northwest
-            HexCoord::new(1, -2),     // 1 east, 2 northwest
-            HexCoord::new(-1, -1),    // 1 west, 1 northwest
-            HexCoord::new(-2, 1),     // 2 west, 1 southeast
-            HexCoord::new(-1, 2),     // 1 west, 2 southeast
-            HexCoord::new(1, 1),      // 1 east, 1 southeast
-            HexCoord::new(3, -2),     // 3 east, 2 northwest
-            HexCoord::new(2, -3),     // 2 east, 3 northwest
-            HexCoord::new(-2, -1),    // 2 west, 1 northwest
-            HexCoord::new(-3, 2),     // 3 west, 2 southeast
-            HexCoord::new(-2, 3),     // 2 west, 3 southeast
-            HexCoord::new(2, 1),      // 2 east, 1 southeast
-        ];
-        
-        for knight_move in knight_moves {
-            let target = from + knight_move;
-            if board.is_valid_coord(target) {
-                moves.push(target);
-            }
-        }
-        
-        moves
+        board.move_tables().knight_leaps(from).to_vec()
     }
 
     /// Pawn moves: Gliński's Chess rules
@@ -186,29 +191,26 @@ impl PieceType {
         let mut moves = Vec::new();
         
         let piece = board.get_piece(from).unwrap();
+        let capture_directions = Self::pawn_captures(piece.color, &board.pawn_movement);
+        let forward_direction = Self::pawn_forward(piece.color, &board.pawn_movement);
         
-        // In Gliński's Chess, pawns move straight forward (1 direction)
-        let (forward_direction, capture_directions) = match piece.color {
-            Color::White => (
-                HexCoord::new(0, 1), // move toward the opponent (increasing r)
-                [
-                    HexCoord::new(-1, 1), // forward-left
-                    HexCoord::new(1, 0),  // forward-right
-                ],
-            ),
-            Color::Black => (
-                HexCoord::new(0, -1), // move toward the opponent (decreasing r)
-                [
-                    HexCoord::new(-1, 0),  // forward-left
-                    HexCoord::new(1, -1),  // forward-right
-                ],
-            ),
-        };
-        
-        // Pawns can move forward to an empty square
+        // Pawns step to empty cells, straight forward or as the variant allows
+        for step in Self::pawn_steps(piece.color, &board.pawn_movement) {
+            let target = from + step;
+            if board.is_valid_coord(target) && !board.is_occupied(target) {
+                moves.push(target);
+            }
+        }
+
+        // From its starting cell a pawn may advance two cells straight ahead through an empty one
         let forward_target = from + forward_direction;
-        if board.is_valid_coord(forward_target) && !board.is_occupied(forward_target) {
-            moves.push(forward_target);
+        let double_target = forward_target + forward_direction;
+        if board.pawn_homes.get(&from) == Some(&piece.color)
+            && moves.contains(&forward_target)
+            && board.is_valid_coord(double_target)
+            && !board.is_occupied(double_target)
+        {
+            moves.push(double_target);
         }
         
         // Pawns capture diagonally forward (2 directions)
@@ -220,6 +222,8 @@ impl PieceType {
                     if target_piece.color != piece.color {
                         moves.push(capture_target);
                     }
+                } else if board.en_passant_capture(from, capture_target).is_some() && !moves.contains(&capture_target) {
+                    moves.push(capture_target);
                 }
             }
         }
@@ -227,19 +231,78 @@ impl PieceType {
         moves
     }
 
+    /// Directions a pawn of `color` may step in without capturing
+    fn pawn_steps(color: Color, movement: &PawnMovement) -> Vec<HexCoord> {
+        let (forward, captures) = Self::pawn_directions(color);
+        match movement {
+            PawnMovement::Standard => vec![forward],
+            PawnMovement::ThreeDirection => vec![forward, captures[0], captures[1]],
+            PawnMovement::Custom(directions) => directions
+                .iter()
+                .map(|&direction| match color {
+                    Color::White => direction,
+                    Color::Black => HexCoord::new(-direction.q, -direction.r),
+                })
+                .collect(),
+        }
+    }
+
+    /// The step a pawn of `color` advances by: the variant's first step, which double steps repeat
+    pub(crate) fn pawn_forward(color: Color, movement: &PawnMovement) -> HexCoord {
+        Self::pawn_steps(color, movement)
+            .first()
+            .copied()
+            .unwrap_or(Self::pawn_directions(color).0)
+    }
+
+    /// The two directions a pawn of `color` captures in: the cells either side of
+    /// its forward step, a sixth of a turn each way
+    pub(crate) fn pawn_captures(color: Color, movement: &PawnMovement) -> [HexCoord; 2] {
+        let forward = Self::pawn_forward(color, movement);
+        [
+            HexCoord::new(-forward.r, forward.q + forward.r),
+            HexCoord::new(forward.q + forward.r, -forward.q),
+        ]
+    }
+
+    /// Forward step and the two capture directions for a pawn of `color` under the standard rules
+    pub(crate) fn pawn_directions(color: Color) -> (HexCoord, [HexCoord; 2]) {
+        // In Gliński's Chess, pawns move straight forward (1 direction)
+        match color {
+            Color::White => (
+                HexCoord::new(0, 1), // move toward the opponent (increasing r)
+                [
+                    HexCoord::new(-1, 1), // forward-left
+                    HexCoord::new(1, 0),  // forward-right
+                ],
+            ),
+            Color::Black => (
+                HexCoord::new(0, -1), // move toward the opponent (decreasing r)
+                [
+                    HexCoord::new(-1, 0),  // forward-left
+                    HexCoord::new(1, -1),  // forward-right
+                ],
+            ),
+        }
+    }
+
     /// Chancellor moves: combination of rook and knight
     fn chancellor_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
-        let mut moves = Vec::new();
-        moves.extend(self.rook_moves(from, board));
-        moves.extend(self.knight_moves(from, board));
-        moves
+        Self::combine(self.rook_moves(from, board), self.knight_moves(from, board))
     }
 
     /// Archbishop moves: combination of bishop and knight
     fn archbishop_moves(&self, from: HexCoord, board: &Board) -> Vec<HexCoord> {
-        let mut moves = Vec::new();
-        moves.extend(self.bishop_moves(from, board));
-        moves.extend(self.knight_moves(from, board));
+        Self::combine(self.bishop_moves(from, board), self.knight_moves(from, board))
+    }
+
+    /// Merge the move lists of a compound piece, keeping each target once
+    fn combine(mut moves: Vec<HexCoord>, other: Vec<HexCoord>) -> Vec<HexCoord> {
+        for target in other {
+            if !moves.contains(&target) {
+                moves.push(target);
+            }
+        }
         moves
     }
 }
@@ -261,6 +324,24 @@ mod tests {
         assert_eq!(moves.len(), 12);
     }
 
+    #[test]
+    fn test_king_steps_across_corners() {
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        let center = HexCoord::new(0, 0);
+        board.place_piece(center, Piece::new(PieceType::King, Color::White)).unwrap();
+
+        // The diagonal steps are exactly the bishop's first cells, and they're guarded like the rest
+        let mut moves = PieceType::King.get_moves(center, &board);
+        moves.sort_by_key(|c| (c.q, c.r));
+        let mut expected: Vec<HexCoord> = center.neighbors().into_iter().chain(center.diagonal_neighbors()).collect();
+        expected.sort_by_key(|c| (c.q, c.r));
+        assert_eq!(moves, expected);
+        for cell in center.diagonal_neighbors() {
+            assert!(board.is_attacked(cell, Color::White));
+        }
+        assert!(!board.is_attacked(HexCoord::new(0, 2), Color::White));
+    }
+
     #[test]
     fn test_rook_moves() {
         let board = Board::new(BoardType::Regular { radius: 2 });
@@ -272,6 +353,92 @@ mod tests {
         assert!(moves.len() > 6);
     }
 
+    #[test]
+    fn test_knight_offsets_are_glinski_leaps() {
+        let origin = HexCoord::new(0, 0);
+        let mut seen = std::collections::HashSet::new();
+
+        for offset in KNIGHT_OFFSETS {
+            assert!(seen.insert(offset), "Duplicate knight offset {:?}", offset);
+            assert_eq!(origin.distance_to(offset), 3, "{:?} is not a 3-step leap", offset);
+
+            // Never on a rook line (that would be a straight 3-step slide)
+            let (q, r, s) = offset.to_cube();
+            assert!(q != 0 && r != 0 && s != 0, "{:?} lies on an orthogonal line", offset);
+        }
+
+        assert_eq!(seen.len(), 12);
+    }
+
+    #[test]
+    fn test_knight_moves_from_center() {
+        let board = Board::new(BoardType::Regular { radius: 5 });
+        let center = HexCoord::new(0, 0);
+
+        let moves = PieceType::Knight.get_moves(center, &board);
+        assert_eq!(moves.len(), 12);
+        assert!(moves.iter().all(|&target| center.distance_to(target) == 3));
+    }
+
+    #[test]
+    fn test_compound_pieces_have_no_duplicate_targets() {
+        let board = Board::new(BoardType::Regular { radius: 5 });
+
+        for piece_type in [PieceType::Queen, PieceType::Chancellor, PieceType::Archbishop] {
+            for &coord in &board.valid_coords {
+                let moves = piece_type.get_moves(coord, &board);
+                let unique: std::collections::HashSet<_> = moves.iter().collect();
+                assert_eq!(unique.len(), moves.len(), "{:?} at {:?}", piece_type, coord);
+            }
+        }
+
+        let center = HexCoord::new(0, 0);
+        let rook = PieceType::Rook.get_moves(center, &board).len();
+        let bishop = PieceType::Bishop.get_moves(center, &board).len();
+        assert_eq!(PieceType::Chancellor.get_moves(center, &board).len(), rook + 12);
+        assert_eq!(PieceType::Archbishop.get_moves(center, &board).len(), bishop + 12);
+    }
+
+    #[test]
+    fn test_pawn_movement_rules() {
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        let center = HexCoord::new(0, 0);
+        board.place_piece(center, Piece::new(PieceType::Pawn, Color::Black)).unwrap();
+        let steps = |board: &Board| {
+            let mut moves = PieceType::Pawn.get_moves(center, board);
+            moves.sort_by_key(|c| (c.q, c.r));
+            moves
+        };
+
+        assert_eq!(steps(&board), vec![HexCoord::new(0, -1)]);
+        board.pawn_movement = PawnMovement::ThreeDirection;
+        assert_eq!(steps(&board), vec![HexCoord::new(-1, 0), HexCoord::new(0, -1), HexCoord::new(1, -1)]);
+        // Custom directions are given for White and mirrored for Black
+        board.pawn_movement = PawnMovement::Custom(vec![HexCoord::new(1, 0), HexCoord::new(-1, 0)]);
+        assert_eq!(steps(&board), vec![HexCoord::new(-1, 0), HexCoord::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_pawn_captures_follow_the_forward_step() {
+        // A White pawn pushing along +q captures either side of that, never onto its push cell
+        let mut board = Board::new(BoardType::Regular { radius: 5 });
+        board.pawn_movement = PawnMovement::Custom(vec![HexCoord::new(1, 0)]);
+        let center = HexCoord::new(0, 0);
+        board.place_piece(center, Piece::new(PieceType::Pawn, Color::White)).unwrap();
+        for (q, r) in [(1, 0), (0, 1), (1, -1)] {
+            board.place_piece(HexCoord::new(q, r), Piece::new(PieceType::Knight, Color::Black)).unwrap();
+        }
+
+        let mut moves = PieceType::Pawn.get_moves(center, &board);
+        moves.sort_by_key(|c| (c.q, c.r));
+        assert_eq!(moves, vec![HexCoord::new(0, 1), HexCoord::new(1, -1)]);
+        let mut attacks = PieceType::Pawn.attacks(center, Color::White, &board);
+        attacks.sort_by_key(|c| (c.q, c.r));
+        assert_eq!(attacks, moves);
+        assert!(board.is_attacked(HexCoord::new(0, 1), Color::White));
+        assert!(!board.is_attacked(HexCoord::new(1, 0), Color::White));
+    }
+
     #[test]
     fn test_piece_symbols() {
         let white_king = Piece::new(PieceType::King, Color::White);
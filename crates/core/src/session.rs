@@ -0,0 +1,439 @@
+use crate::coords::HexCoord;
+use crate::game::{ClockSnapshot, DrawReason, Game, GameError, GameState, MoveOutcome};
+use crate::pieces::{Color, PieceType};
+use crate::variants::VariantConfig;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Time each player gets for the game, plus what they gain back per move
+///
+/// `move_secs` caps each move on its own, whatever is left on the main clock;
+/// with `initial_secs` at 0 there is no main clock and only that cap applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TimeControl {
+    pub initial_secs: u64,
+    pub increment_secs: u64,
+    #[serde(default)]
+    pub move_secs: Option<u64>,
+}
+
+impl TimeControl {
+    /// A fixed time for every move and no clock for the game, e.g. 30 seconds a move
+    pub fn per_move(secs: u64) -> Self {
+        Self { initial_secs: 0, increment_secs: 0, move_secs: Some(secs) }
+    }
+
+    /// Whether the players have a clock for the whole game
+    pub fn has_main_clock(&self) -> bool {
+        self.initial_secs > 0
+    }
+}
+
+impl std::fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.has_main_clock() {
+            if self.initial_secs.is_multiple_of(60) {
+                write!(f, "{}+{}", self.initial_secs / 60, self.increment_secs)?;
+            } else {
+                write!(f, "{}s+{}", self.initial_secs, self.increment_secs)?;
+            }
+        }
+        match (self.has_main_clock(), self.move_secs) {
+            (true, Some(secs)) => write!(f, ", {}s/move", secs),
+            (false, Some(secs)) => write!(f, "{}s/move", secs),
+            (_, None) => Ok(()),
+        }
+    }
+}
+
+/// A refereed game: the rules, both clocks and any draw offer on the table
+///
+/// The host passes in a reading of its own monotonic clock, in milliseconds,
+/// with everything time-sensitive; the session never reads the time itself.
+/// Nobody's time runs until the first move.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSession {
+    pub game: Game,
+    pub time_control: Option<TimeControl>,
+    /// White's and Black's time as of the last move
+    remaining_ms: [u64; 2],
+    last_move_at: Option<u64>,
+    draw_offer: Option<Color>,
+}
+
+impl GameSession {
+    pub fn new(variant: VariantConfig, time_control: Option<TimeControl>) -> Self {
+        let initial_ms = time_control.map_or(0, |tc| tc.initial_secs * 1000);
+        Self {
+            game: Game::new(variant),
+            time_control,
+            remaining_ms: [initial_ms; 2],
+            last_move_at: None,
+            draw_offer: None,
+        }
+    }
+
+    /// Time left on `color`'s clock at `now_ms`, or None without a main clock
+    pub fn remaining_ms(&self, color: Color, now_ms: u64) -> Option<u64> {
+        self.time_control.filter(TimeControl::has_main_clock)?;
+        let banked = self.remaining_ms[seat(color)];
+        match self.last_move_at {
+            Some(at) if color == self.game.current_player && !self.game.is_over() => {
+                Some(banked.saturating_sub(now_ms.saturating_sub(at)))
+            }
+            _ => Some(banked),
+        }
+    }
+
+    /// Time the side to move has left for this move at `now_ms`, or None without a per-move limit
+    ///
+    /// Like the main clock, it doesn't run until the first move.
+    pub fn move_remaining_ms(&self, now_ms: u64) -> Option<u64> {
+        let limit_ms = self.time_control?.move_secs? * 1000;
+        match self.last_move_at {
+            Some(at) if !self.game.is_over() => Some(limit_ms.saturating_sub(now_ms.saturating_sub(at))),
+            _ => Some(limit_ms),
+        }
+    }
+
+    /// Play a move for `color`, charging it the time since the last move
+    pub fn play(
+        &mut self,
+        color: Color,
+        from: HexCoord,
+        to: HexCoord,
+        promotion: Option<PieceType>,
+        now_ms: u64,
+    ) -> Result<MoveOutcome, SessionError> {
+        self.take_turn(color, now_ms, |game| game.make_move_with_promotion(from, to, promotion))
+    }
+
+    /// Drop a piece from `color`'s reserve, in variants with drops
+    pub fn drop_piece(&mut self, color: Color, piece_type: PieceType, to: HexCoord, now_ms: u64) -> Result<MoveOutcome, SessionError> {
+        self.take_turn(color, now_ms, |game| game.drop_piece(piece_type, to))
+    }
+
+    fn take_turn(
+        &mut self,
+        color: Color,
+        now_ms: u64,
+        turn: impl FnOnce(&mut Game) -> Result<MoveOutcome, GameError>,
+    ) -> Result<MoveOutcome, SessionError> {
+        if self.game.is_over() {
+            return Err(GameError::GameOver.into());
+        }
+        // Any message can find the side to move out of time, not just its own late move
+        if self.check_flag(now_ms) {
+            return Err(SessionError::OutOfTime(self.game.current_player));
+        }
+        if color != self.game.current_player {
+            return Err(SessionError::NotYourTurn);
+        }
+        let outcome = turn(&mut self.game)?;
+
+        if let Some(tc) = self.time_control.filter(TimeControl::has_main_clock) {
+            let think_ms = self.last_move_at.map_or(0, |at| now_ms.saturating_sub(at));
+            let remaining = &mut self.remaining_ms[seat(color)];
+            *remaining = *remaining - think_ms + tc.increment_secs * 1000;
+            let remaining_ms = *remaining;
+            self.game.record_clock(ClockSnapshot { think_ms, remaining_ms })?;
+        }
+        self.last_move_at = Some(now_ms);
+        // Moving answers an opponent's offer with a no; the mover's own offer stands
+        if self.draw_offer != Some(color) {
+            self.draw_offer = None;
+        }
+        Ok(outcome)
+    }
+
+    /// When the side to move runs out of time, on the main clock or for this
+    /// move, or None while no time is running
+    pub fn flag_at_ms(&self) -> Option<u64> {
+        let at = self.last_move_at.filter(|_| !self.game.is_over())?;
+        let tc = self.time_control?;
+        let main = tc.has_main_clock().then(|| at + self.remaining_ms[seat(self.game.current_player)]);
+        let per_move = tc.move_secs.map(|secs| at + secs * 1000);
+        main.into_iter().chain(per_move).min()
+    }
+
+    /// End the game if the side to move has run out of time, on the main clock
+    /// or for this move; true if it just did
+    ///
+    /// A flag loses, unless the other side has too little left to mate.
+    pub fn check_flag(&mut self, now_ms: u64) -> bool {
+        let color = self.game.current_player;
+        if self.game.is_over() || self.last_move_at.is_none() {
+            return false;
+        }
+        let left_ms = self.remaining_ms(color, now_ms);
+        if left_ms != Some(0) && self.move_remaining_ms(now_ms) != Some(0) {
+            return false;
+        }
+        // Bank what the main clock shows, so the game ends with the time actually used
+        if let Some(left_ms) = left_ms {
+            self.remaining_ms[seat(color)] = left_ms;
+        }
+        let opponent = other(color);
+        let rule = self.game.variant.draw_rules.insufficient_material;
+        let opponent_pieces = self.game.board.pieces.values().filter(|piece| piece.color == opponent);
+        let opponent_hand_empty = self.game.reserves.get(opponent).is_empty();
+        self.game.game_state = if opponent_hand_empty && rule.applies_to(opponent_pieces) {
+            GameState::Draw(DrawReason::InsufficientMaterial)
+        } else {
+            GameState::TimedOut(color)
+        };
+        self.draw_offer = None;
+        true
+    }
+
+    /// The side whose draw offer is waiting for an answer
+    pub fn draw_offer(&self) -> Option<Color> {
+        self.draw_offer
+    }
+
+    /// Offer a draw; offering back to a pending offer agrees to it
+    pub fn offer_draw(&mut self, color: Color) -> Result<(), SessionError> {
+        if self.game.is_over() {
+            return Err(GameError::GameOver.into());
+        }
+        if self.draw_offer == Some(other(color)) {
+            return self.accept_draw(color);
+        }
+        self.draw_offer = Some(color);
+        Ok(())
+    }
+
+    pub fn accept_draw(&mut self, color: Color) -> Result<(), SessionError> {
+        if self.draw_offer != Some(other(color)) {
+            return Err(SessionError::NoDrawOffer);
+        }
+        self.game.agree_draw()?;
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    pub fn decline_draw(&mut self, color: Color) -> Result<(), SessionError> {
+        if self.draw_offer != Some(other(color)) {
+            return Err(SessionError::NoDrawOffer);
+        }
+        self.draw_offer = None;
+        Ok(())
+    }
+
+    pub fn resign(&mut self, color: Color) -> Result<(), SessionError> {
+        self.game.resign(color)?;
+        self.draw_offer = None;
+        Ok(())
+    }
+}
+
+/// Two players' score over a run of games in which they swap colours each game,
+/// optionally a best-of-N match that ends once one of them can't be caught
+///
+/// Players are numbered 0 for whoever had White in the first game, and 1.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MatchSeries {
+    /// Games in the match, None for open-ended rematches
+    pub best_of: Option<u32>,
+    /// Each player's points
+    pub scores: [f32; 2],
+    /// Games finished so far
+    pub played: u32,
+}
+
+impl MatchSeries {
+    pub fn new(best_of: Option<u32>) -> Self {
+        Self { best_of, ..Self::default() }
+    }
+
+    /// The player who has White in the game being played
+    pub fn white_player(&self) -> usize {
+        (self.played % 2) as usize
+    }
+
+    /// Count a finished game's (white, black) score
+    pub fn record(&mut self, (white, black): (f32, f32)) {
+        let white_player = self.white_player();
+        self.scores[white_player] += white;
+        self.scores[1 - white_player] += black;
+        self.played += 1;
+    }
+
+    /// The player who has won the match: more than half of its points
+    pub fn winner(&self) -> Option<usize> {
+        let best_of = self.best_of? as f32;
+        self.scores.iter().position(|&score| score > best_of / 2.0)
+    }
+
+    /// Whether the match is over, won outright or with every game played
+    pub fn is_decided(&self) -> bool {
+        self.winner().is_some() || self.best_of.is_some_and(|best_of| self.played >= best_of)
+    }
+}
+
+fn seat(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn other(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error("Not your turn")]
+    NotYourTurn,
+    #[error("{0:?} ran out of time")]
+    OutOfTime(Color),
+    #[error("There is no draw offer to answer")]
+    NoDrawOffer,
+    #[error(transparent)]
+    Game(#[from] GameError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variants::Variants;
+
+    fn coord(q: i32, r: i32) -> HexCoord {
+        HexCoord::new(q, r)
+    }
+
+    #[test]
+    fn test_clocks_and_flag() {
+        let blitz = TimeControl { initial_secs: 60, increment_secs: 2, move_secs: None };
+        assert_eq!(blitz.to_string(), "1+2");
+        let mut session = GameSession::new(Variants::glinski_chess(), Some(blitz));
+
+        // f5-f6 starts Black's clock; e8-e7 after 10s
+        session.play(Color::White, coord(0, -1), coord(0, 0), None, 5_000).unwrap();
+        assert!(matches!(
+            session.play(Color::White, coord(1, -1), coord(1, 0), None, 6_000),
+            Err(SessionError::NotYourTurn)
+        ));
+        session.play(Color::Black, coord(-1, 2), coord(-1, 1), None, 15_000).unwrap();
+        assert_eq!(session.remaining_ms(Color::White, 15_000), Some(62_000));
+        assert_eq!(session.remaining_ms(Color::Black, 15_000), Some(52_000));
+        assert_eq!(
+            session.game.clock_history(Color::Black),
+            vec![Some(ClockSnapshot { think_ms: 10_000, remaining_ms: 52_000 })]
+        );
+
+        // White's flag falls with Black still holding mating material, noticed
+        // even when it's Black who speaks up
+        assert_eq!(session.flag_at_ms(), Some(77_000));
+        assert!(!session.check_flag(76_999));
+        assert!(matches!(
+            session.play(Color::Black, coord(0, 2), coord(0, 1), None, 77_000),
+            Err(SessionError::OutOfTime(Color::White))
+        ));
+        assert_eq!(session.game.game_state, GameState::TimedOut(Color::White));
+        assert_eq!(session.flag_at_ms(), None);
+        assert_eq!(session.game.score(), Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn test_flag_against_bare_king_is_a_draw() {
+        let blitz = TimeControl { initial_secs: 1, increment_secs: 0, move_secs: None };
+        let mut session = GameSession::new(Variants::glinski_chess(), Some(blitz));
+        session.play(Color::White, coord(0, -1), coord(0, 0), None, 0).unwrap();
+        session
+            .game
+            .board
+            .pieces
+            .retain(|_, piece| piece.color == Color::Black || piece.piece_type == PieceType::King);
+
+        assert!(session.check_flag(1_000));
+        assert_eq!(session.game.game_state, GameState::Draw(DrawReason::InsufficientMaterial));
+    }
+
+    #[test]
+    fn test_move_time_limit() {
+        let casual = TimeControl::per_move(30);
+        assert_eq!(casual.to_string(), "30s/move");
+        let mut session = GameSession::new(Variants::glinski_chess(), Some(casual));
+        assert_eq!(session.remaining_ms(Color::White, 0), None);
+        assert_eq!(session.move_remaining_ms(0), Some(30_000));
+
+        // Each move gets the full limit again, however long the last one took
+        session.play(Color::White, coord(0, -1), coord(0, 0), None, 1_000).unwrap();
+        session.play(Color::Black, coord(-1, 2), coord(-1, 1), None, 30_000).unwrap();
+        assert_eq!(session.move_remaining_ms(40_000), Some(20_000));
+        assert!(session.game.clock_history(Color::Black).iter().all(Option::is_none));
+        assert!(matches!(
+            session.play(Color::White, coord(0, 0), coord(-1, 1), None, 60_000),
+            Err(SessionError::OutOfTime(Color::White))
+        ));
+        assert_eq!(session.game.game_state, GameState::TimedOut(Color::White));
+
+        // Alongside a main clock the move limit can run out first
+        let capped = TimeControl { initial_secs: 300, increment_secs: 0, move_secs: Some(20) };
+        assert_eq!(capped.to_string(), "5+0, 20s/move");
+        let mut session = GameSession::new(Variants::glinski_chess(), Some(capped));
+        session.play(Color::White, coord(0, -1), coord(0, 0), None, 0).unwrap();
+        assert_eq!(session.flag_at_ms(), Some(20_000));
+        assert!(!session.check_flag(19_999));
+        assert!(session.check_flag(20_000));
+        assert_eq!(session.remaining_ms(Color::Black, 20_000), Some(280_000));
+    }
+
+    #[test]
+    fn test_draw_offers() {
+        let mut session = GameSession::new(Variants::glinski_chess(), None);
+        assert!(matches!(session.accept_draw(Color::Black), Err(SessionError::NoDrawOffer)));
+
+        // Moving on declines the offer
+        session.offer_draw(Color::White).unwrap();
+        session.play(Color::White, coord(0, -1), coord(0, 0), None, 0).unwrap();
+        assert_eq!(session.draw_offer(), Some(Color::White));
+        session.play(Color::Black, coord(-1, 2), coord(-1, 1), None, 0).unwrap();
+        assert_eq!(session.draw_offer(), None);
+
+        session.offer_draw(Color::White).unwrap();
+        session.decline_draw(Color::Black).unwrap();
+        session.offer_draw(Color::White).unwrap();
+        session.offer_draw(Color::Black).unwrap();
+        assert_eq!(session.game.game_state, GameState::Draw(DrawReason::Agreement));
+        assert!(session.resign(Color::White).is_err());
+        assert_eq!(session.remaining_ms(Color::White, 0), None);
+    }
+
+    #[test]
+    fn test_match_series() {
+        let mut series = MatchSeries::new(Some(3));
+        assert_eq!(series.white_player(), 0);
+
+        // Player 0 wins with White, then draws with Black
+        series.record((1.0, 0.0));
+        assert_eq!(series.white_player(), 1);
+        series.record((0.5, 0.5));
+        assert_eq!(series.scores, [1.5, 0.5]);
+        assert!(!series.is_decided());
+
+        // Winning the third game with White takes 2½ of 3
+        series.record((1.0, 0.0));
+        assert_eq!(series.winner(), Some(0));
+        assert!(series.is_decided());
+
+        // Two wins in a best of three settle it early
+        let mut series = MatchSeries::new(Some(3));
+        series.record((0.0, 1.0));
+        series.record((1.0, 0.0));
+        assert_eq!(series.winner(), Some(1));
+
+        // Open-ended rematches never end on their own
+        let mut series = MatchSeries::new(None);
+        for _ in 0..5 {
+            series.record((1.0, 0.0));
+        }
+        assert_eq!(series.scores, [3.0, 2.0]);
+        assert!(!series.is_decided());
+    }
+}
@@ -0,0 +1,461 @@
+use crate::board::Board;
+use crate::coords::{BoardType, HexCoord};
+use crate::move_tables::MoveTables;
+use crate::pieces::{Color, PieceType};
+use std::collections::HashMap;
+
+/// Stored value for draws and illegal positions
+const DRAW: u8 = u8::MAX;
+
+/// Result of a tablebase probe, from the side to move's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TablebaseResult {
+    /// The side to move mates in `plies` half-moves with best play
+    Win { plies: u8 },
+    /// The side to move is mated in `plies` half-moves with best play
+    Loss { plies: u8 },
+    Draw,
+}
+
+/// One position of a K+X vs K ending, as cell indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    strong_king: usize,
+    piece: usize,
+    weak_king: usize,
+    strong_to_move: bool,
+}
+
+/// Perfect-play table for king and one piece against a bare king
+///
+/// Built by retrograde analysis: mated positions first, then every position
+/// that reaches them, one ply further out per round. Each position stores its
+/// distance to mate in a single byte, so K+R vs K on Mini Hexchess takes about
+/// 100 KB. Stalemates and positions where the lone king wins the piece are draws.
+#[derive(Debug, Clone)]
+pub struct Tablebase {
+    board_type: BoardType,
+    piece_type: PieceType,
+    cells: Vec<HexCoord>,
+    index: HashMap<HexCoord, usize>,
+    king_steps: Vec<Vec<usize>>,
+    leaps: Vec<Vec<usize>>,
+    rays: Vec<Vec<Vec<usize>>>,
+    plies: Vec<u8>,
+}
+
+impl Tablebase {
+    /// Solve K + `piece_type` vs K on `board_type`
+    pub fn generate(board_type: BoardType, piece_type: PieceType) -> Result<Self, TablebaseError> {
+        let mut tablebase = Self::empty(board_type, piece_type)?;
+        tablebase.solve();
+        Ok(tablebase)
+    }
+
+    /// Rebuild a table from the bytes returned by `as_bytes`
+    pub fn from_bytes(board_type: BoardType, piece_type: PieceType, bytes: &[u8]) -> Result<Self, TablebaseError> {
+        let mut tablebase = Self::empty(board_type, piece_type)?;
+        if bytes.len() != tablebase.plies.len() {
+            return Err(TablebaseError::SizeMismatch { expected: tablebase.plies.len(), found: bytes.len() });
+        }
+        tablebase.plies.copy_from_slice(bytes);
+        Ok(tablebase)
+    }
+
+    /// The compact table: one distance-to-mate byte per position
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.plies
+    }
+
+    pub fn piece_type(&self) -> PieceType {
+        self.piece_type
+    }
+
+    /// Look up a position, if it is covered by this table
+    pub fn probe(&self, board: &Board, to_move: Color) -> Option<TablebaseResult> {
+        let (position, _) = self.position_of(board, to_move)?;
+        if !self.is_legal(position) {
+            return None;
+        }
+
+        let plies = self.plies[self.position_index(position)];
+        Some(match (plies, position.strong_to_move) {
+            (DRAW, _) => TablebaseResult::Draw,
+            (plies, true) => TablebaseResult::Win { plies },
+            (plies, false) => TablebaseResult::Loss { plies },
+        })
+    }
+
+    /// The best move for the side to move, if the position is covered
+    ///
+    /// The winning side picks the fastest mate, the losing side the slowest,
+    /// and in a draw the lone king keeps the draw.
+    pub fn best_move(&self, board: &Board, to_move: Color) -> Option<(HexCoord, HexCoord)> {
+        let (position, reflected) = self.position_of(board, to_move)?;
+        if !self.is_legal(position) {
+            return None;
+        }
+
+        let value = |successor: Option<Position>| match successor {
+            Some(next) => self.plies[self.position_index(next)],
+            None => DRAW,
+        };
+
+        let successors = self.successors(position);
+        let best = if position.strong_to_move {
+            successors.into_iter().min_by_key(|&(_, _, next)| value(next))
+        } else if self.plies[self.position_index(position)] == DRAW {
+            successors.into_iter().find(|&(_, _, next)| value(next) == DRAW)
+        } else {
+            successors.into_iter().max_by_key(|&(_, _, next)| value(next))
+        }?;
+
+        let unmap = |cell: usize| {
+            let coord = self.cells[cell];
+            if reflected { reflect(coord) } else { coord }
+        };
+        Some((unmap(best.0), unmap(best.1)))
+    }
+
+    fn empty(board_type: BoardType, piece_type: PieceType) -> Result<Self, TablebaseError> {
+        if matches!(piece_type, PieceType::King | PieceType::Pawn) {
+            return Err(TablebaseError::UnsupportedPiece(piece_type));
+        }
+
+        let mut cells: Vec<_> = board_type.valid_coords().into_iter().collect();
+        cells.sort_by_key(|coord| (coord.q, coord.r));
+        let index: HashMap<_, _> = cells.iter().enumerate().map(|(i, &coord)| (coord, i)).collect();
+        let tables = MoveTables::for_board_type(&board_type);
+        let to_indices = |coords: &[HexCoord]| coords.iter().map(|coord| index[coord]).collect::<Vec<_>>();
+
+        let slides_orthogonally = matches!(piece_type, PieceType::Rook | PieceType::Queen | PieceType::Chancellor);
+        let slides_diagonally = matches!(piece_type, PieceType::Bishop | PieceType::Queen | PieceType::Archbishop);
+        let leaps = matches!(piece_type, PieceType::Knight | PieceType::Chancellor | PieceType::Archbishop);
+
+        let king_steps = cells.iter().map(|&cell| to_indices(tables.king_steps(cell))).collect();
+        let leap_targets = cells
+            .iter()
+            .map(|&cell| if leaps { to_indices(tables.knight_leaps(cell)) } else { Vec::new() })
+            .collect();
+        let rays = cells
+            .iter()
+            .map(|&cell| {
+                let mut rays = Vec::new();
+                if slides_orthogonally {
+                    rays.extend(tables.orthogonal_rays(cell).iter().map(|ray| to_indices(ray)));
+                }
+                if slides_diagonally {
+                    rays.extend(tables.diagonal_rays(cell).iter().map(|ray| to_indices(ray)));
+                }
+                rays
+            })
+            .collect();
+
+        let positions = cells.len() * cells.len() * cells.len() * 2;
+        Ok(Self {
+            board_type,
+            piece_type,
+            cells,
+            index,
+            king_steps,
+            leaps: leap_targets,
+            rays,
+            plies: vec![DRAW; positions],
+        })
+    }
+
+    fn solve(&mut self) {
+        let count = self.cells.len();
+        let all_positions = || {
+            (0..count).flat_map(move |strong_king| {
+                (0..count).flat_map(move |piece| {
+                    (0..count).flat_map(move |weak_king| {
+                        [true, false].into_iter().map(move |strong_to_move| Position {
+                            strong_king,
+                            piece,
+                            weak_king,
+                            strong_to_move,
+                        })
+                    })
+                })
+            })
+        };
+
+        // Round 0: the lone king is mated
+        let mated: Vec<_> = all_positions()
+            .filter(|&p| !p.strong_to_move && self.is_legal(p))
+            .filter(|&p| self.weak_in_check(p) && self.successors(p).is_empty())
+            .map(|p| self.position_index(p))
+            .collect();
+        for index in mated {
+            self.plies[index] = 0;
+        }
+
+        let mut quiet_rounds = 0;
+        for round in 1..DRAW {
+            let strong_round = round % 2 == 1;
+            let mut solved = Vec::new();
+
+            for position in all_positions() {
+                if position.strong_to_move != strong_round
+                    || self.plies[self.position_index(position)] != DRAW
+                    || !self.is_legal(position)
+                {
+                    continue;
+                }
+
+                let successors = self.successors(position);
+                let resolved = |next: Option<Position>| {
+                    next.map(|next| self.plies[self.position_index(next)]).filter(|&plies| plies < round)
+                };
+                let wins = if strong_round {
+                    successors.iter().any(|&(_, _, next)| resolved(next) == Some(round - 1))
+                } else {
+                    !successors.is_empty() && successors.iter().all(|&(_, _, next)| resolved(next).is_some())
+                };
+                if wins {
+                    solved.push(self.position_index(position));
+                }
+            }
+
+            // Two quiet rounds in a row means nothing further can be forced
+            quiet_rounds = if solved.is_empty() { quiet_rounds + 1 } else { 0 };
+            if quiet_rounds == 2 {
+                break;
+            }
+            for index in solved {
+                self.plies[index] = round;
+            }
+        }
+    }
+
+    /// Legal moves as (from, to, resulting position); `None` means the lone
+    /// king captured the piece and the game is a draw
+    fn successors(&self, position: Position) -> Vec<(usize, usize, Option<Position>)> {
+        let Position { strong_king, piece, weak_king, .. } = position;
+        let mut moves = Vec::new();
+
+        if position.strong_to_move {
+            for &step in &self.king_steps[strong_king] {
+                if step != piece && step != weak_king && !self.king_steps[weak_king].contains(&step) {
+                    moves.push((strong_king, step, Some(Position { strong_king: step, strong_to_move: false, ..position })));
+                }
+            }
+            for target in self.piece_targets(piece, &[strong_king, weak_king]) {
+                if target != strong_king && target != weak_king {
+                    moves.push((piece, target, Some(Position { piece: target, strong_to_move: false, ..position })));
+                }
+            }
+        } else {
+            for &step in &self.king_steps[weak_king] {
+                if step == strong_king || self.king_steps[strong_king].contains(&step) {
+                    continue;
+                }
+                if step == piece {
+                    moves.push((weak_king, step, None));
+                } else if !self.piece_attacks(piece, step, strong_king) {
+                    moves.push((weak_king, step, Some(Position { weak_king: step, strong_to_move: true, ..position })));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Cells the piece reaches, stopping each ray at the first blocker
+    fn piece_targets(&self, from: usize, blockers: &[usize]) -> Vec<usize> {
+        let mut targets = self.leaps[from].clone();
+        for ray in &self.rays[from] {
+            for &cell in ray {
+                targets.push(cell);
+                if blockers.contains(&cell) {
+                    break;
+                }
+            }
+        }
+        targets
+    }
+
+    fn piece_attacks(&self, from: usize, target: usize, blocker: usize) -> bool {
+        self.piece_targets(from, &[blocker, target]).contains(&target)
+    }
+
+    fn weak_in_check(&self, position: Position) -> bool {
+        self.piece_attacks(position.piece, position.weak_king, position.strong_king)
+    }
+
+    fn is_legal(&self, position: Position) -> bool {
+        let Position { strong_king, piece, weak_king, strong_to_move } = position;
+        strong_king != piece
+            && strong_king != weak_king
+            && piece != weak_king
+            && !self.king_steps[strong_king].contains(&weak_king)
+            && !(strong_to_move && self.weak_in_check(position))
+    }
+
+    fn position_index(&self, position: Position) -> usize {
+        let count = self.cells.len();
+        ((position.strong_king * count + position.piece) * count + position.weak_king) * 2
+            + usize::from(position.strong_to_move)
+    }
+
+    /// Locate a board in the table, reflecting it if Black has the extra piece
+    fn position_of(&self, board: &Board, to_move: Color) -> Option<(Position, bool)> {
+        if board.board_type != self.board_type || board.pieces.len() != 3 {
+            return None;
+        }
+
+        let (&piece_coord, piece) = board
+            .pieces
+            .iter()
+            .find(|(_, piece)| piece.piece_type == self.piece_type)?;
+        let strong = piece.color;
+        let weak = match strong {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+        let reflected = strong == Color::Black;
+        let cell = |coord: HexCoord| {
+            let coord = if reflected { reflect(coord) } else { coord };
+            self.index.get(&coord).copied()
+        };
+
+        Some((
+            Position {
+                strong_king: cell(board.get_king(strong)?)?,
+                piece: cell(piece_coord)?,
+                weak_king: cell(board.get_king(weak)?)?,
+                strong_to_move: to_move == strong,
+            },
+            reflected,
+        ))
+    }
+}
+
+/// Swap the White and Black halves of a regular board
+fn reflect(coord: HexCoord) -> HexCoord {
+    HexCoord::new(coord.q, -coord.r - coord.q)
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TablebaseError {
+    #[error("Tablebases cover a single non-pawn piece, not {0:?}")]
+    UnsupportedPiece(PieceType),
+    #[error("Expected {expected} table bytes, found {found}")]
+    SizeMismatch { expected: usize, found: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Game, GameState};
+    use crate::pieces::Piece;
+    use crate::variants::Variants;
+
+    const MINI: BoardType = BoardType::Small { radius: 3 };
+
+    fn krk() -> &'static Tablebase {
+        static TABLE: std::sync::OnceLock<Tablebase> = std::sync::OnceLock::new();
+        TABLE.get_or_init(|| Tablebase::generate(MINI, PieceType::Rook).unwrap())
+    }
+
+    fn mini_game(pieces: &[(i32, i32, PieceType, Color)], to_move: Color) -> Game {
+        let mut game = Game::new(Variants::mini_hexchess());
+        game.board.pieces.clear();
+        for &(q, r, piece_type, color) in pieces {
+            game.board.place_piece(HexCoord::new(q, r), Piece::new(piece_type, color)).unwrap();
+        }
+        game.current_player = to_move;
+        game
+    }
+
+    #[test]
+    fn test_rejects_pawns_and_bad_sizes() {
+        assert_eq!(
+            Tablebase::generate(MINI, PieceType::Pawn).unwrap_err(),
+            TablebaseError::UnsupportedPiece(PieceType::Pawn)
+        );
+        assert!(matches!(
+            Tablebase::from_bytes(MINI, PieceType::Rook, &[0; 3]),
+            Err(TablebaseError::SizeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_best_play_mates_on_schedule() {
+        let table = krk();
+        let mut game = mini_game(
+            &[
+                (0, 0, PieceType::King, Color::White),
+                (-3, 3, PieceType::Rook, Color::White),
+                (2, 1, PieceType::King, Color::Black),
+            ],
+            Color::White,
+        );
+
+        let Some(TablebaseResult::Win { plies }) = table.probe(&game.board, Color::White) else {
+            panic!("K+R vs K should be a win");
+        };
+
+        // Follow the table's own advice for both sides, checking every move with the rules engine
+        for remaining in (1..=plies).rev() {
+            let to_move = game.current_player;
+            let expected = match to_move {
+                Color::White => TablebaseResult::Win { plies: remaining },
+                Color::Black => TablebaseResult::Loss { plies: remaining },
+            };
+            assert_eq!(table.probe(&game.board, to_move), Some(expected));
+
+            let (from, to) = table.best_move(&game.board, to_move).unwrap();
+            game.make_move(from, to).unwrap();
+        }
+
+        assert!(matches!(game.game_state, GameState::Checkmate(_)));
+        assert_eq!(table.probe(&game.board, Color::Black), Some(TablebaseResult::Loss { plies: 0 }));
+    }
+
+    #[test]
+    fn test_reflected_probe_for_black() {
+        let table = krk();
+        let white = mini_game(
+            &[
+                (0, -1, PieceType::King, Color::White),
+                (2, -2, PieceType::Rook, Color::White),
+                (-1, 3, PieceType::King, Color::Black),
+            ],
+            Color::Black,
+        );
+        let black = mini_game(
+            &[
+                (0, 1, PieceType::King, Color::Black),
+                (2, 0, PieceType::Rook, Color::Black),
+                (-1, -2, PieceType::King, Color::White),
+            ],
+            Color::White,
+        );
+
+        let result = table.probe(&white.board, Color::Black);
+        assert!(matches!(result, Some(TablebaseResult::Loss { .. })));
+        assert_eq!(table.probe(&black.board, Color::White), result);
+    }
+
+    #[test]
+    fn test_hanging_rook_is_a_draw_and_bytes_round_trip() {
+        let table = krk();
+        // The black king stands next to an unprotected rook
+        let game = mini_game(
+            &[
+                (-3, 0, PieceType::King, Color::White),
+                (2, 0, PieceType::Rook, Color::White),
+                (3, 0, PieceType::King, Color::Black),
+            ],
+            Color::Black,
+        );
+        assert_eq!(table.probe(&game.board, Color::Black), Some(TablebaseResult::Draw));
+        assert_eq!(table.best_move(&game.board, Color::Black), Some((HexCoord::new(3, 0), HexCoord::new(2, 0))));
+
+        let copy = Tablebase::from_bytes(MINI, PieceType::Rook, table.as_bytes()).unwrap();
+        assert_eq!(copy.probe(&game.board, Color::Black), Some(TablebaseResult::Draw));
+        assert_eq!(table.as_bytes().len(), 37 * 37 * 37 * 2);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::board::{Board, CellColor};
+use crate::coords::HexCoord;
+use crate::pieces::{Color, PieceType};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Cell fills, matching the game's default board theme
+const LIGHT: &str = "#f2edd6";
+const MEDIUM: &str = "#bd9963";
+const DARK: &str = "#614d36";
+
+/// Distance from a cell's centre to its corners, in `HexCoord::to_pixel` units
+const CELL_RADIUS: f32 = 0.5;
+
+/// Size of the file and rank labels, in `HexCoord::to_pixel` units
+const LABEL_SIZE: f32 = 0.3;
+
+/// How `board_svg_styled` draws a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgStyle {
+    /// Width of the picture in pixels; the height follows the board's shape
+    pub width: u32,
+    /// Name the files below the board and the ranks at their left ends
+    pub labels: bool,
+    /// Turn the board around, with Black's side at the bottom
+    pub flipped: bool,
+}
+
+impl SvgStyle {
+    /// A plain picture `width` pixels wide, White at the bottom and no labels
+    pub fn new(width: u32) -> Self {
+        Self { width, labels: false, flipped: false }
+    }
+}
+
+/// A position drawn as a standalone SVG document `width` pixels wide, for thumbnails
+/// on pages and in messages; it needs no GPU or fonts beyond a generic sans-serif
+pub fn board_svg(board: &Board, width: u32) -> String {
+    board_svg_styled(board, SvgStyle::new(width))
+}
+
+/// A position drawn as a standalone SVG document, labelled and turned as `style` asks
+pub fn board_svg_styled(board: &Board, style: SvgStyle) -> String {
+    let mut cells: Vec<HexCoord> = board.valid_coords.iter().copied().collect();
+    cells.sort_by_key(|coord| (coord.q, coord.r));
+    let place = |coord: HexCoord| {
+        let (x, y) = coord.to_pixel();
+        if style.flipped { (-x, -y) } else { (x, y) }
+    };
+
+    // Files are columns of one q and ranks rows of one r: a file's name sits on the
+    // cell below its last one and a rank's on the cell before its first
+    let mut labels: Vec<(HexCoord, String)> = Vec::new();
+    if style.labels {
+        let mut files: HashMap<i32, HexCoord> = HashMap::new();
+        let mut ranks: HashMap<i32, HexCoord> = HashMap::new();
+        for &coord in &cells {
+            files.entry(coord.q).and_modify(|low| if coord.r < low.r { *low = coord }).or_insert(coord);
+            ranks.entry(coord.r).and_modify(|first| if coord.q < first.q { *first = coord }).or_insert(coord);
+        }
+        let name = |coord: HexCoord| board.board_type.square_name(coord);
+        for coord in files.into_values() {
+            if let Some(file) = name(coord).and_then(|name| name.chars().next()) {
+                labels.push((coord + HexCoord::new(0, -1), file.to_string()));
+            }
+        }
+        for coord in ranks.into_values() {
+            if let Some(rank) = name(coord).map(|name| name[1..].to_string()) {
+                labels.push((coord + HexCoord::new(-1, 0), rank));
+            }
+        }
+        labels.sort_by_key(|(coord, _)| (coord.q, coord.r));
+    }
+
+    // Flat-top cells reach CELL_RADIUS left and right, and √3/2 of it up and down
+    let half_height = CELL_RADIUS * 3.0_f32.sqrt() / 2.0;
+    let (mut min_x, mut min_y) = (f32::INFINITY, f32::INFINITY);
+    let (mut max_x, mut max_y) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for coord in cells.iter().chain(labels.iter().map(|(coord, _)| coord)) {
+        let (x, y) = place(*coord);
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
+    if cells.is_empty() {
+        (min_x, min_y, max_x, max_y) = (0.0, 0.0, 0.0, 0.0);
+    }
+    let (min_x, min_y) = (min_x - CELL_RADIUS, min_y - half_height);
+    let (view_width, view_height) = (max_x + CELL_RADIUS - min_x, max_y + half_height - min_y);
+    let width = style.width;
+    let height = (width as f32 * view_height / view_width).round() as u32;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{:.3} {:.3} {:.3} {:.3}">"#,
+        width, height, min_x, min_y, view_width, view_height
+    );
+
+    for &coord in &cells {
+        let (x, y) = place(coord);
+        let fill = match board.cell_colors.get(&coord) {
+            Some(CellColor::Medium) => MEDIUM,
+            Some(CellColor::Dark) => DARK,
+            Some(CellColor::Light) | None => LIGHT,
+        };
+        let corners: Vec<String> = (0..6)
+            .map(|corner| {
+                let angle = std::f32::consts::PI / 3.0 * corner as f32;
+                format!("{:.3},{:.3}", x + CELL_RADIUS * angle.cos(), y + CELL_RADIUS * angle.sin())
+            })
+            .collect();
+        let _ = write!(svg, r#"<polygon points="{}" fill="{}"/>"#, corners.join(" "), fill);
+    }
+
+    for &coord in &cells {
+        let Some(piece) = board.get_piece(coord) else {
+            continue;
+        };
+        let (x, y) = place(coord);
+        let (fill, ink) = match piece.color {
+            Color::White => ("#ffffff", "#000000"),
+            Color::Black => ("#333333", "#ffffff"),
+        };
+        let radius = if piece.piece_type == PieceType::Pawn { 0.28 } else { 0.34 };
+        let _ = write!(
+            svg,
+            concat!(
+                r#"<circle cx="{x:.3}" cy="{y:.3}" r="{radius}" fill="{fill}" stroke="black" stroke-width="0.03"/>"#,
+                r#"<text x="{x:.3}" y="{y:.3}" fill="{ink}" font-family="sans-serif" font-size="0.36" "#,
+                r#"text-anchor="middle" dominant-baseline="central">{letter}</text>"#,
+            ),
+            x = x,
+            y = y,
+            radius = radius,
+            fill = fill,
+            ink = ink,
+            letter = piece.symbol().to_ascii_uppercase(),
+        );
+    }
+
+    for (coord, label) in &labels {
+        let (x, y) = place(*coord);
+        let _ = write!(
+            svg,
+            concat!(
+                r#"<text x="{:.3}" y="{:.3}" fill="{}" font-family="sans-serif" font-size="{}" "#,
+                r#"text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            ),
+            x, y, DARK, LABEL_SIZE, label,
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+    use crate::variants::Variants;
+
+    #[test]
+    fn test_board_svg() {
+        let game = Game::new(Variants::glinski_chess());
+        let svg = board_svg(&game.board, 240);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"240\""));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<polygon").count(), 91);
+        assert_eq!(svg.matches("<circle").count(), game.board.pieces.len());
+        assert_eq!(svg.matches("fill=\"#333333\"").count(), game.board.get_pieces_by_color(Color::Black).len());
+
+        // Gliński's board is 11 cells tall and 11 files wide, the files packed closer than the cells
+        let height: u32 = svg.split("height=\"").nth(1).unwrap().split('"').next().unwrap().parse().unwrap();
+        assert_eq!(height, 269);
+
+        // The same position always draws the same picture
+        assert_eq!(svg, board_svg(&game.board.clone(), 240));
+    }
+
+    #[test]
+    fn test_board_svg_styled() {
+        let game = Game::new(Variants::glinski_chess());
+        let plain = board_svg(&game.board, 240);
+        assert_eq!(board_svg_styled(&game.board, SvgStyle::new(240)), plain);
+
+        // Eleven file letters and eleven rank numbers, beside the pieces' letters
+        let labelled = board_svg_styled(&game.board, SvgStyle { labels: true, ..SvgStyle::new(240) });
+        let pieces = game.board.pieces.len();
+        assert_eq!(labelled.matches("<text").count(), pieces + 22);
+        assert!(labelled.contains(">a</text>") && labelled.contains(">l</text>") && !labelled.contains(">j</text>"));
+        assert!(labelled.contains(">1</text>") && labelled.contains(">11</text>"));
+
+        // Turned around, White's king at f1 is drawn at the top of the picture
+        let king = HexCoord::from_file_rank('f', 1).unwrap();
+        let (x, y) = king.to_pixel();
+        let flipped = board_svg_styled(&game.board, SvgStyle { flipped: true, ..SvgStyle::new(240) });
+        assert!(plain.contains(&format!(r#"cx="{:.3}" cy="{:.3}""#, x, y)));
+        assert!(flipped.contains(&format!(r#"cx="{:.3}" cy="{:.3}""#, -x, -y)));
+        assert_eq!(flipped.matches("<polygon").count(), 91);
+    }
+}
@@ -13,62 +13,404 @@ pub struct VariantConfig {
     pub starting_positions: HashMap<HexCoord, Piece>,
     pub pawn_movement: PawnMovement,
     pub special_rules: Vec<SpecialRule>,
+    #[serde(default)]
+    pub stalemate_rule: StalemateRule,
+    #[serde(default)]
+    pub draw_rules: DrawRules,
 }
 
 /// Pawn movement rules (varies by variant)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Pawns capture on the two cells either side of their forward step, the first
+/// step the rule gives; the rules differ in where a pawn may step without capturing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PawnMovement {
-    /// Standard pawn movement
+    /// Pawns step straight forward
+    #[default]
     Standard,
-    /// Pawns can move in 3 directions
+    /// Pawns step straight forward or onto either capture cell, when empty
     ThreeDirection,
-    /// Custom pawn movement
+    /// Pawns step in these directions, as seen by White; Black's are mirrored
     Custom(Vec<HexCoord>),
 }
 
 /// Special rules for variants
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SpecialRule {
-    /// En passant rule
+    /// Pawns may advance two cells from their starting cells, and can be taken en passant after doing so
     EnPassant,
-    /// Castling rule
+    /// A king and rook that have not moved may castle: the king steps two cells toward the rook,
+    /// which lands on the cell the king crossed
     Castling,
+    /// Captured pieces join the capturer's reserve and can be dropped on empty cells (crazyhouse)
+    Drops,
+    /// A capture explodes, clearing the capturer and every adjacent non-pawn (atomic chess)
+    AtomicCaptures,
+    /// Each side sees only the cells its pieces stand on or can move to; there is no check,
+    /// and taking the king wins (dark chess)
+    FogOfWar,
     /// Custom rule
     Custom(String),
 }
 
+/// How a stalemate is scored (varies by variant)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StalemateRule {
+    /// Stalemate is a draw (½–½)
+    #[default]
+    Draw,
+    /// The stalemating side scores ¾ and the stalemated side ¼ (Gliński)
+    PartialWin,
+    /// The stalemating side wins outright (McCooey)
+    Win,
+}
+
+impl StalemateRule {
+    /// Get the (white, black) score when `stalemated` has no legal moves
+    pub fn score(self, stalemated: Color) -> (f32, f32) {
+        let (winner, loser) = match self {
+            StalemateRule::Draw => (0.5, 0.5),
+            StalemateRule::PartialWin => (0.75, 0.25),
+            StalemateRule::Win => (1.0, 0.0),
+        };
+
+        match stalemated {
+            Color::White => (loser, winner),
+            Color::Black => (winner, loser),
+        }
+    }
+}
+
+/// Automatic draw conditions (tunable per variant or event)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrawRules {
+    /// Occurrences of the same position (same side to move) that draw the game, None to disable
+    pub repetition_count: Option<u32>,
+    /// Half-moves without a capture or pawn move that draw the game, None to disable
+    pub halfmove_limit: Option<u32>,
+    /// Which material counts as unable to mate
+    pub insufficient_material: InsufficientMaterial,
+}
+
+impl Default for DrawRules {
+    /// Threefold repetition, the fifty-move rule and a draw when only kings remain
+    fn default() -> Self {
+        Self {
+            repetition_count: Some(3),
+            halfmove_limit: Some(100),
+            insufficient_material: InsufficientMaterial::BareKings,
+        }
+    }
+}
+
+/// Insufficient material policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InsufficientMaterial {
+    /// Never draw for lack of material
+    Ignore,
+    /// Draw when only the two kings remain
+    #[default]
+    BareKings,
+    /// Draw when the kings are joined by at most one bishop or knight
+    SingleMinorPiece,
+}
+
+impl InsufficientMaterial {
+    /// Check whether the given pieces (kings included) are too few to mate
+    pub fn applies_to<'a>(self, pieces: impl IntoIterator<Item = &'a Piece>) -> bool {
+        let mut others = pieces
+            .into_iter()
+            .filter(|piece| piece.piece_type != PieceType::King);
+
+        match self {
+            InsufficientMaterial::Ignore => false,
+            InsufficientMaterial::BareKings => others.next().is_none(),
+            InsufficientMaterial::SingleMinorPiece => match others.next() {
+                None => true,
+                Some(piece) => {
+                    matches!(piece.piece_type, PieceType::Bishop | PieceType::Knight)
+                        && others.next().is_none()
+                }
+            },
+        }
+    }
+}
+
 impl VariantConfig {
+    /// Whether captured pieces can be dropped back in
+    pub fn allows_drops(&self) -> bool {
+        self.special_rules.iter().any(|rule| matches!(rule, SpecialRule::Drops))
+    }
+
+    /// Whether pawns double-step and can be taken en passant
+    pub fn has_en_passant(&self) -> bool {
+        self.special_rules.iter().any(|rule| matches!(rule, SpecialRule::EnPassant))
+    }
+
+    /// Whether kings may castle
+    pub fn has_castling(&self) -> bool {
+        self.special_rules.iter().any(|rule| matches!(rule, SpecialRule::Castling))
+    }
+
+    /// Whether captures explode
+    pub fn has_atomic_captures(&self) -> bool {
+        self.special_rules.iter().any(|rule| matches!(rule, SpecialRule::AtomicCaptures))
+    }
+
+    /// Whether each side plays with only part of the board in view
+    pub fn has_fog_of_war(&self) -> bool {
+        self.special_rules.iter().any(|rule| matches!(rule, SpecialRule::FogOfWar))
+    }
+
+    /// Piece types a pawn may promote to: any the variant starts with but kings and pawns, strongest first
+    pub fn promotion_choices(&self) -> Vec<PieceType> {
+        [
+            PieceType::Queen,
+            PieceType::Chancellor,
+            PieceType::Archbishop,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ]
+        .into_iter()
+        .filter(|&piece_type| self.starting_positions.values().any(|piece| piece.piece_type == piece_type))
+        .collect()
+    }
+
     /// Create a board with the starting positions for this variant
     pub fn create_board(&self) -> Board {
-        let mut board = Board::new(self.board_type);
+        let mut board = Board::new(self.board_type.clone());
+        board.pawn_movement = self.pawn_movement.clone();
         
         for (&coord, &piece) in &self.starting_positions {
             // Only place pieces on valid coordinates, skip invalid ones
             if let Err(e) = board.place_piece(coord, piece) {
                 eprintln!("Warning: Could not place piece at {:?}: {:?}", coord, e);
             }
+            if piece.piece_type == PieceType::Pawn && self.has_en_passant() {
+                board.pawn_homes.insert(coord, piece.color);
+            }
+            if matches!(piece.piece_type, PieceType::King | PieceType::Rook) && self.has_castling() {
+                board.castling_pieces.insert(coord);
+            }
         }
         
         board
     }
+
+    /// Check that the starting position is playable: every piece sits on the
+    /// board, each side has exactly one king, and both armies match
+    pub fn verify(&self) -> Result<(), VariantError> {
+        if self.starting_positions.is_empty() {
+            return Err(VariantError::NoPieces);
+        }
+
+        let board = Board::new(self.board_type.clone());
+        let mut coords: Vec<_> = self.starting_positions.keys().copied().collect();
+        coords.sort_by_key(|coord| (coord.q, coord.r));
+        if let Some(&coord) = coords.iter().find(|&&coord| !board.is_valid_coord(coord)) {
+            return Err(VariantError::PieceOffBoard(coord));
+        }
+
+        let mut white = HashMap::new();
+        let mut black = HashMap::new();
+        for piece in self.starting_positions.values() {
+            let army = match piece.color {
+                Color::White => &mut white,
+                Color::Black => &mut black,
+            };
+            *army.entry(piece.piece_type).or_insert(0) += 1;
+        }
+
+        for (color, army) in [(Color::White, &white), (Color::Black, &black)] {
+            let count = army.get(&PieceType::King).copied().unwrap_or(0);
+            if count != 1 {
+                return Err(VariantError::KingCount { color, count });
+            }
+        }
+
+        if white != black {
+            return Err(VariantError::UnbalancedArmies);
+        }
+
+        Ok(())
+    }
+}
+
+/// Problems found by `VariantConfig::verify`
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum VariantError {
+    #[error("Variant has no starting pieces")]
+    NoPieces,
+    #[error("Starting piece at {0:?} is off the board")]
+    PieceOffBoard(HexCoord),
+    #[error("{color:?} starts with {count} kings")]
+    KingCount { color: Color, count: usize },
+    #[error("White and Black start with different armies")]
+    UnbalancedArmies,
+    #[error("No cell is named {file}{rank} on this board")]
+    NoSuchSquare { file: char, rank: u8 },
+    #[error("A variant is already registered as {0}")]
+    AlreadyRegistered(String),
+}
+
+/// Where a `VariantBuilder` was asked to put a piece
+#[derive(Debug, Clone)]
+enum Placement {
+    Square(char, u8),
+    Cell(HexCoord),
+}
+
+/// Builds a variant in code, one setting at a time, e.g.
+///
+/// ```
+/// use hex_chess_core::{BoardType, Color, PieceType, VariantBuilder};
+///
+/// let variant = VariantBuilder::new("Kings and Rooks")
+///     .board(BoardType::Small { radius: 3 })
+///     .place('d', 1, PieceType::King, Color::White)
+///     .place('c', 2, PieceType::Rook, Color::White)
+///     .place('d', 7, PieceType::King, Color::Black)
+///     .place('e', 6, PieceType::Rook, Color::Black)
+///     .build()
+///     .unwrap();
+/// assert_eq!(variant.starting_positions.len(), 4);
+/// ```
+///
+/// Anything left unset is as in Gliński's Chess, without its special rules: a
+/// radius-5 board, pawns stepping straight ahead and the default draw rules.
+#[derive(Debug, Clone)]
+pub struct VariantBuilder {
+    name: String,
+    description: String,
+    board_type: BoardType,
+    placements: Vec<(Placement, Piece)>,
+    pawn_movement: PawnMovement,
+    special_rules: Vec<SpecialRule>,
+    stalemate_rule: StalemateRule,
+    draw_rules: DrawRules,
+}
+
+impl VariantBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            board_type: BoardType::Regular { radius: 5 },
+            placements: Vec::new(),
+            pawn_movement: PawnMovement::Standard,
+            special_rules: Vec::new(),
+            stalemate_rule: StalemateRule::default(),
+            draw_rules: DrawRules::default(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn board(mut self, board_type: BoardType) -> Self {
+        self.board_type = board_type;
+        self
+    }
+
+    /// Put a piece on a square named in the board's labelling, e.g. ('f', 1); squares
+    /// are looked up when the variant is built, so this may come before `board`
+    pub fn place(mut self, file: char, rank: u8, piece_type: PieceType, color: Color) -> Self {
+        self.placements.push((Placement::Square(file, rank), Piece::new(piece_type, color)));
+        self
+    }
+
+    /// Put a piece on a cell given by its axial coordinates
+    pub fn place_at(mut self, coord: HexCoord, piece_type: PieceType, color: Color) -> Self {
+        self.placements.push((Placement::Cell(coord), Piece::new(piece_type, color)));
+        self
+    }
+
+    pub fn pawn_movement(mut self, pawn_movement: PawnMovement) -> Self {
+        self.pawn_movement = pawn_movement;
+        self
+    }
+
+    /// Add a special rule, such as castling or drops
+    pub fn rule(mut self, rule: SpecialRule) -> Self {
+        self.special_rules.push(rule);
+        self
+    }
+
+    pub fn stalemate_rule(mut self, stalemate_rule: StalemateRule) -> Self {
+        self.stalemate_rule = stalemate_rule;
+        self
+    }
+
+    pub fn draw_rules(mut self, draw_rules: DrawRules) -> Self {
+        self.draw_rules = draw_rules;
+        self
+    }
+
+    /// Finish the variant, checking it with `VariantConfig::verify`; a later piece
+    /// placed on the same cell replaces an earlier one
+    pub fn build(self) -> Result<VariantConfig, VariantError> {
+        let mut starting_positions = HashMap::new();
+        for (placement, piece) in self.placements {
+            let coord = match placement {
+                Placement::Square(file, rank) => self
+                    .board_type
+                    .cell_at(file, rank)
+                    .ok_or(VariantError::NoSuchSquare { file, rank })?,
+                Placement::Cell(coord) => coord,
+            };
+            starting_positions.insert(coord, piece);
+        }
+
+        let config = VariantConfig {
+            name: self.name,
+            description: self.description,
+            board_type: self.board_type,
+            starting_positions,
+            pawn_movement: self.pawn_movement,
+            special_rules: self.special_rules,
+            stalemate_rule: self.stalemate_rule,
+            draw_rules: self.draw_rules,
+        };
+        config.verify()?;
+        Ok(config)
+    }
 }
 
 /// All available hexagonal chess variants
 pub struct Variants;
 
+/// Builds one of the variants below
+type VariantSetup = fn() -> VariantConfig;
+
+/// The built-in variants by short id, in menu order
+const BUILT_IN_VARIANTS: [(&str, VariantSetup); 10] = [
+    ("glinski", Variants::glinski_chess),
+    ("mccooey", Variants::mccooey_chess),
+    ("chevron", Variants::chevron_chess),
+    ("rhombus", Variants::rhombus_chess),
+    ("mini", Variants::mini_hexchess),
+    ("glinski_capablanca", Variants::glinski_capablanca_chess),
+    ("mccooey_capablanca", Variants::mccooey_capablanca_chess),
+    ("glinski_crazyhouse", Variants::glinski_crazyhouse),
+    ("glinski_atomic", Variants::glinski_atomic),
+    ("glinski_dark", Variants::glinski_dark),
+];
+
 impl Variants {
     /// Get all available variants
     pub fn all() -> Vec<VariantConfig> {
-        vec![
-            Self::glinski_chess(),
-            Self::mccooey_chess(),
-            Self::shafran_chess(),
-            Self::brusky_chess(),
-            Self::de_vasa_chess(),
-            Self::mini_hexchess(),
-            Self::glinski_capablanca_chess(),
-            Self::mccooey_capablanca_chess(),
-        ]
+        BUILT_IN_VARIANTS.iter().map(|(_, variant)| variant()).collect()
+    }
+
+    /// Look a variant up by its short id, e.g. "glinski" or "mini"
+    pub fn find(id: &str) -> Option<VariantConfig> {
+        BUILT_IN_VARIANTS
+            .iter()
+            .find(|(built_in, _)| *built_in == id)
+            .map(|(_, variant)| variant())
     }
 
     /// Gliński's Chess - 91 cells, regular hexagon
@@ -80,11 +422,11 @@ impl Variants {
         // Reference: https://greenchess.net/rules.php?v=glinski
         // Each side: 1K, 1Q, 2R, 2N, 3B, 9P = 18 pieces per side (36 total)
         
+        // Squares that fail to map are left out and reported by `VariantConfig::verify`
         let mut place_piece = |file: char, rank: u8, piece_type: PieceType, color: Color| {
-            let coord = HexCoord::from_file_rank(file, rank)
-                .unwrap_or_else(|| panic!("Invalid square for Gliński mapping: {}{}", file, rank));
-            let previous = starting_positions.insert(coord, Piece::new(piece_type, color));
-            assert!(previous.is_none(), "Duplicate placement at {:?}", coord);
+            if let Some(coord) = HexCoord::from_file_rank(file, rank) {
+                starting_positions.insert(coord, Piece::new(piece_type, color));
+            }
         };
 
         // White pieces (bottom)
@@ -145,14 +487,6 @@ impl Variants {
             place_piece(file, rank, PieceType::Pawn, Color::Black);
         }
 
-        debug_assert_eq!(starting_positions.len(), 36);
-        debug_assert!(
-            starting_positions
-                .keys()
-                .all(|coord| coord.in_hexagon(5)),
-            "All starting pieces must fit within radius 5"
-        );
-
         VariantConfig {
             name: "Gliński's Chess".to_string(),
             description: "91 cells, regular hexagon".to_string(),
@@ -160,138 +494,191 @@ impl Variants {
             starting_positions,
             pawn_movement: PawnMovement::Standard,
             special_rules: vec![SpecialRule::EnPassant],
+            stalemate_rule: StalemateRule::PartialWin,
+            draw_rules: DrawRules::default(),
         }
     }
 
-    /// McCooey's Chess - 81 cells, regular hexagon
+    /// McCooey's Chess - 91 cells, regular hexagon
     pub fn mccooey_chess() -> VariantConfig {
         let mut starting_positions = HashMap::new();
         
-        // Similar to Gliński but with 81 cells (radius 3)
-        // White pieces
-        for q in -3..=3 {
-            for r in 2..=3 {
-                if HexCoord::new(q, r).in_hexagon(3) {
-                    starting_positions.insert(HexCoord::new(q, r), Piece::new(PieceType::Pawn, Color::White));
-                }
-            }
-        }
-        
-        // White back rank
-        starting_positions.insert(HexCoord::new(0, 4), Piece::new(PieceType::King, Color::White));
-        starting_positions.insert(HexCoord::new(1, 4), Piece::new(PieceType::Queen, Color::White));
-        starting_positions.insert(HexCoord::new(-1, 4), Piece::new(PieceType::Bishop, Color::White));
-        starting_positions.insert(HexCoord::new(2, 4), Piece::new(PieceType::Bishop, Color::White));
-        starting_positions.insert(HexCoord::new(-2, 4), Piece::new(PieceType::Knight, Color::White));
-        starting_positions.insert(HexCoord::new(3, 4), Piece::new(PieceType::Knight, Color::White));
-        starting_positions.insert(HexCoord::new(-3, 4), Piece::new(PieceType::Rook, Color::White));
+        // Same board as Gliński's Chess with a more compact army:
+        // 1K, 1Q, 2R, 2N, 3B, 7P = 16 pieces per side (32 total).
+        // Black's army is White's reflected across the centre rank.
+        let white = [
+            (HexCoord::new(1, -5), PieceType::King),
+            (HexCoord::new(-1, -4), PieceType::Queen),
+            (HexCoord::new(-2, -3), PieceType::Rook),
+            (HexCoord::new(2, -5), PieceType::Rook),
+            (HexCoord::new(-1, -3), PieceType::Knight),
+            (HexCoord::new(1, -4), PieceType::Knight),
+            (HexCoord::new(0, -5), PieceType::Bishop),
+            (HexCoord::new(0, -4), PieceType::Bishop),
+            (HexCoord::new(0, -3), PieceType::Bishop),
+            (HexCoord::new(-3, -2), PieceType::Pawn),
+            (HexCoord::new(-2, -2), PieceType::Pawn),
+            (HexCoord::new(-1, -2), PieceType::Pawn),
+            (HexCoord::new(0, -2), PieceType::Pawn),
+            (HexCoord::new(1, -3), PieceType::Pawn),
+            (HexCoord::new(2, -4), PieceType::Pawn),
+            (HexCoord::new(3, -5), PieceType::Pawn),
+        ];
         
-        // Black pieces
-        for q in -3..=3 {
-            for r in -3..=-2 {
-                if HexCoord::new(q, r).in_hexagon(3) {
-                    starting_positions.insert(HexCoord::new(q, r), Piece::new(PieceType::Pawn, Color::Black));
-                }
-            }
+        for (coord, piece_type) in white {
+            starting_positions.insert(coord, Piece::new(piece_type, Color::White));
+            let mirrored = HexCoord::new(coord.q, -coord.r - coord.q);
+            starting_positions.insert(mirrored, Piece::new(piece_type, Color::Black));
         }
         
-        // Black back rank
-        starting_positions.insert(HexCoord::new(0, -4), Piece::new(PieceType::King, Color::Black));
-        starting_positions.insert(HexCoord::new(-1, -4), Piece::new(PieceType::Queen, Color::Black));
-        starting_positions.insert(HexCoord::new(1, -4), Piece::new(PieceType::Bishop, Color::Black));
-        starting_positions.insert(HexCoord::new(-2, -4), Piece::new(PieceType::Bishop, Color::Black));
-        starting_positions.insert(HexCoord::new(2, -4), Piece::new(PieceType::Knight, Color::Black));
-        starting_positions.insert(HexCoord::new(-3, -4), Piece::new(PieceType::Knight, Color::Black));
-        starting_positions.insert(HexCoord::new(3, -4), Piece::new(PieceType::Rook, Color::Black));
-        
         VariantConfig {
             name: "McCooey's Chess".to_string(),
-            description: "81 cells, regular hexagon".to_string(),
-            board_type: BoardType::Regular { radius: 3 },
+            description: "91 cells, regular hexagon".to_string(),
+            board_type: BoardType::Regular { radius: 5 },
             starting_positions,
             pawn_movement: PawnMovement::Standard,
             special_rules: vec![SpecialRule::EnPassant],
+            stalemate_rule: StalemateRule::Win,
+            draw_rules: DrawRules::default(),
         }
     }
 
-    /// Shafran's Chess - irregular board
-    pub fn shafran_chess() -> VariantConfig {
-        // Simplified irregular board for now
-        VariantConfig {
-            name: "Shafran's Chess".to_string(),
-            description: "Irregular board layout".to_string(),
-            board_type: BoardType::Irregular,
-            starting_positions: HashMap::new(), // TODO: Define irregular layout
-            pawn_movement: PawnMovement::Standard,
-            special_rules: vec![],
+    /// Chevron Chess - Gliński's armies and rules on Shafran's 70-cell board
+    ///
+    /// Only the board is Shafran's: his own setup and pawn rules aren't implemented,
+    /// so the game goes by its own name.
+    pub fn chevron_chess() -> VariantConfig {
+        let mut starting_positions = HashMap::new();
+        
+        // Nine files a-i, e down the middle with ranks 1-10 (see `BoardType::Shafran`).
+        // Each side: 1K, 1Q, 2R, 2N, 3B, 9P = 18 pieces per side (36 total), the
+        // bishops stacked on the middle file and the pawns in a chevron covering
+        // every line out of the camp, so neither side starts with a capture.
+        // Black's army is White's reflected across the middle of the board.
+        let board_type = BoardType::Shafran;
+        let white = [
+            ('c', 3, PieceType::Rook),
+            ('d', 2, PieceType::Knight),
+            ('d', 3, PieceType::Queen),
+            ('e', 1, PieceType::Bishop),
+            ('e', 2, PieceType::Bishop),
+            ('e', 3, PieceType::Bishop),
+            ('f', 1, PieceType::King),
+            ('f', 2, PieceType::Knight),
+            ('g', 1, PieceType::Rook),
+            ('a', 5, PieceType::Pawn),
+            ('b', 4, PieceType::Pawn),
+            ('c', 4, PieceType::Pawn),
+            ('d', 4, PieceType::Pawn),
+            ('e', 4, PieceType::Pawn),
+            ('f', 3, PieceType::Pawn),
+            ('g', 2, PieceType::Pawn),
+            ('h', 1, PieceType::Pawn),
+            ('i', 1, PieceType::Pawn),
+        ];
+        
+        // Squares that fail to map are left out and reported by `VariantConfig::verify`
+        for (file, rank, piece_type) in white {
+            if let Some(coord) = board_type.cell_at(file, rank) {
+                starting_positions.insert(coord, Piece::new(piece_type, Color::White));
+                let mirrored = HexCoord::new(coord.q, -coord.r - coord.q - 1);
+                starting_positions.insert(mirrored, Piece::new(piece_type, Color::Black));
+            }
         }
-    }
-
-    /// Brusky's Chess - irregular board
-    pub fn brusky_chess() -> VariantConfig {
+        
         VariantConfig {
-            name: "Brusky's Chess".to_string(),
-            description: "Irregular board layout".to_string(),
-            board_type: BoardType::Irregular,
-            starting_positions: HashMap::new(), // TODO: Define irregular layout
+            name: "Chevron Chess".to_string(),
+            description: "Shafran's 70-cell board, pawns in a chevron".to_string(),
+            board_type,
+            starting_positions,
             pawn_movement: PawnMovement::Standard,
-            special_rules: vec![],
+            special_rules: vec![SpecialRule::EnPassant],
+            stalemate_rule: StalemateRule::Draw,
+            draw_rules: DrawRules::default(),
         }
     }
 
-    /// De Vasa's Chess - irregular board
-    pub fn de_vasa_chess() -> VariantConfig {
+    /// Rhombus Chess - Gliński's armies, with castling, on De Vasa's 81-cell rhombus
+    ///
+    /// Only the board is De Vasa's: his own setup isn't implemented, so the game
+    /// goes by its own name.
+    pub fn rhombus_chess() -> VariantConfig {
+        let mut starting_positions = HashMap::new();
+        
+        // Nine files a-i of nine ranks (see `BoardType::DeVasa`), pieces on rank 1
+        // and pawns on rank 2. Each side: 1K, 1Q, 2R, 2N, 3B, 9P = 18 pieces per
+        // side (36 total). The long diagonals cross the board two ranks a step, so
+        // the queen and bishops start on files a-d, where theirs run off the board
+        // rather than into the enemy camp. Black's army is White's turned half a
+        // circle, so the armies face each other across the centre.
+        let board_type = BoardType::DeVasa;
+        let white = [
+            ('a', 1, PieceType::Bishop),
+            ('b', 1, PieceType::Bishop),
+            ('c', 1, PieceType::Bishop),
+            ('d', 1, PieceType::Queen),
+            ('e', 1, PieceType::King),
+            ('f', 1, PieceType::Knight),
+            ('g', 1, PieceType::Knight),
+            ('h', 1, PieceType::Rook),
+            ('i', 1, PieceType::Rook),
+        ];
+        
+        // Squares that fail to map are left out and reported by `VariantConfig::verify`
+        let pawns = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i'].map(|file| (file, 2, PieceType::Pawn));
+        for (file, rank, piece_type) in white.into_iter().chain(pawns) {
+            if let Some(coord) = board_type.cell_at(file, rank) {
+                starting_positions.insert(coord, Piece::new(piece_type, Color::White));
+                let turned = HexCoord::new(-coord.q, -coord.r);
+                starting_positions.insert(turned, Piece::new(piece_type, Color::Black));
+            }
+        }
+        
         VariantConfig {
-            name: "De Vasa's Chess".to_string(),
-            description: "Irregular board layout".to_string(),
-            board_type: BoardType::Irregular,
-            starting_positions: HashMap::new(), // TODO: Define irregular layout
+            name: "Rhombus Chess".to_string(),
+            description: "De Vasa's 81-cell rhombus, with castling".to_string(),
+            board_type,
+            starting_positions,
             pawn_movement: PawnMovement::Standard,
-            special_rules: vec![],
+            special_rules: vec![SpecialRule::EnPassant, SpecialRule::Castling],
+            stalemate_rule: StalemateRule::Draw,
+            draw_rules: DrawRules::default(),
         }
     }
 
-    /// Mini Hexchess - 37 cells
+    /// Mini Hexchess - 37 cells, small hexagon
     pub fn mini_hexchess() -> VariantConfig {
         let mut starting_positions = HashMap::new();
         
-        // White pieces
-        for q in -2..=2 {
-            for r in 1..=2 {
-                if HexCoord::new(q, r).in_hexagon(2) {
-                    starting_positions.insert(HexCoord::new(q, r), Piece::new(PieceType::Pawn, Color::White));
-                }
-            }
-        }
-        
-        starting_positions.insert(HexCoord::new(0, 3), Piece::new(PieceType::King, Color::White));
-        starting_positions.insert(HexCoord::new(1, 3), Piece::new(PieceType::Queen, Color::White));
-        starting_positions.insert(HexCoord::new(-1, 3), Piece::new(PieceType::Bishop, Color::White));
-        starting_positions.insert(HexCoord::new(2, 3), Piece::new(PieceType::Knight, Color::White));
-        starting_positions.insert(HexCoord::new(-2, 3), Piece::new(PieceType::Rook, Color::White));
+        // McCooey's Mini Hexchess: each side has K, R, B, N and 5 pawns on a
+        // radius-3 board. Black's army is White's reflected across the centre rank.
+        let white = [
+            (HexCoord::new(1, -3), PieceType::King),
+            (HexCoord::new(0, -3), PieceType::Bishop),
+            (HexCoord::new(0, -2), PieceType::Knight),
+            (HexCoord::new(-1, -2), PieceType::Rook),
+            (HexCoord::new(-2, -1), PieceType::Pawn),
+            (HexCoord::new(-1, -1), PieceType::Pawn),
+            (HexCoord::new(0, -1), PieceType::Pawn),
+            (HexCoord::new(1, -2), PieceType::Pawn),
+            (HexCoord::new(2, -3), PieceType::Pawn),
+        ];
         
-        // Black pieces
-        for q in -2..=2 {
-            for r in -2..=-1 {
-                if HexCoord::new(q, r).in_hexagon(2) {
-                    starting_positions.insert(HexCoord::new(q, r), Piece::new(PieceType::Pawn, Color::Black));
-                }
-            }
+        for (coord, piece_type) in white {
+            starting_positions.insert(coord, Piece::new(piece_type, Color::White));
+            let mirrored = HexCoord::new(coord.q, -coord.r - coord.q);
+            starting_positions.insert(mirrored, Piece::new(piece_type, Color::Black));
         }
         
-        starting_positions.insert(HexCoord::new(0, -3), Piece::new(PieceType::King, Color::Black));
-        starting_positions.insert(HexCoord::new(-1, -3), Piece::new(PieceType::Queen, Color::Black));
-        starting_positions.insert(HexCoord::new(1, -3), Piece::new(PieceType::Bishop, Color::Black));
-        starting_positions.insert(HexCoord::new(-2, -3), Piece::new(PieceType::Knight, Color::Black));
-        starting_positions.insert(HexCoord::new(2, -3), Piece::new(PieceType::Rook, Color::Black));
-        
         VariantConfig {
             name: "Mini Hexchess".to_string(),
             description: "37 cells, small hexagon".to_string(),
-            board_type: BoardType::Small,
+            board_type: BoardType::Small { radius: 3 },
             starting_positions,
             pawn_movement: PawnMovement::Standard,
             special_rules: vec![],
+            stalemate_rule: StalemateRule::Draw,
+            draw_rules: DrawRules::default(),
         }
     }
 
@@ -301,11 +688,12 @@ impl Variants {
         config.name = "Gliński-Capablanca Chess".to_string();
         config.description = "91 cells with fairy pieces".to_string();
         
-        // Replace some pieces with fairy pieces
-        config.starting_positions.insert(HexCoord::new(2, 5), Piece::new(PieceType::Chancellor, Color::White));
-        config.starting_positions.insert(HexCoord::new(-2, 5), Piece::new(PieceType::Archbishop, Color::White));
-        config.starting_positions.insert(HexCoord::new(-2, -5), Piece::new(PieceType::Chancellor, Color::Black));
-        config.starting_positions.insert(HexCoord::new(2, -5), Piece::new(PieceType::Archbishop, Color::Black));
+        // The fairy pieces fill the two empty back cells either side of the bishops,
+        // e3 and g2, and Black's mirror them across the centre rank
+        config.starting_positions.insert(HexCoord::new(-1, -3), Piece::new(PieceType::Chancellor, Color::White));
+        config.starting_positions.insert(HexCoord::new(1, -4), Piece::new(PieceType::Archbishop, Color::White));
+        config.starting_positions.insert(HexCoord::new(-1, 4), Piece::new(PieceType::Chancellor, Color::Black));
+        config.starting_positions.insert(HexCoord::new(1, 3), Piece::new(PieceType::Archbishop, Color::Black));
         
         config
     }
@@ -314,16 +702,101 @@ impl Variants {
     pub fn mccooey_capablanca_chess() -> VariantConfig {
         let mut config = Self::mccooey_chess();
         config.name = "McCooey-Capablanca Chess".to_string();
-        config.description = "81 cells with fairy pieces".to_string();
-        
-        // Replace some pieces with fairy pieces
-        config.starting_positions.insert(HexCoord::new(2, 4), Piece::new(PieceType::Chancellor, Color::White));
-        config.starting_positions.insert(HexCoord::new(-2, 4), Piece::new(PieceType::Archbishop, Color::White));
-        config.starting_positions.insert(HexCoord::new(-2, -4), Piece::new(PieceType::Chancellor, Color::Black));
-        config.starting_positions.insert(HexCoord::new(2, -4), Piece::new(PieceType::Archbishop, Color::Black));
+        config.description = "91 cells with fairy pieces".to_string();
         
+        // Replace the knights with fairy pieces
+        config.starting_positions.insert(HexCoord::new(-1, -3), Piece::new(PieceType::Chancellor, Color::White));
+        config.starting_positions.insert(HexCoord::new(1, -4), Piece::new(PieceType::Archbishop, Color::White));
+        config.starting_positions.insert(HexCoord::new(-1, 4), Piece::new(PieceType::Chancellor, Color::Black));
+        config.starting_positions.insert(HexCoord::new(1, 3), Piece::new(PieceType::Archbishop, Color::Black));
+
+        config
+    }
+
+    /// Gliński's Crazyhouse - captured pieces change sides and can be dropped back in
+    pub fn glinski_crazyhouse() -> VariantConfig {
+        let mut config = Self::glinski_chess();
+        config.name = "Gliński's Crazyhouse".to_string();
+        config.description = "91 cells; captures go to your reserve for dropping".to_string();
+        config.special_rules.push(SpecialRule::Drops);
         config
     }
+
+    /// Atomic Gliński (experimental) - captures explode, taking out neighbouring pieces
+    pub fn glinski_atomic() -> VariantConfig {
+        let mut config = Self::glinski_chess();
+        config.name = "Atomic Gliński".to_string();
+        config.description = "91 cells; captures clear the capturer and every adjacent non-pawn".to_string();
+        config.special_rules.push(SpecialRule::AtomicCaptures);
+        config
+    }
+
+    /// Dark Gliński - fog of war, where each side sees only what its pieces reach
+    pub fn glinski_dark() -> VariantConfig {
+        let mut config = Self::glinski_chess();
+        config.name = "Dark Gliński".to_string();
+        config.description = "91 cells; you see only the cells your pieces reach, and win by taking the king".to_string();
+        config.special_rules.push(SpecialRule::FogOfWar);
+        config
+    }
+}
+
+/// Variants a host knows by id: the built-in ones plus any it registers, such as
+/// those made with `VariantBuilder`
+#[derive(Debug, Clone)]
+pub struct VariantRegistry {
+    variants: Vec<(String, VariantConfig)>, // in registration order
+}
+
+impl VariantRegistry {
+    /// A registry holding the built-in variants
+    pub fn new() -> Self {
+        Self {
+            variants: BUILT_IN_VARIANTS
+                .iter()
+                .map(|(id, variant)| (id.to_string(), variant()))
+                .collect(),
+        }
+    }
+
+    /// Add a variant under a new id; it must pass `VariantConfig::verify`, and
+    /// neither its id nor its name may be taken
+    pub fn register(&mut self, id: impl Into<String>, config: VariantConfig) -> Result<(), VariantError> {
+        let id = id.into();
+        if self.get(&id).is_some() {
+            return Err(VariantError::AlreadyRegistered(id));
+        }
+        if self.find_by_name(&config.name).is_some() {
+            return Err(VariantError::AlreadyRegistered(config.name));
+        }
+        config.verify()?;
+        self.variants.push((id, config));
+        Ok(())
+    }
+
+    /// The variant with this id
+    pub fn get(&self, id: &str) -> Option<&VariantConfig> {
+        self.variants.iter().find(|(known, _)| known == id).map(|(_, config)| config)
+    }
+
+    /// The id and variant going by this name, as in `VariantConfig::name`
+    pub fn find_by_name(&self, name: &str) -> Option<(&str, &VariantConfig)> {
+        self.variants
+            .iter()
+            .find(|(_, config)| config.name == name)
+            .map(|(id, config)| (id.as_str(), config))
+    }
+
+    /// Every variant with its id, built-in ones first
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &VariantConfig)> {
+        self.variants.iter().map(|(id, config)| (id.as_str(), config))
+    }
+}
+
+impl Default for VariantRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -334,19 +807,256 @@ mod tests {
     fn test_variant_creation() {
         let glinski = Variants::glinski_chess();
         assert_eq!(glinski.name, "Gliński's Chess");
-        assert!(glinski.starting_positions.len() > 0);
+        assert!(!glinski.starting_positions.is_empty());
+    }
+
+    #[test]
+    fn test_glinski_verifies() {
+        let glinski = Variants::glinski_chess();
+        assert_eq!(glinski.verify(), Ok(()));
+        assert_eq!(glinski.starting_positions.len(), 36);
+        assert!(glinski.starting_positions.keys().all(|coord| coord.in_hexagon(5)));
+    }
+
+    #[test]
+    fn test_mccooey_setup() {
+        let mccooey = Variants::mccooey_chess();
+        assert_eq!(mccooey.verify(), Ok(()));
+        assert_eq!(mccooey.board_type.valid_coords().len(), 91);
+        assert_eq!(mccooey.starting_positions.len(), 32);
+
+        let board = mccooey.create_board();
+        assert_eq!(board.pieces.len(), 32);
+        assert_eq!(board.get_king(Color::White), Some(HexCoord::new(1, -5)));
+        assert_eq!(board.get_king(Color::Black), Some(HexCoord::new(1, 4)));
+        // The three bishops share the centre file, one on each cell colour
+        let bishops: Vec<_> = board
+            .get_pieces_by_color(Color::White)
+            .into_iter()
+            .filter(|(_, piece)| piece.piece_type == PieceType::Bishop)
+            .map(|(coord, _)| board.cell_colors[&coord])
+            .collect();
+        assert_eq!(bishops.len(), 3);
+        assert!(bishops[0] != bishops[1] && bishops[1] != bishops[2] && bishops[0] != bishops[2]);
+
+        assert_eq!(Variants::mccooey_capablanca_chess().verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_mini_hexchess_setup() {
+        let mini = Variants::mini_hexchess();
+        assert_eq!(mini.verify(), Ok(()));
+        assert_eq!(mini.board_type.valid_coords().len(), 37);
+        assert_eq!(mini.starting_positions.len(), 18);
+
+        let board = mini.create_board();
+        assert_eq!(board.pieces.len(), 18);
+        assert_eq!(board.get_king(Color::White), Some(HexCoord::new(1, -3)));
+        assert_eq!(board.get_king(Color::Black), Some(HexCoord::new(1, 2)));
+        // Each pawn has an empty cell ahead of it
+        for (coord, piece) in board.get_pieces_by_color(Color::White) {
+            if piece.piece_type == PieceType::Pawn {
+                assert!(!board.is_occupied(coord + HexCoord::new(0, 1)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_chevron_setup() {
+        let chevron = Variants::chevron_chess();
+        assert_eq!(chevron.verify(), Ok(()));
+        assert_eq!(chevron.board_type.valid_coords().len(), 70);
+        assert_eq!(chevron.starting_positions.len(), 36);
+
+        let board = chevron.create_board();
+        assert_eq!(board.get_king(Color::White), BoardType::Shafran.cell_at('f', 1));
+        assert_eq!(board.get_king(Color::Black), BoardType::Shafran.cell_at('f', 9));
+        // One pawn on each file
+        for color in [Color::White, Color::Black] {
+            let mut files: Vec<_> = board
+                .get_pieces_by_color(color)
+                .into_iter()
+                .filter(|(_, piece)| piece.piece_type == PieceType::Pawn)
+                .map(|(coord, _)| coord.q)
+                .collect();
+            files.sort();
+            assert_eq!(files, (-4..=4).collect::<Vec<_>>());
+        }
+        // The three bishops stand on three different cell colours
+        let bishops: std::collections::HashSet<_> = board
+            .get_pieces_by_color(Color::White)
+            .into_iter()
+            .filter(|(_, piece)| piece.piece_type == PieceType::Bishop)
+            .map(|(coord, _)| board.cell_colors[&coord])
+            .collect();
+        assert_eq!(bishops.len(), 3);
+    }
+
+    #[test]
+    fn test_rhombus_setup() {
+        let rhombus = Variants::find("rhombus").unwrap();
+        assert_eq!(rhombus.verify(), Ok(()));
+        assert_eq!(rhombus.starting_positions.len(), 36);
+
+        let board = rhombus.create_board();
+        let square = |file, rank| BoardType::DeVasa.cell_at(file, rank).unwrap();
+        assert_eq!(board.get_king(Color::White), Some(square('e', 1)));
+        assert_eq!(board.get_king(Color::Black), Some(square('e', 9)));
+        // Both ranks of pawns are full
+        for (rank, color) in [(2, Color::White), (8, Color::Black)] {
+            for file in ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i'] {
+                assert_eq!(board.get_piece(square(file, rank)), Some(&Piece::new(PieceType::Pawn, color)));
+            }
+        }
+        // The three bishops stand on three different cell colours
+        let bishops: std::collections::HashSet<_> = board
+            .get_pieces_by_color(Color::White)
+            .into_iter()
+            .filter(|(_, piece)| piece.piece_type == PieceType::Bishop)
+            .map(|(coord, _)| board.cell_colors[&coord])
+            .collect();
+        assert_eq!(bishops.len(), 3);
+        // Kings and rooks start able to castle
+        assert_eq!(board.castling_pieces.len(), 6);
+    }
+
+    #[test]
+    fn test_verify_reports_broken_setups() {
+        let mut config = Variants::glinski_chess();
+        config.starting_positions.insert(HexCoord::new(4, 4), Piece::new(PieceType::Pawn, Color::White));
+        assert_eq!(config.verify(), Err(VariantError::PieceOffBoard(HexCoord::new(4, 4))));
+
+        let mut config = Variants::glinski_chess();
+        config.starting_positions.insert(HexCoord::new(0, 0), Piece::new(PieceType::King, Color::Black));
+        assert_eq!(config.verify(), Err(VariantError::KingCount { color: Color::Black, count: 2 }));
+
+        let mut config = Variants::glinski_chess();
+        config.starting_positions.insert(HexCoord::new(0, 0), Piece::new(PieceType::Knight, Color::White));
+        assert_eq!(config.verify(), Err(VariantError::UnbalancedArmies));
+
+        config.starting_positions.clear();
+        assert_eq!(config.verify(), Err(VariantError::NoPieces));
+    }
+
+    #[test]
+    fn test_variant_builder() {
+        let mini = Variants::mini_hexchess();
+        let mut builder = VariantBuilder::new("Mini Hexchess")
+            .description("37 cells, small hexagon")
+            .board(BoardType::Small { radius: 3 })
+            .stalemate_rule(StalemateRule::Draw);
+        for (&coord, piece) in &mini.starting_positions {
+            builder = builder.place_at(coord, piece.piece_type, piece.color);
+        }
+        let built = builder.build().unwrap();
+        assert_eq!(built.starting_positions, mini.starting_positions);
+        assert_eq!(built.board_type, mini.board_type);
+        assert_eq!(built.stalemate_rule, mini.stalemate_rule);
+
+        // Squares are named on the board given, even when it's given last
+        let kings = VariantBuilder::new("Kings")
+            .place('f', 1, PieceType::King, Color::White)
+            .place('f', 11, PieceType::King, Color::Black)
+            .rule(SpecialRule::Castling)
+            .board(BoardType::Regular { radius: 5 })
+            .build()
+            .unwrap();
+        assert_eq!(kings.starting_positions.get(&HexCoord::new(0, -5)), Some(&Piece::new(PieceType::King, Color::White)));
+        assert!(kings.has_castling());
+
+        let off_board = VariantBuilder::new("Off the board")
+            .board(BoardType::Small { radius: 3 })
+            .place('k', 1, PieceType::King, Color::White)
+            .build();
+        assert_eq!(off_board.unwrap_err(), VariantError::NoSuchSquare { file: 'k', rank: 1 });
+
+        let kingless = VariantBuilder::new("Kingless").place('f', 1, PieceType::Queen, Color::White).build();
+        assert_eq!(kingless.unwrap_err(), VariantError::KingCount { color: Color::White, count: 0 });
+    }
+
+    #[test]
+    fn test_variant_registry() {
+        let mut registry = VariantRegistry::new();
+        assert_eq!(registry.iter().count(), Variants::all().len());
+        assert_eq!(registry.get("mini").map(|config| config.name.as_str()), Some("Mini Hexchess"));
+        assert_eq!(registry.find_by_name("Gliński's Chess").map(|(id, _)| id), Some("glinski"));
+
+        let mut kings = Variants::mini_hexchess();
+        kings.name = "Mini Kings".to_string();
+        kings.starting_positions.retain(|_, piece| piece.piece_type == PieceType::King);
+        registry.register("mini_kings", kings.clone()).unwrap();
+        assert_eq!(registry.find_by_name("Mini Kings").map(|(id, _)| id), Some("mini_kings"));
+        assert_eq!(registry.iter().last().map(|(id, _)| id), Some("mini_kings"));
+
+        let taken = |id: &str| Err(VariantError::AlreadyRegistered(id.to_string()));
+        assert_eq!(registry.register("mini_kings", Variants::glinski_chess()), taken("mini_kings"));
+        assert_eq!(registry.register("glinski_again", Variants::glinski_chess()), taken("Gliński's Chess"));
+        kings.name = "Kingless".to_string();
+        kings.starting_positions.clear();
+        assert_eq!(registry.register("kingless", kings), Err(VariantError::NoPieces));
     }
 
     #[test]
     fn test_board_creation_from_variant() {
         let mini = Variants::mini_hexchess();
         let board = mini.create_board();
-        assert!(board.pieces.len() > 0);
+        assert!(!board.pieces.is_empty());
+    }
+
+    #[test]
+    fn test_stalemate_rules_per_variant() {
+        assert_eq!(Variants::glinski_chess().stalemate_rule, StalemateRule::PartialWin);
+        assert_eq!(Variants::mccooey_chess().stalemate_rule, StalemateRule::Win);
+        assert_eq!(Variants::mini_hexchess().stalemate_rule, StalemateRule::Draw);
+
+        assert_eq!(StalemateRule::PartialWin.score(Color::Black), (0.75, 0.25));
+        assert_eq!(StalemateRule::Win.score(Color::White), (0.0, 1.0));
+        assert_eq!(StalemateRule::Draw.score(Color::White), (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_insufficient_material_policies() {
+        let kings = [
+            Piece::new(PieceType::King, Color::White),
+            Piece::new(PieceType::King, Color::Black),
+        ];
+        let with_knight = [kings[0], kings[1], Piece::new(PieceType::Knight, Color::White)];
+        let with_rook = [kings[0], kings[1], Piece::new(PieceType::Rook, Color::Black)];
+
+        assert!(InsufficientMaterial::BareKings.applies_to(&kings));
+        assert!(!InsufficientMaterial::BareKings.applies_to(&with_knight));
+        assert!(InsufficientMaterial::SingleMinorPiece.applies_to(&with_knight));
+        assert!(!InsufficientMaterial::SingleMinorPiece.applies_to(&with_rook));
+        assert!(!InsufficientMaterial::Ignore.applies_to(&kings));
     }
 
     #[test]
     fn test_all_variants() {
         let variants = Variants::all();
-        assert_eq!(variants.len(), 8);
+        assert_eq!(variants.len(), 10);
+        for variant in &variants {
+            assert_eq!(variant.verify(), Ok(()), "{}", variant.name);
+        }
+        for (id, variant) in VariantRegistry::new().iter() {
+            assert_eq!(variant.verify(), Ok(()), "{}", id);
+        }
+    }
+
+    #[test]
+    fn test_glinski_capablanca_setup() {
+        let config = Variants::glinski_capablanca_chess();
+        assert_eq!(config.starting_positions.len(), 40);
+        let board = config.create_board();
+        assert_eq!(board.pieces.len(), 40);
+        for (q, r, piece_type, color) in [
+            (-1, -3, PieceType::Chancellor, Color::White),
+            (1, -4, PieceType::Archbishop, Color::White),
+            (-1, 4, PieceType::Chancellor, Color::Black),
+            (1, 3, PieceType::Archbishop, Color::Black),
+        ] {
+            assert_eq!(board.get_piece(HexCoord::new(q, r)), Some(&Piece::new(piece_type, color)));
+        }
+        assert_eq!(HexCoord::new(-1, -3).to_file_rank().as_deref(), Some("e3"));
+        assert_eq!(HexCoord::new(1, -4).to_file_rank().as_deref(), Some("g2"));
     }
 }
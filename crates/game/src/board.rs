@@ -0,0 +1,749 @@
+use bevy::prelude::*;
+use bevy::input::mouse::MouseWheel;
+use bevy::sprite::{MaterialMesh2dBundle, ColorMaterial};
+use bevy::render::camera::Viewport;
+use bevy::render::view::RenderLayers;
+use bevy::window::{WindowResized, WindowScaleFactorChanged};
+use hex_chess_core::{piece_letter, HexCoord, Piece, PieceType, Color as ChessColor, CellColor};
+
+use crate::{BoardTheme, GameData, GameState, ValidMoveColor};
+use crate::board3d::render_2d;
+use crate::platform::announce_ready;
+use crate::settings::{Action, GameConfig};
+
+/// The board, its pieces and the cameras looking at them
+pub struct BoardRenderPlugin;
+
+impl Plugin for BoardRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<BoardLayout>()
+            .add_systems(Startup, setup)
+            .add_systems(Update, (
+                handle_camera_zoom.run_if(render_2d),
+                handle_camera_pan.run_if(render_2d),
+                update_minimap,
+                handle_minimap_click,
+                update_board_visuals,
+                update_selection_visuals, // Show selected piece and valid moves
+            ).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (
+                fit_camera_to_window,
+                apply_display_scale,
+            ));
+    }
+}
+
+#[derive(Component)]
+pub struct HexTile {
+    pub coord: HexCoord,
+    pub base_color: Color, // Store original color for 2D
+}
+
+#[derive(Component)]
+pub struct ChessPiece {
+    pub coord: HexCoord,
+    pub piece: Piece,
+}
+
+/// The letter drawn on a piece
+#[derive(Component)]
+pub struct PieceLabel;
+
+/// Where the board sits in the world; every board <-> world conversion goes through here
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct BoardLayout {
+    pub scale: f32,     // world units per hex unit
+    pub origin: Vec2,   // world position of the centre cell
+    pub rotation: f32,  // radians, counter-clockwise
+    pub flipped: bool,  // turned half a turn, so Black's side is nearest
+}
+
+impl Default for BoardLayout {
+    fn default() -> Self {
+        Self {
+            scale: 100.0,
+            origin: Vec2::ZERO,
+            rotation: 0.0,
+            flipped: false,
+        }
+    }
+}
+
+impl BoardLayout {
+    fn turn(&self) -> Vec2 {
+        let flip = if self.flipped { std::f32::consts::PI } else { 0.0 };
+        Vec2::from_angle(self.rotation + flip)
+    }
+    
+    /// World position of a point given in hex units, as `HexCoord::to_pixel` returns them
+    pub fn hex_to_world(&self, point: Vec2) -> Vec2 {
+        self.origin + self.turn().rotate(point * self.scale)
+    }
+    
+    /// World position of a cell's centre
+    pub fn board_to_world(&self, coord: HexCoord) -> Vec2 {
+        let (x, y) = coord.to_pixel();
+        self.hex_to_world(Vec2::new(x, y))
+    }
+    
+    /// The cell containing a world position; it may lie off the board
+    pub fn world_to_board(&self, world: Vec2) -> HexCoord {
+        let turn = self.turn();
+        let point = Vec2::new(turn.x, -turn.y).rotate(world - self.origin) / self.scale;
+        
+        // Undo HexCoord::to_pixel, then round to the nearest cell in cube space
+        let q = point.x / 0.75;
+        let r = (-point.y - 3.0_f32.sqrt() / 4.0 * q) / (3.0_f32.sqrt() / 2.0);
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        HexCoord::new(rq as i32, rr as i32)
+    }
+    
+    /// Radius of a drawn tile, leaving a gap between neighbours
+    pub fn tile_radius(&self) -> f32 {
+        self.scale * 0.45
+    }
+}
+
+/// Meshes, materials and layout needed to spawn pieces from inside a system
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct PieceAssets<'w> {
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<ColorMaterial>>,
+    pub layout: Res<'w, BoardLayout>,
+}
+
+#[derive(Component)]
+pub struct MoveIndicator;
+
+#[derive(Component)]
+pub struct CoordinateLabel;
+
+/// Camera drawing the whole-board overview inset
+#[derive(Component)]
+pub struct MinimapCamera;
+
+/// Outline of the main camera's view, drawn only in the overview inset
+#[derive(Component)]
+pub struct MinimapViewport;
+
+/// Query filter for the main board camera
+pub(crate) type MainCamera = (With<Camera2d>, Without<MinimapCamera>);
+
+/// Camera scale that frames the board in a window of the reference size
+const FIT_SCALE: f32 = 0.9;
+
+/// Logical window size the default framing was tuned for
+const FIT_REFERENCE: Vec2 = Vec2::new(1200.0, 800.0);
+
+/// The overview inset appears once the main camera is zoomed in past this scale
+const MINIMAP_ZOOM_THRESHOLD: f32 = 0.6;
+
+/// Side of the overview inset in logical pixels
+const MINIMAP_SIZE: f32 = 180.0;
+
+/// Render layer of the viewport outline, which only the overview camera sees
+const MINIMAP_LAYER: usize = 1;
+
+pub(crate) fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
+    layout: Res<BoardLayout>,
+    config: Res<GameConfig>,
+    theme: Res<BoardTheme>,
+) {
+    // Note: meshes and materials are kept for potential future use, but we're using SpriteBundle for 2D
+    // Create the game in the chosen variant
+    let game = hex_chess_core::Game::new(config.variant());
+    
+    // Store game data temporarily to access board
+    let game_data = GameData {
+        game,
+        selected_piece: None,
+        valid_moves: Vec::new(),
+        camera_entity: Entity::PLACEHOLDER, // Will be set after spawning
+        promotion: None,
+    };
+    
+    // Spawn 2D camera - centered on the board
+    // Default Camera2dBundle scale=1.0 means 1 pixel = 1 world unit
+    let camera_entity = commands.spawn((
+        Camera2dBundle {
+            transform: Transform::from_translation(layout.origin.extend(1000.0)), // 2D camera uses z for depth
+            projection: OrthographicProjection {
+                scale: 0.9, // Zoom in a bit more to fill the frame
+                ..default()
+            },
+            ..default()
+        },
+        IsDefaultUiCamera, // Keep the UI off the overview camera
+    )).id();
+    spawn_minimap(&mut commands, &layout);
+    
+    // Debug: log camera setup
+    info!("2D Camera spawned");
+    
+    // Update game data with camera entity
+    let game_data = GameData {
+        camera_entity,
+        ..game_data
+    };
+    
+    // Spawn the board first (needs game_data to know which tiles to spawn)
+    spawn_board(&mut commands, &mut meshes, &mut materials, &layout, &game_data, &theme, &asset_server);
+    
+    // Spawn coordinate labels around the perimeter
+    spawn_coordinate_labels(&mut commands, &layout, &game_data);
+    
+    // Store game data resource after spawning board
+    commands.insert_resource(game_data);
+    
+    // Create and cache valid move color (green highlight)
+    commands.insert_resource(ValidMoveColor {
+        color: bevy::prelude::Color::srgb(0.2, 0.8, 0.3),
+    });
+
+    announce_ready();
+}
+
+fn spawn_minimap(commands: &mut Commands, layout: &BoardLayout) {
+    // Sized and switched on by update_minimap once the main camera zooms in
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                is_active: false,
+                clear_color: ClearColorConfig::Custom(Color::srgb(0.08, 0.08, 0.1)),
+                ..default()
+            },
+            transform: Transform::from_translation(layout.origin.extend(1000.0)),
+            ..default()
+        },
+        RenderLayers::from_layers(&[0, MINIMAP_LAYER]),
+        MinimapCamera,
+    ));
+    
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(1.0, 1.0, 1.0, 0.25),
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 900.0),
+            ..default()
+        },
+        RenderLayers::layer(MINIMAP_LAYER),
+        MinimapViewport,
+    ));
+}
+
+fn spawn_board(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    layout: &BoardLayout,
+    game_data: &GameData,
+    theme: &BoardTheme,
+    _asset_server: &Res<AssetServer>,
+) {
+    let BoardTheme { light: light_color, medium: medium_color, dark: dark_color } = *theme;
+    
+    // Debug: log cell colors availability
+    let cell_colors_count = game_data.game.board.cell_colors.len();
+    let valid_coords_count = game_data.game.board.valid_coords.len();
+    info!("Board has {} valid coords, {} cell colors defined", valid_coords_count, cell_colors_count);
+    
+    // Spawn hex tiles for all valid coordinates on the board
+    let mut light_count = 0;
+    let mut dark_count = 0;
+    let mut medium_count = 0;
+    
+    for &coord in &game_data.game.board.valid_coords {
+        // Get cell color from board, with fallback to checkerboard pattern
+        let (base_color, color_name) = match game_data.game.board.cell_colors.get(&coord) {
+            Some(CellColor::Light) => {
+                light_count += 1;
+                (light_color, "Light")
+            }
+            Some(CellColor::Medium) => {
+                medium_count += 1;
+                (medium_color, "Medium")
+            }
+            Some(CellColor::Dark) => {
+                dark_count += 1;
+                (dark_color, "Dark")
+            }
+            None => {
+                // Fallback to checkerboard pattern based on hex coordinates
+                let (q, r, _s) = coord.to_cube();
+                if (q + r) % 2 == 0 {
+                    light_count += 1;
+                    (light_color, "Light (fallback)")
+                } else {
+                    dark_count += 1;
+                    (dark_color, "Dark (fallback)")
+                }
+            }
+        };
+        
+        let world = layout.board_to_world(coord);
+        
+        // Debug: log a few tile positions and their colors
+        if coord == HexCoord::new(0, 0) || coord == HexCoord::new(-2, 3) || coord == HexCoord::new(3, -4) {
+            info!("Tile at {:?} -> {} color -> world ({:.2}, {:.2})", coord, color_name, world.x, world.y);
+        }
+        
+        // Use MaterialMesh2dBundle for hexagonal tiles
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(RegularPolygon::new(layout.tile_radius(), 6)).into(),
+                material: materials.add(ColorMaterial::from(base_color)),
+                transform: Transform::from_translation(world.extend(0.0))
+                    .with_rotation(Quat::from_rotation_z(std::f32::consts::PI / 6.0)),  // Rotate 30 degrees for flat-top
+                ..default()
+            },
+            HexTile { coord, base_color },
+        ));
+    }
+    
+    // Debug: log tile color distribution
+    info!("Tile colors: {} light, {} dark, {} medium", light_count, dark_count, medium_count);
+    
+    spawn_pieces(commands, meshes, materials, layout, game_data);
+}
+
+/// Size of a piece's token as a fraction of the board scale: the royal and compound
+/// pieces stand largest, pawns smallest
+fn piece_token_size(piece_type: PieceType) -> f32 {
+    match piece_type {
+        PieceType::Pawn => 0.35,
+        PieceType::King | PieceType::Queen | PieceType::Chancellor | PieceType::Archbishop => 0.45,
+        PieceType::Rook | PieceType::Bishop | PieceType::Knight => 0.40,
+    }
+}
+
+/// Spawn an entity for every piece on the board
+pub(crate) fn spawn_pieces(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    layout: &BoardLayout,
+    game_data: &GameData,
+) {
+    // Spawn chess pieces
+    let piece_count = game_data.game.board.pieces.len();
+    info!("Spawning {} pieces", piece_count);
+    
+    for (&coord, &piece) in &game_data.game.board.pieces {
+        let world = layout.board_to_world(coord);
+        
+        info!("Spawning piece {:?} at {:?} -> world ({:.2}, {:.2})", piece, coord, world.x, world.y);
+        
+        // Side picks the token colour; the letter comes from the notation, so fairy pieces get theirs too
+        let piece_color = match piece.color {
+            ChessColor::White => bevy::prelude::Color::srgb(1.0, 1.0, 1.0),
+            ChessColor::Black => bevy::prelude::Color::srgb(0.2, 0.2, 0.2),
+        };
+        let piece_label = piece_letter(piece.piece_type).to_string();
+        let piece_size = piece_token_size(piece.piece_type);
+        
+        let text_color = if piece.color == ChessColor::White {
+            bevy::prelude::Color::srgb(0.0, 0.0, 0.0) // Black text for white pieces
+        } else {
+            bevy::prelude::Color::srgb(1.0, 1.0, 1.0) // White text for black pieces
+        };
+        
+        // Create piece as a hexagonal mesh
+        let piece_size_pixels = piece_size * layout.scale * 0.35;
+        
+        // Spawn piece with hexagonal mesh and text label
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(RegularPolygon::new(piece_size_pixels, 6)).into(),
+                material: materials.add(ColorMaterial::from(piece_color)),
+                transform: Transform::from_translation(world.extend(1.0)) // z=1.0 to be above tiles
+                    .with_rotation(Quat::from_rotation_z(std::f32::consts::PI / 6.0)),  // Rotate 30 degrees for flat-top
+                ..default()
+            },
+            ChessPiece { coord, piece },
+        )).with_children(|parent| {
+            // Add text label for piece type (use default font if custom font not available)
+            parent.spawn((Text2dBundle {
+                text: Text::from_section(
+                    piece_label,
+                    TextStyle {
+                        font_size: piece_size * layout.scale * 0.5,
+                        color: text_color,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(0.0, 0.0, 0.1), // Slightly above the hexagon
+                ..default()
+            }, PieceLabel));
+        });
+    }
+    
+    info!("Finished spawning pieces. Total pieces on board: {}", game_data.game.board.pieces.len());
+}
+
+pub(crate) fn update_board_visuals(
+    _game_data: Res<GameData>,
+    _piece_query: Query<(&mut Transform, &ChessPiece)>,
+) {
+    // Update piece positions based on game state
+    // Note: Selection visuals are handled in update_selection_visuals
+    // This function is kept for future use when we need to sync piece positions
+    // with the game board state after moves
+}
+
+/// How a cell is tinted beyond its own colour; shared by the 2D and 3D boards
+pub(crate) struct TileShading {
+    fog: Option<std::collections::HashSet<HexCoord>>,
+    checkers: Vec<HexCoord>,
+}
+
+impl TileShading {
+    pub(crate) fn new(game: &hex_chess_core::Game) -> Self {
+        // In fog-of-war variants the side to move only sees what its pieces reach
+        let fog = game.variant.has_fog_of_war().then(|| game.visible_cells(game.current_player));
+        // Pieces giving check stand on red while the warning shows
+        let checkers = match game.game_state {
+            hex_chess_core::GameState::Check(color) => game.board.checkers(color),
+            _ => Vec::new(),
+        };
+        Self { fog, checkers }
+    }
+    
+    pub(crate) fn hidden(&self, coord: HexCoord) -> bool {
+        self.fog.as_ref().is_some_and(|visible| !visible.contains(&coord))
+    }
+    
+    /// The colour to draw a cell: green where the selection can go, dark under fog, red under a checker
+    pub(crate) fn color(&self, game_data: &GameData, coord: HexCoord, base: Color, valid_move: Color) -> Color {
+        if game_data.valid_moves.contains(&coord) {
+            valid_move
+        } else if self.hidden(coord) {
+            base.mix(&Color::srgb(0.05, 0.05, 0.08), 0.8)
+        } else if self.checkers.contains(&coord) {
+            base.mix(&Color::srgb(0.9, 0.1, 0.1), 0.6)
+        } else {
+            base
+        }
+    }
+}
+
+pub(crate) fn update_selection_visuals(
+    game_data: Res<GameData>,
+    mut piece_query: Query<(&mut Transform, &mut Visibility, &ChessPiece)>,
+    mut tile_query: Query<(&mut Transform, &Handle<ColorMaterial>, &HexTile), Without<ChessPiece>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    valid_move_color: Res<ValidMoveColor>,
+    layout: Res<BoardLayout>,
+) {
+
+    let shading = TileShading::new(&game_data.game);
+    
+    // Update piece positions - selected pieces are highlighted by z-index
+    for (mut transform, mut visibility, chess_piece) in piece_query.iter_mut() {
+        *visibility = if shading.hidden(chess_piece.coord) { Visibility::Hidden } else { Visibility::Inherited };
+        let world = layout.board_to_world(chess_piece.coord);
+        let is_selected = game_data.selected_piece == Some(chess_piece.coord);
+        let z = if is_selected {
+            2.0 // Raise selected piece above normal pieces
+        } else {
+            1.0 // Normal height above tiles
+        };
+        
+        // Note: Scale changes would require access to the mesh handle
+        // For now, we just use z-index to highlight selected pieces
+        
+        transform.translation = world.extend(z);
+    }
+    
+    // Highlight valid move tiles by changing color
+    for (mut transform, material_handle, tile) in tile_query.iter_mut() {
+        let world = layout.board_to_world(tile.coord);
+        // Change material color for valid moves (green highlight), fog and checkers
+        if let Some(material) = materials.get_mut(material_handle) {
+            material.color = shading.color(&game_data, tile.coord, tile.base_color, valid_move_color.color);
+        }
+        
+        transform.translation = world.extend(0.0);
+    }
+}
+
+pub(crate) fn handle_camera_zoom(
+    mut camera_query: Query<&mut OrthographicProjection, MainCamera>,
+    mut scroll_events: EventReader<MouseWheel>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+) {
+    let mut projection = match camera_query.get_single_mut() {
+        Ok(proj) => proj,
+        Err(_) => return,
+    };
+    
+    // Mouse wheel zoom
+    for event in scroll_events.read() {
+        let zoom_delta = event.y * 0.1;
+        projection.scale = (projection.scale - zoom_delta).clamp(0.2, 2.0);
+        
+        info!("Zoom: {:.2}", projection.scale);
+    }
+    
+    // Keyboard zoom (+ and - keys)
+    if keyboard.pressed(KeyCode::Equal) || keyboard.pressed(KeyCode::NumpadAdd) {
+        projection.scale = (projection.scale - 0.02).max(0.2);
+    }
+    if keyboard.pressed(KeyCode::Minus) || keyboard.pressed(KeyCode::NumpadSubtract) {
+        projection.scale = (projection.scale + 0.02).min(2.0);
+    }
+}
+
+pub(crate) fn minimap_contains(camera: &Camera, cursor: Vec2) -> bool {
+    camera.is_active && camera.logical_viewport_rect().is_some_and(|rect| rect.contains(cursor))
+}
+
+pub(crate) fn update_minimap(
+    windows: Query<&Window>,
+    main_camera: Query<(&OrthographicProjection, &GlobalTransform), MainCamera>,
+    mut minimap_camera: Query<(&mut Camera, &mut OrthographicProjection), With<MinimapCamera>>,
+    mut outline: Query<&mut Transform, With<MinimapViewport>>,
+    hex_tiles: Query<&GlobalTransform, With<HexTile>>,
+    layout: Res<BoardLayout>,
+    config: Res<GameConfig>,
+) {
+    let (Ok(window), Ok((main_projection, main_transform)), Ok((mut camera, mut projection))) =
+        (windows.get_single(), main_camera.get_single(), minimap_camera.get_single_mut())
+    else {
+        return;
+    };
+    
+    // Only worth showing when zoomed in, and only if the window has room for it
+    let show = !config.render_3d
+        && main_projection.scale < MINIMAP_ZOOM_THRESHOLD
+        && window.width() > MINIMAP_SIZE * 3.0
+        && window.height() > MINIMAP_SIZE + 130.0;
+    if camera.is_active != show {
+        camera.is_active = show;
+    }
+    if !show {
+        return;
+    }
+    
+    // Top right, below the captured pieces area
+    let scale_factor = window.scale_factor();
+    let position = Vec2::new(window.width() - MINIMAP_SIZE - 10.0, 110.0) * scale_factor;
+    let size = (MINIMAP_SIZE * scale_factor) as u32;
+    camera.viewport = Some(Viewport {
+        physical_position: position.as_uvec2(),
+        physical_size: UVec2::splat(size),
+        ..default()
+    });
+    
+    // Fit the whole board, whichever variant is loaded
+    let extent = hex_tiles
+        .iter()
+        .map(|tile| (tile.translation().truncate() - layout.origin).abs().max_element())
+        .fold(0.0, f32::max)
+        + layout.scale * 0.5;
+    projection.scale = extent * 2.0 / MINIMAP_SIZE;
+    
+    if let Ok(mut outline) = outline.get_single_mut() {
+        outline.translation = main_transform.translation().truncate().extend(900.0);
+        outline.scale = main_projection.area.size().extend(1.0);
+    }
+}
+
+pub(crate) fn handle_minimap_click(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    minimap_camera: Query<(&Camera, &GlobalTransform), With<MinimapCamera>>,
+    mut main_camera: Query<&mut Transform, MainCamera>,
+) {
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform)), Ok(mut main_transform)) =
+        (windows.get_single(), minimap_camera.get_single(), main_camera.get_single_mut())
+    else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    if !minimap_contains(camera, cursor) {
+        return;
+    }
+    
+    // Holding the button down drags the view around the overview
+    let Some(rect) = camera.logical_viewport_rect() else {
+        return;
+    };
+    if let Some(target) = camera.viewport_to_world_2d(camera_transform, cursor - rect.min) {
+        main_transform.translation.x = target.x;
+        main_transform.translation.y = target.y;
+    }
+}
+
+pub(crate) fn apply_display_scale(
+    config: Res<GameConfig>,
+    mut ui_scale: ResMut<UiScale>,
+    mut pieces: Query<(Ref<ChessPiece>, &mut Transform), Without<PieceLabel>>,
+    mut labels: Query<(Ref<PieceLabel>, &mut Transform), Without<ChessPiece>>,
+) {
+    if config.is_changed() && ui_scale.0 != config.ui_scale {
+        ui_scale.0 = config.ui_scale;
+    }
+    
+    // Pieces are respawned wholesale at times, so new ones pick the sizes up too
+    for (piece, mut transform) in pieces.iter_mut() {
+        if config.is_changed() || piece.is_added() {
+            transform.scale = Vec3::splat(config.piece_scale);
+        }
+    }
+    for (label, mut transform) in labels.iter_mut() {
+        if config.is_changed() || label.is_added() {
+            transform.scale = Vec3::splat(config.label_scale);
+        }
+    }
+}
+
+fn fitted_camera_scale(window: &Window) -> f32 {
+    // Measure in logical pixels, so a high-DPI canvas frames the board the same as any other
+    let logical = Vec2::new(window.physical_width() as f32, window.physical_height() as f32) / window.scale_factor();
+    if logical.min_element() <= 0.0 {
+        return FIT_SCALE;
+    }
+    (FIT_SCALE * (FIT_REFERENCE / logical).max_element()).clamp(0.2, 2.0)
+}
+
+pub(crate) fn fit_camera_to_window(
+    mut resized: EventReader<WindowResized>,
+    mut rescaled: EventReader<WindowScaleFactorChanged>,
+    windows: Query<&Window>,
+    mut camera_query: Query<&mut OrthographicProjection, MainCamera>,
+) {
+    // Moving to a display with a different pixel ratio counts as a resize too
+    let changed = resized.read().count() + rescaled.read().count() > 0;
+    if !changed {
+        return;
+    }
+    let (Ok(window), Ok(mut projection)) = (windows.get_single(), camera_query.get_single_mut()) else {
+        return;
+    };
+    projection.scale = fitted_camera_scale(window);
+    
+    info!(
+        "Window {}x{} at {}x pixel ratio, camera scale {:.2}",
+        window.width(),
+        window.height(),
+        window.scale_factor(),
+        projection.scale,
+    );
+}
+
+pub(crate) fn handle_camera_pan(
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), MainCamera>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut last_cursor_pos: Local<Option<Vec2>>,
+    windows: Query<&Window>,
+    config: Res<GameConfig>,
+    layout: Res<BoardLayout>,
+) {
+    let (mut camera_transform, projection) = match camera_query.get_single_mut() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    
+    // Arrow key panning
+    let pan_speed = 5.0;
+    if keyboard.pressed(KeyCode::ArrowLeft) {
+        camera_transform.translation.x -= pan_speed;
+    }
+    if keyboard.pressed(KeyCode::ArrowRight) {
+        camera_transform.translation.x += pan_speed;
+    }
+    if keyboard.pressed(KeyCode::ArrowUp) {
+        camera_transform.translation.y += pan_speed;
+    }
+    if keyboard.pressed(KeyCode::ArrowDown) {
+        camera_transform.translation.y -= pan_speed;
+    }
+    
+    // Middle mouse button drag panning
+    let window = windows.single();
+    if mouse_buttons.pressed(MouseButton::Middle) {
+        if let Some(cursor_pos) = window.cursor_position() {
+            if let Some(last_pos) = *last_cursor_pos {
+                // Cursor positions are logical pixels; the projection scale turns them into world units
+                let delta = (cursor_pos - last_pos) * projection.scale;
+                camera_transform.translation.x -= delta.x;
+                camera_transform.translation.y += delta.y; // Invert Y
+            }
+            *last_cursor_pos = Some(cursor_pos);
+        }
+    } else {
+        *last_cursor_pos = None;
+    }
+    
+    // Reset camera with the reset key ('R' by default)
+    if config.keys.just_pressed(&keyboard, Action::ResetCamera) {
+        camera_transform.translation = layout.origin.extend(1000.0);
+        info!("Camera reset to center");
+    }
+}
+
+fn spawn_coordinate_labels(
+    commands: &mut Commands,
+    layout: &BoardLayout,
+    game_data: &GameData,
+) {
+    const LABEL_DISTANCE: f32 = 1.3; // Position labels 30% beyond hex center
+    
+    info!("Spawning coordinate labels...");
+    
+    for &coord in &game_data.game.board.valid_coords {
+        // Check if this is a perimeter hex (has at least one invalid neighbor)
+        let neighbors = coord.neighbors();
+        let is_perimeter = neighbors.iter()
+            .any(|n| !game_data.game.board.valid_coords.contains(n));
+        
+        if is_perimeter {
+            let (px, py) = coord.to_pixel();
+            let label = layout.hex_to_world(Vec2::new(px, py) * LABEL_DISTANCE);
+            
+            // Use the board's file/rank labels if it has them, otherwise fall back to axial
+            let label_text = game_data.game.board.board_type.square_name(coord)
+                .unwrap_or_else(|| format!("({}, {})", coord.q, coord.r));
+            
+            commands.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        label_text,
+                        TextStyle {
+                            font_size: 11.0,
+                            color: Color::srgba(0.7, 0.7, 0.7, 0.6),
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_translation(label.extend(5.0)),
+                    ..default()
+                },
+                CoordinateLabel,
+            ));
+        }
+    }
+    
+    info!("Coordinate labels spawned");
+}
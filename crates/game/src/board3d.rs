@@ -0,0 +1,307 @@
+use bevy::prelude::*;
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::render::view::RenderLayers;
+use hex_chess_core::{HexCoord, Piece, PieceType, Color as ChessColor};
+
+use crate::{GameData, ValidMoveColor};
+use crate::board::{BoardLayout, HexTile, MainCamera, TileShading};
+use crate::settings::{Action, GameConfig};
+
+/// The optional 3D view: the same game drawn as extruded hex prisms and solid
+/// pieces, seen through an orbiting camera
+pub struct Board3dPlugin;
+
+impl Plugin for Board3dPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, spawn_3d_camera)
+            .add_systems(Update, (
+                apply_render_mode,
+                spawn_3d_tiles.run_if(render_3d),
+                sync_3d_pieces.run_if(render_3d),
+                update_3d_visuals.run_if(render_3d),
+                orbit_camera.run_if(render_3d),
+            ).chain());
+    }
+}
+
+/// Whether the board is drawn in 3D
+pub fn render_3d(config: Res<GameConfig>) -> bool {
+    config.render_3d
+}
+
+/// Whether the board is drawn in 2D, for systems that only make sense there
+pub fn render_2d(config: Res<GameConfig>) -> bool {
+    !config.render_3d
+}
+
+/// The camera of the 3D view, circling the centre of the board
+#[derive(Component, Debug, Clone, Copy)]
+pub struct OrbitCamera {
+    pub yaw: f32,      // radians around the board; 0 looks from White's side
+    pub pitch: f32,    // radians above the board
+    pub distance: f32, // from the centre of the board, in world units
+}
+
+impl OrbitCamera {
+    fn facing(layout: &BoardLayout) -> Self {
+        Self {
+            yaw: if layout.flipped { std::f32::consts::PI } else { 0.0 },
+            pitch: 0.9,
+            distance: layout.scale * 14.0,
+        }
+    }
+
+    fn transform(&self, layout: &BoardLayout) -> Transform {
+        let target = to_3d(layout.origin, 0.0);
+        let offset = Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        ) * self.distance;
+        Transform::from_translation(target + offset).looking_at(target, Vec3::Y)
+    }
+}
+
+/// A cell of the 3D board
+#[derive(Component)]
+pub struct Tile3d {
+    pub coord: HexCoord,
+    pub base_color: Color,
+}
+
+/// A piece of the 3D board
+#[derive(Component)]
+pub struct Piece3d {
+    pub coord: HexCoord,
+    pub piece: Piece,
+}
+
+/// Height of a tile prism, as a fraction of the board scale
+const TILE_DEPTH: f32 = 0.12;
+
+/// Height a selected piece is lifted by, as a fraction of the board scale
+const SELECTED_LIFT: f32 = 0.15;
+
+/// Render layer nothing is drawn on, given to the 2D camera so it only draws the UI
+const EMPTY_LAYER: usize = 2;
+
+/// A point of the 2D board's world at `height` above the 3D board's floor
+pub fn to_3d(world: Vec2, height: f32) -> Vec3 {
+    Vec3::new(world.x, height, -world.y)
+}
+
+/// The cell under a cursor position, found by casting a ray onto the top of the tiles
+pub fn pick_cell_3d(camera: &Camera, camera_transform: &GlobalTransform, cursor: Vec2, layout: &BoardLayout) -> Option<HexCoord> {
+    let ray = camera.viewport_to_world(camera_transform, cursor)?;
+    let top = Vec3::Y * TILE_DEPTH * layout.scale;
+    let distance = ray.intersect_plane(top, InfinitePlane3d::new(Vec3::Y))?;
+    let hit = ray.get_point(distance);
+    Some(layout.world_to_board(Vec2::new(hit.x, -hit.z)))
+}
+
+fn spawn_3d_camera(mut commands: Commands, layout: Res<BoardLayout>) {
+    let orbit = OrbitCamera::facing(&layout);
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: -1, // under the 2D camera, which keeps drawing the UI
+                is_active: false,
+                ..default()
+            },
+            transform: orbit.transform(&layout),
+            ..default()
+        },
+        orbit,
+    ));
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            illuminance: 6000.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(-0.4, 1.0, 0.6).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+/// Switch between the 2D and 3D views when the setting changes
+fn apply_render_mode(
+    config: Res<GameConfig>,
+    mut camera_3d: Query<&mut Camera, (With<OrbitCamera>, Without<Camera2d>)>,
+    mut camera_2d: Query<(Entity, &mut Camera), MainCamera>,
+    mut commands: Commands,
+) {
+    if !config.is_changed() {
+        return;
+    }
+    if let Ok(mut camera) = camera_3d.get_single_mut() {
+        if camera.is_active != config.render_3d {
+            camera.is_active = config.render_3d;
+        }
+    }
+    let Ok((entity, mut camera)) = camera_2d.get_single_mut() else {
+        return;
+    };
+    // The 2D camera stays on for the UI, drawing over the 3D view instead of the 2D board
+    if config.render_3d {
+        camera.clear_color = ClearColorConfig::None;
+        commands.entity(entity).insert(RenderLayers::layer(EMPTY_LAYER));
+    } else {
+        camera.clear_color = ClearColorConfig::Default;
+        commands.entity(entity).remove::<RenderLayers>();
+    }
+}
+
+/// Raise a prism under every 2D tile, in the same colours
+fn spawn_3d_tiles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    tiles: Query<&HexTile>,
+    tiles_3d: Query<(), With<Tile3d>>,
+    layout: Res<BoardLayout>,
+) {
+    if !tiles_3d.is_empty() {
+        return;
+    }
+    let depth = TILE_DEPTH * layout.scale;
+    let prism = meshes.add(Extrusion::new(RegularPolygon::new(layout.tile_radius(), 6), depth));
+    // Extrusions run along z: stand them up, and turn them flat-top like the 2D tiles
+    let rotation = Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2) * Quat::from_rotation_z(std::f32::consts::PI / 6.0);
+    for tile in tiles.iter() {
+        commands.spawn((
+            PbrBundle {
+                mesh: prism.clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: tile.base_color,
+                    perceptual_roughness: 0.8,
+                    ..default()
+                }),
+                transform: Transform::from_translation(to_3d(layout.board_to_world(tile.coord), depth / 2.0))
+                    .with_rotation(rotation),
+                ..default()
+            },
+            Tile3d { coord: tile.coord, base_color: tile.base_color },
+        ));
+    }
+}
+
+/// The solid shape standing on a piece's base, with its height above the base's bottom
+fn piece_crown(piece_type: PieceType, scale: f32) -> (Mesh, f32) {
+    match piece_type {
+        PieceType::Pawn => (Sphere::new(0.16 * scale).into(), 0.24),
+        PieceType::Knight => (Cuboid::new(0.18 * scale, 0.42 * scale, 0.3 * scale).into(), 0.29),
+        PieceType::Bishop => (Cone { radius: 0.2 * scale, height: 0.5 * scale }.into(), 0.33),
+        PieceType::Rook => (Cylinder::new(0.22 * scale, 0.4 * scale).into(), 0.28),
+        PieceType::Queen => (Capsule3d::new(0.17 * scale, 0.38 * scale).into(), 0.44),
+        PieceType::King => (Cylinder::new(0.2 * scale, 0.6 * scale).into(), 0.38),
+        PieceType::Chancellor => (Cuboid::new(0.34 * scale, 0.44 * scale, 0.34 * scale).into(), 0.3),
+        PieceType::Archbishop => (Torus::new(0.1 * scale, 0.22 * scale).into(), 0.34),
+    }
+}
+
+/// Rebuild the 3D pieces whenever the position on the board changes
+fn sync_3d_pieces(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_data: Res<GameData>,
+    pieces: Query<Entity, With<Piece3d>>,
+    layout: Res<BoardLayout>,
+    mut shown: Local<Option<(u64, usize)>>,
+) {
+    let board = &game_data.game.board;
+    let position = Some((board.position_hash(), board.pieces.len()));
+    if *shown == position && !pieces.is_empty() {
+        return;
+    }
+    *shown = position;
+    for entity in pieces.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let scale = layout.scale;
+    let base = meshes.add(Cylinder::new(0.3 * scale, 0.08 * scale));
+    let white = materials.add(StandardMaterial { base_color: Color::srgb(0.95, 0.93, 0.88), ..default() });
+    let black = materials.add(StandardMaterial { base_color: Color::srgb(0.15, 0.15, 0.17), ..default() });
+    for (&coord, &piece) in &board.pieces {
+        let material = match piece.color {
+            ChessColor::White => white.clone(),
+            ChessColor::Black => black.clone(),
+        };
+        let (crown, height) = piece_crown(piece.piece_type, scale);
+        commands.spawn((
+            PbrBundle {
+                mesh: base.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(to_3d(layout.board_to_world(coord), (TILE_DEPTH + 0.04) * scale)),
+                ..default()
+            },
+            Piece3d { coord, piece },
+        )).with_children(|parent| {
+            parent.spawn(PbrBundle {
+                mesh: meshes.add(crown),
+                material,
+                transform: Transform::from_xyz(0.0, (height - 0.04) * scale, 0.0),
+                ..default()
+            });
+        });
+    }
+}
+
+/// Highlights, fog and the selected piece, as the 2D board shows them
+fn update_3d_visuals(
+    game_data: Res<GameData>,
+    config: Res<GameConfig>,
+    valid_move_color: Res<ValidMoveColor>,
+    layout: Res<BoardLayout>,
+    tiles: Query<(&Tile3d, &Handle<StandardMaterial>)>,
+    mut pieces: Query<(&Piece3d, &mut Transform, &mut Visibility)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let shading = TileShading::new(&game_data.game);
+    for (tile, material) in tiles.iter() {
+        let color = shading.color(&game_data, tile.coord, tile.base_color, valid_move_color.color);
+        if let Some(material) = materials.get_mut(material) {
+            if material.base_color != color {
+                material.base_color = color;
+            }
+        }
+    }
+
+    for (piece, mut transform, mut visibility) in pieces.iter_mut() {
+        *visibility = if shading.hidden(piece.coord) { Visibility::Hidden } else { Visibility::Inherited };
+        let lift = if game_data.selected_piece == Some(piece.coord) { SELECTED_LIFT } else { 0.0 };
+        transform.translation.y = (TILE_DEPTH + 0.04 + lift) * layout.scale;
+        transform.scale = Vec3::splat(config.piece_scale);
+    }
+}
+
+/// Drag with the right button to circle the board, scroll to move closer or further
+fn orbit_camera(
+    mut camera: Query<(&mut OrbitCamera, &mut Transform)>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut motion: EventReader<MouseMotion>,
+    mut scroll: EventReader<MouseWheel>,
+    config: Res<GameConfig>,
+    layout: Res<BoardLayout>,
+) {
+    let Ok((mut orbit, mut transform)) = camera.get_single_mut() else {
+        return;
+    };
+    let dragged: Vec2 = motion.read().map(|event| event.delta).sum();
+    if mouse_buttons.pressed(MouseButton::Right) {
+        orbit.yaw -= dragged.x * 0.01;
+        orbit.pitch = (orbit.pitch + dragged.y * 0.01).clamp(0.2, 1.5);
+    }
+    for event in scroll.read() {
+        orbit.distance = (orbit.distance * (1.0 - event.y * 0.1)).clamp(layout.scale * 4.0, layout.scale * 30.0);
+    }
+    if config.keys.just_pressed(&keyboard, Action::ResetCamera) {
+        *orbit = OrbitCamera::facing(&layout);
+    }
+    if orbit.is_changed() {
+        *transform = orbit.transform(&layout);
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use hex_chess_core::{board_svg_styled, Color as ChessColor, SvgStyle};
+
+use crate::{GameData, GameState};
+use crate::board::TileShading;
+use crate::platform::copy_image;
+use crate::settings::{Action, GameConfig};
+use crate::toast::ShowToast;
+
+/// Copying the position on the board as a picture, drawn by the same renderer as the
+/// lobby's thumbnails
+pub struct BoardImagePlugin;
+
+impl Plugin for BoardImagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, copy_board_image.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// Width of a copied board image, in pixels
+const IMAGE_WIDTH: u32 = 800;
+
+/// The position as the player sees it, as SVG: fogged pieces stay hidden, and the
+/// board turns to the side to move if the settings ask for it
+pub fn board_image_svg(game_data: &GameData, config: &GameConfig) -> String {
+    let game = &game_data.game;
+    let shading = TileShading::new(game);
+    let mut board = game.board.clone();
+    board.pieces.retain(|&coord, _| !shading.hidden(coord));
+    board_svg_styled(&board, SvgStyle {
+        width: IMAGE_WIDTH,
+        labels: config.image_labels,
+        flipped: config.image_from_mover && game.current_player == ChessColor::Black,
+    })
+}
+
+fn copy_board_image(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_data: Res<GameData>,
+    config: Res<GameConfig>,
+) {
+    if !config.keys.just_pressed(&keyboard_input, Action::CopyBoardImage) {
+        return;
+    }
+    copy_image("hex-chess-board", &board_image_svg(&game_data, &config));
+    commands.add(ShowToast::info("Board image copied"));
+}
@@ -0,0 +1,274 @@
+use bevy::prelude::*;
+use hex_chess_core::{PieceType, Variants, Drill};
+
+use crate::{GameData, GameState};
+use crate::board::{ChessPiece, HexTile, PieceAssets, spawn_pieces, update_selection_visuals};
+use crate::input::BoardPicker;
+use crate::settings::{Action, GameConfig};
+use crate::toast::ShowToast;
+
+/// Piece movement drills played on the main board
+pub struct DrillPlugin;
+
+impl Plugin for DrillPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnExit(GameState::Playing), leave_drill)
+            .add_systems(Update, (
+                handle_drill_keys,
+                handle_drill_input,
+                sync_drill_board,
+                update_drill_targets.after(update_selection_visuals),
+                update_drill_ui,
+            ).chain().run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// A single-piece training drill in progress, with the real game set aside
+#[derive(Resource)]
+pub struct ActiveDrill {
+    pub drill: Drill,
+    pub round: usize,                           // drills started so far, picks the piece
+    pub saved: Option<hex_chess_core::Game>,    // the real game, put back when the drill ends
+}
+
+impl ActiveDrill {
+    /// The drill for this round, or why one couldn't be set up
+    pub fn new(round: usize) -> Result<Self, String> {
+        let piece_type = DRILL_PIECES[round % DRILL_PIECES.len()];
+        let seed = getrandom::u64().unwrap_or(round as u64);
+        let board_type = Variants::glinski_chess().board_type;
+        match Drill::generate(board_type, piece_type, DRILL_TARGETS, seed) {
+            Ok(drill) => Ok(Self { drill, round, saved: None }),
+            Err(e) => Err(format!("Could not set up a {:?} drill: {}", piece_type, e)),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct DrillUI;
+
+/// Pieces the training drills take turns with
+const DRILL_PIECES: [PieceType; 5] = [
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+/// Cells to reach in each drill
+const DRILL_TARGETS: usize = 4;
+
+pub(crate) fn handle_drill_keys(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<GameConfig>,
+    drill: Option<ResMut<ActiveDrill>>,
+    mut game_data: ResMut<GameData>,
+    mut assets: PieceAssets,
+    piece_query: Query<Entity, With<ChessPiece>>,
+) {
+    let Some(mut drill) = drill else {
+        return;
+    };
+    
+    // Next drill, with the next piece
+    if config.keys.just_pressed(&keyboard_input, Action::Training) {
+        match ActiveDrill::new(drill.round + 1) {
+            Ok(next) => {
+                drill.drill = next.drill;
+                drill.round = next.round;
+            }
+            Err(e) => commands.add(ShowToast::error(e)),
+        }
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::Back) {
+        end_drill(&mut commands, &mut drill, &mut game_data, &mut assets, &piece_query);
+    }
+}
+
+pub(crate) fn handle_drill_input(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    picker: BoardPicker,
+    drill: Option<ResMut<ActiveDrill>>,
+    mut game_data: ResMut<GameData>,
+) {
+    let Some(mut drill) = drill else {
+        return;
+    };
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(coord) = picker.clicked_hex() else {
+        return;
+    };
+    
+    // Click the piece to show where it can go, then click one of those cells
+    if game_data.selected_piece.is_some() && game_data.valid_moves.contains(&coord) {
+        if let Ok(reached) = drill.drill.make_move(coord) {
+            if reached {
+                info!("Target reached, {} to go", drill.drill.targets.len());
+            }
+        }
+        game_data.selected_piece = None;
+        game_data.valid_moves.clear();
+    } else if coord == drill.drill.position && !drill.drill.is_complete() {
+        game_data.selected_piece = Some(coord);
+        game_data.valid_moves = drill.drill.legal_moves();
+    } else {
+        game_data.selected_piece = None;
+        game_data.valid_moves.clear();
+    }
+}
+
+pub(crate) fn sync_drill_board(
+    mut commands: Commands,
+    drill: Option<ResMut<ActiveDrill>>,
+    mut game_data: ResMut<GameData>,
+    mut assets: PieceAssets,
+    piece_query: Query<Entity, With<ChessPiece>>,
+) {
+    let Some(mut drill) = drill else {
+        return;
+    };
+    if !drill.is_changed() {
+        return;
+    }
+    
+    // Set the real game aside the first time round
+    if drill.saved.is_none() {
+        let saved = std::mem::replace(&mut game_data.game, hex_chess_core::Game::new(Variants::glinski_chess()));
+        drill.bypass_change_detection().saved = Some(saved);
+        game_data.selected_piece = None;
+        game_data.valid_moves.clear();
+    }
+    game_data.game.board = drill.drill.board.clone();
+    
+    for entity in piece_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_pieces(&mut commands, &mut assets.meshes, &mut assets.materials, &assets.layout, &game_data);
+}
+
+pub(crate) fn update_drill_targets(
+    drill: Option<Res<ActiveDrill>>,
+    tile_query: Query<(&Handle<ColorMaterial>, &HexTile)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let Some(drill) = drill else {
+        return;
+    };
+    
+    // The next target stands out; the ones after it are fainter
+    for (position, target) in drill.drill.targets.iter().enumerate() {
+        let Some((handle, tile)) = tile_query.iter().find(|(_, tile)| tile.coord == *target) else {
+            continue;
+        };
+        let tint = if position == 0 {
+            Color::srgb(0.95, 0.75, 0.2)
+        } else {
+            tile.base_color.mix(&Color::srgb(0.95, 0.75, 0.2), 0.4)
+        };
+        if let Some(material) = materials.get_mut(handle) {
+            material.color = tint;
+        }
+    }
+}
+
+pub(crate) fn update_drill_ui(
+    mut commands: Commands,
+    drill: Option<Res<ActiveDrill>>,
+    mut ui_query: Query<(Entity, &mut Text), With<DrillUI>>,
+    config: Res<GameConfig>,
+) {
+    let Some(drill) = drill else {
+        for (entity, _) in ui_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+    if !drill.is_changed() {
+        return;
+    }
+    
+    let keys = &config.keys;
+    let piece = format!("{:?}", drill.drill.piece.piece_type);
+    let content = if drill.drill.is_complete() {
+        format!(
+            "{} drill done in {} moves (par {})\n{} for another drill, {} to leave",
+            piece,
+            drill.drill.moves,
+            drill.drill.par,
+            keys.name(Action::Training),
+            keys.name(Action::Back),
+        )
+    } else {
+        format!(
+            "{} drill: reach the gold cells in order\nMoves {}, par {}, {} targets left\n{} for another drill, {} to leave",
+            piece,
+            drill.drill.moves,
+            drill.drill.par,
+            drill.drill.targets.len(),
+            keys.name(Action::Training),
+            keys.name(Action::Back),
+        )
+    };
+    
+    if let Ok((_, mut text)) = ui_query.get_single_mut() {
+        text.sections[0].value = content;
+        return;
+    }
+    
+    commands.spawn((
+        TextBundle::from_section(
+            content,
+            TextStyle {
+                font_size: 16.0,
+                color: bevy::prelude::Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            left: Val::Px(20.0),
+            max_width: Val::Px(480.0),
+            ..default()
+        }).with_background_color(bevy::prelude::Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        DrillUI,
+    ));
+}
+
+pub(crate) fn leave_drill(
+    mut commands: Commands,
+    drill: Option<ResMut<ActiveDrill>>,
+    mut game_data: ResMut<GameData>,
+    mut assets: PieceAssets,
+    piece_query: Query<Entity, With<ChessPiece>>,
+) {
+    if let Some(mut drill) = drill {
+        end_drill(&mut commands, &mut drill, &mut game_data, &mut assets, &piece_query);
+    }
+}
+
+/// Put the real game back on the board and drop the drill
+fn end_drill(
+    commands: &mut Commands,
+    drill: &mut ActiveDrill,
+    game_data: &mut GameData,
+    assets: &mut PieceAssets,
+    piece_query: &Query<Entity, With<ChessPiece>>,
+) {
+    commands.remove_resource::<ActiveDrill>();
+    if let Some(saved) = drill.saved.take() {
+        game_data.game = saved;
+    }
+    game_data.selected_piece = None;
+    game_data.valid_moves.clear();
+    
+    for entity in piece_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_pieces(commands, &mut assets.meshes, &mut assets.materials, &assets.layout, game_data);
+}
@@ -0,0 +1,579 @@
+use bevy::prelude::*;
+use hex_chess_core::Color as ChessColor;
+
+use crate::{GameData, GameState};
+use crate::hud::GameTimer;
+use crate::input::Confirmation;
+use crate::settings::{Action, GameConfig};
+
+/// Spotting the end of a game and the results screen that follows
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, check_game_over_conditions.run_if(in_state(GameState::Playing)))
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen)
+            .add_systems(OnExit(GameState::GameOver), cleanup_game_over_screen)
+            .add_systems(Update, (
+                handle_game_over_input.run_if(not(resource_exists::<Confirmation>)),
+                update_celebration,
+            ).run_if(in_state(GameState::GameOver)));
+    }
+}
+
+#[derive(Component)]
+pub struct GameOverUI;
+
+/// How the game-over screen marks the result
+#[derive(Clone, Copy)]
+pub enum Celebration {
+    Win(ChessColor),
+    Draw,
+}
+
+/// A confetti or drift particle on the game-over screen, positioned in percent of the window
+#[derive(Component)]
+pub struct CelebrationParticle {
+    pub position: Vec2,
+    pub velocity: Vec2, // percent per second
+    pub gravity: f32,
+    pub life: f32,      // seconds
+    pub age: f32,
+}
+
+/// Particles in a win's confetti burst
+const CONFETTI_COUNT: usize = 80;
+
+/// Particles drifting down after a draw
+const DRIFT_COUNT: usize = 30;
+
+/// Plot area of a post-game report chart, in logical pixels
+const CHART_SIZE: Vec2 = Vec2::new(300.0, 140.0);
+
+/// One line on a chart: a value per move, with gaps where there is no reading
+struct ChartSeries {
+    color: Color,
+    values: Vec<Option<f32>>,
+}
+
+pub(crate) fn check_game_over_conditions(
+    game_data: Res<GameData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    use hex_chess_core::GameState as CoreGameState;
+    
+    // First check if the core game already detected game over
+    match game_data.game.game_state {
+        CoreGameState::Checkmate(_) | CoreGameState::Stalemate | CoreGameState::Draw(_) | CoreGameState::Resigned(_) | CoreGameState::Exploded(_) | CoreGameState::KingCaptured(_) => {
+            next_state.set(GameState::GameOver);
+            
+            let msg = match game_data.game.game_state {
+                CoreGameState::Checkmate(winner) => {
+                    let winner_name = match winner {
+                        ChessColor::White => "White",
+                        ChessColor::Black => "Black",
+                    };
+                    format!("CHECKMATE! {} wins!", winner_name)
+                }
+                CoreGameState::Stalemate => format!(
+                    "STALEMATE! {}.",
+                    game_data.game.get_result().map(|result| result.reason).unwrap_or_default()
+                ),
+                CoreGameState::Draw(_) => format!(
+                    "DRAW! {}.",
+                    game_data.game.get_result().map(|result| result.reason).unwrap_or_default()
+                ),
+                CoreGameState::Resigned(_) => format!(
+                    "RESIGNATION! {}.",
+                    game_data.game.get_result().map(|result| result.reason).unwrap_or_default()
+                ),
+                CoreGameState::Exploded(_) => format!(
+                    "BOOM! {}.",
+                    game_data.game.get_result().map(|result| result.reason).unwrap_or_default()
+                ),
+                CoreGameState::KingCaptured(_) => format!(
+                    "KING TAKEN! {}.",
+                    game_data.game.get_result().map(|result| result.reason).unwrap_or_default()
+                ),
+                _ => return,
+            };
+            
+            info!("{}", msg);
+            return;
+        }
+        _ => {}
+    }
+    
+    // Additional check: If in check and no legal moves are available, it's checkmate
+    // This catches checkmate situations immediately without waiting for a move attempt
+    if matches!(game_data.game.game_state, CoreGameState::Check(_)) {
+        // Check all pieces of the current player to see if ANY legal move exists
+        let has_legal_move = !game_data.game.all_legal_moves().is_empty();
+        
+        // If no legal moves exist while in check, it's checkmate
+        if !has_legal_move {
+            next_state.set(GameState::GameOver);
+            let winner_name = match game_data.game.current_player {
+                ChessColor::White => "Black", // White is checkmated, Black wins
+                ChessColor::Black => "White", // Black is checkmated, White wins
+            };
+            info!("CHECKMATE detected! {} wins!", winner_name);
+        }
+    }
+}
+
+pub(crate) fn spawn_game_over_screen(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    timer: Option<Res<GameTimer>>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+) {
+    use hex_chess_core::GameState as CoreGameState;
+    
+    info!("Spawning game over screen...");
+    
+    // Determine the result message
+    let (title, subtitle, reason, celebration) = match game_data.game.game_state {
+        CoreGameState::Checkmate(winner) => {
+            let winner_name = match winner {
+                ChessColor::White => "White",
+                ChessColor::Black => "Black",
+            };
+            (
+                "CHECKMATE!".to_string(),
+                format!("{} Wins!", winner_name),
+                "by checkmate".to_string(),
+                Some(Celebration::Win(winner)),
+            )
+        }
+        CoreGameState::Stalemate => {
+            use hex_chess_core::StalemateRule;
+
+            let winner = match game_data.game.current_player {
+                ChessColor::White => ChessColor::Black,
+                ChessColor::Black => ChessColor::White,
+            };
+            let winner_name = match winner {
+                ChessColor::White => "White",
+                ChessColor::Black => "Black",
+            };
+            let (subtitle, celebration) = match game_data.variant().stalemate_rule {
+                StalemateRule::Draw => ("Draw".to_string(), Celebration::Draw),
+                StalemateRule::PartialWin => (format!("{} scores ¾ – ¼", winner_name), Celebration::Win(winner)),
+                StalemateRule::Win => (format!("{} Wins!", winner_name), Celebration::Win(winner)),
+            };
+            ("STALEMATE!".to_string(), subtitle, "no legal moves available".to_string(), Some(celebration))
+        }
+        CoreGameState::Draw(reason) => {
+            let reason = match reason {
+                hex_chess_core::DrawReason::Repetition => "by repetition",
+                hex_chess_core::DrawReason::InsufficientMaterial => "by insufficient material",
+                hex_chess_core::DrawReason::Agreement => "by agreement",
+                hex_chess_core::DrawReason::NoProgress => "by the fifty-move rule",
+            };
+            ("DRAW!".to_string(), "Game Over".to_string(), reason.to_string(), Some(Celebration::Draw))
+        }
+        CoreGameState::Resigned(loser) => {
+            let (winner, winner_name, loser_name) = match loser {
+                ChessColor::White => (ChessColor::Black, "Black", "White"),
+                ChessColor::Black => (ChessColor::White, "White", "Black"),
+            };
+            (
+                "RESIGNATION".to_string(),
+                format!("{} Wins!", winner_name),
+                format!("{} resigned", loser_name),
+                Some(Celebration::Win(winner)),
+            )
+        }
+        CoreGameState::Exploded(loser) => {
+            let (winner, winner_name, loser_name) = match loser {
+                ChessColor::White => (ChessColor::Black, "Black", "White"),
+                ChessColor::Black => (ChessColor::White, "White", "Black"),
+            };
+            (
+                "KING EXPLODED!".to_string(),
+                format!("{} Wins!", winner_name),
+                format!("{}'s king was caught in a blast", loser_name),
+                Some(Celebration::Win(winner)),
+            )
+        }
+        CoreGameState::KingCaptured(loser) => {
+            let (winner, winner_name, loser_name) = match loser {
+                ChessColor::White => (ChessColor::Black, "Black", "White"),
+                ChessColor::Black => (ChessColor::White, "White", "Black"),
+            };
+            (
+                "KING TAKEN!".to_string(),
+                format!("{} Wins!", winner_name),
+                format!("{}'s king was captured in the dark", loser_name),
+                Some(Celebration::Win(winner)),
+            )
+        }
+        _ => {
+            // Check if it was a timeout
+            match timer.as_ref().and_then(|timer| timer.timed_out.map(|loser| (timer, loser))) {
+                Some((timer, loser)) => {
+                    let (winner, winner_name, loser_name) = match loser {
+                        ChessColor::White => (ChessColor::Black, "Black", "White"),
+                        ChessColor::Black => (ChessColor::White, "White", "Black"),
+                    };
+                    let left = match loser {
+                        ChessColor::White => timer.white_time,
+                        ChessColor::Black => timer.black_time,
+                    };
+                    let reason = if left > 0.0 {
+                        format!("{} went over the time allowed for a move", loser_name)
+                    } else {
+                        format!("{} ran out of time", loser_name)
+                    };
+                    ("TIME'S UP!".to_string(), format!("{} Wins!", winner_name), reason, Some(Celebration::Win(winner)))
+                }
+                None => ("GAME OVER".to_string(), "".to_string(), "".to_string(), None),
+            }
+        }
+    };
+    
+    // Full screen overlay
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            // A win only dims the board so the celebration shows; draws get a cool, heavier veil
+            background_color: match celebration {
+                Some(Celebration::Win(_)) => Color::srgba(0.0, 0.0, 0.0, 0.6),
+                Some(Celebration::Draw) => Color::srgba(0.02, 0.04, 0.1, 0.85),
+                None => Color::srgba(0.0, 0.0, 0.0, 0.85),
+            }.into(),
+            z_index: ZIndex::Global(2000),
+            ..default()
+        },
+        GameOverUI,
+    )).with_children(|parent| {
+        // Title
+        parent.spawn(TextBundle::from_section(
+            title,
+            TextStyle {
+                font_size: 56.0,
+                color: match celebration {
+                    Some(Celebration::Draw) => Color::srgb(0.6, 0.8, 1.0),
+                    _ => Color::srgb(1.0, 0.9, 0.2),
+                },
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(20.0)),
+            ..default()
+        }));
+        
+        // Subtitle (winner)
+        if !subtitle.is_empty() {
+            parent.spawn(TextBundle::from_section(
+                subtitle,
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::srgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::bottom(Val::Px(15.0)),
+                ..default()
+            }));
+        }
+        
+        // Reason
+        if !reason.is_empty() {
+            parent.spawn(TextBundle::from_section(
+                reason,
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::srgb(0.7, 0.7, 0.7),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::bottom(Val::Px(40.0)),
+                ..default()
+            }));
+        }
+        
+        // Post-game report
+        if !game_data.game.move_history.is_empty() {
+            let think_time = |color| ChartSeries {
+                color: match color {
+                    ChessColor::White => Color::srgb(0.95, 0.95, 0.95),
+                    ChessColor::Black => Color::srgb(0.4, 0.6, 1.0),
+                },
+                values: game_data.game.clock_history(color)
+                    .into_iter()
+                    .map(|clock| clock.map(|clock| clock.think_ms as f32 / 1000.0))
+                    .collect(),
+            };
+            let material = ChartSeries {
+                color: Color::srgb(1.0, 0.9, 0.2),
+                values: game_data.game.material_history()
+                    .into_iter()
+                    .map(|balance| Some(balance as f32 / 100.0))
+                    .collect(),
+            };
+            let think_series = [think_time(ChessColor::White), think_time(ChessColor::Black)];
+            let timed = think_series.iter().any(|series| series.values.iter().any(Option::is_some));
+            
+            parent.spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+                ..default()
+            }).with_children(|row| {
+                if timed {
+                    spawn_chart(row, "Think time per move (s), White / Black", &think_series);
+                }
+                spawn_chart(row, "Material (pawns, White ahead is up)", &[material]);
+            });
+        }
+        
+        // New Game button hint
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} for New Game", config.keys.name(Action::Confirm)),
+            TextStyle {
+                font_size: 24.0,
+                color: Color::srgb(0.4, 0.8, 0.4),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(10.0)),
+            ..default()
+        }));
+        
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} to Return to Menu", config.keys.name(Action::Back)),
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ));
+    });
+    
+    if let Some(celebration) = celebration.filter(|_| !config.reduced_motion()) {
+        spawn_celebration(&mut commands, celebration, time.elapsed().as_nanos() as u64);
+    }
+}
+
+/// Draw a minimal point chart: a zero line, a dot per reading and the value range
+fn spawn_chart(parent: &mut ChildBuilder, title: &str, series: &[ChartSeries]) {
+    let steps = series.iter().map(|series| series.values.len()).max().unwrap_or(0);
+    let (low, high) = series
+        .iter()
+        .flat_map(|series| series.values.iter().flatten())
+        .fold((0.0_f32, 0.0_f32), |(low, high), &value| (low.min(value), high.max(value)));
+    let high = if high > low { high } else { low + 1.0 };
+    let y_of = |value: f32| CHART_SIZE.y * (high - value) / (high - low);
+    let x_of = |step: usize| if steps > 1 {
+        CHART_SIZE.x * step as f32 / (steps - 1) as f32
+    } else {
+        CHART_SIZE.x / 2.0
+    };
+    let marker = |left: f32, top: f32, width: Val, height: f32, color: Color| NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(left),
+            top: Val::Px(top),
+            width,
+            height: Val::Px(height),
+            ..default()
+        },
+        background_color: color.into(),
+        ..default()
+    };
+    
+    parent.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            margin: UiRect::horizontal(Val::Px(15.0)),
+            ..default()
+        },
+        ..default()
+    }).with_children(|column| {
+        column.spawn(TextBundle::from_section(
+            title,
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.8, 0.8, 0.8),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        }));
+        
+        column.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(CHART_SIZE.x),
+                height: Val::Px(CHART_SIZE.y),
+                ..default()
+            },
+            background_color: Color::srgba(1.0, 1.0, 1.0, 0.06).into(),
+            ..default()
+        }).with_children(|plot| {
+            plot.spawn(marker(0.0, y_of(0.0) - 0.5, Val::Percent(100.0), 1.0, Color::srgba(1.0, 1.0, 1.0, 0.3)));
+            for line in series {
+                for (step, value) in line.values.iter().enumerate() {
+                    if let Some(value) = value {
+                        plot.spawn(marker(x_of(step) - 2.0, y_of(*value) - 2.0, Val::Px(4.0), 4.0, line.color));
+                    }
+                }
+            }
+        });
+        
+        column.spawn(TextBundle::from_section(
+            format!("{:.1} to {:.1}", low, high),
+            TextStyle {
+                font_size: 11.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ));
+    });
+}
+
+/// Pick a pseudo-random number in [0, 1) from an xorshift state
+fn next_unit(state: &mut u64) -> f32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    (*state >> 40) as f32 / (1u64 << 24) as f32
+}
+
+fn spawn_celebration(commands: &mut Commands, celebration: Celebration, seed: u64) {
+    let mut rng = seed | 1;
+    
+    let (count, palette): (usize, &[Color]) = match celebration {
+        Celebration::Win(ChessColor::White) => (CONFETTI_COUNT, &[
+            Color::srgb(1.0, 0.9, 0.2),
+            Color::srgb(1.0, 1.0, 1.0),
+            Color::srgb(0.95, 0.4, 0.3),
+            Color::srgb(0.3, 0.7, 1.0),
+        ]),
+        Celebration::Win(ChessColor::Black) => (CONFETTI_COUNT, &[
+            Color::srgb(1.0, 0.9, 0.2),
+            Color::srgb(0.25, 0.25, 0.3),
+            Color::srgb(0.7, 0.3, 0.9),
+            Color::srgb(0.3, 0.9, 0.5),
+        ]),
+        Celebration::Draw => (DRIFT_COUNT, &[
+            Color::srgb(0.6, 0.7, 0.8),
+            Color::srgb(0.8, 0.85, 0.9),
+        ]),
+    };
+    
+    for index in 0..count {
+        let particle = match celebration {
+            // Confetti bursts up and out from just above the title, then falls
+            Celebration::Win(_) => {
+                let angle = std::f32::consts::PI * (0.1 + 0.8 * next_unit(&mut rng));
+                let speed = 25.0 + 45.0 * next_unit(&mut rng);
+                CelebrationParticle {
+                    position: Vec2::new(50.0, 30.0),
+                    velocity: Vec2::new(angle.cos() * speed, -angle.sin() * speed),
+                    gravity: 55.0,
+                    life: 2.5 + next_unit(&mut rng),
+                    age: 0.0,
+                }
+            }
+            // Draws get a slow, quiet drift from the top of the screen
+            Celebration::Draw => CelebrationParticle {
+                position: Vec2::new(100.0 * next_unit(&mut rng), -10.0 * next_unit(&mut rng)),
+                velocity: Vec2::new(4.0 * next_unit(&mut rng) - 2.0, 8.0 + 6.0 * next_unit(&mut rng)),
+                gravity: 0.0,
+                life: 8.0 + 4.0 * next_unit(&mut rng),
+                age: 0.0,
+            },
+        };
+        let size = 5.0 + 5.0 * next_unit(&mut rng);
+        
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(particle.position.x),
+                    top: Val::Percent(particle.position.y),
+                    width: Val::Px(size),
+                    height: Val::Px(size),
+                    ..default()
+                },
+                background_color: palette[index % palette.len()].into(),
+                z_index: ZIndex::Global(2001),
+                ..default()
+            },
+            particle,
+            GameOverUI,
+        ));
+    }
+}
+
+pub(crate) fn update_celebration(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut CelebrationParticle, &mut Style, &mut BackgroundColor)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut particle, mut style, mut background) in particles.iter_mut() {
+        particle.age += delta;
+        if particle.age >= particle.life {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        
+        particle.velocity.y += particle.gravity * delta;
+        let step = particle.velocity * delta;
+        particle.position += step;
+        style.left = Val::Percent(particle.position.x);
+        style.top = Val::Percent(particle.position.y);
+        
+        // Fade out over the last second
+        background.0.set_alpha((particle.life - particle.age).min(1.0));
+    }
+}
+
+pub(crate) fn cleanup_game_over_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<GameOverUI>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    
+    info!("Cleaned up game over screen");
+}
+
+pub(crate) fn handle_game_over_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    config: Res<GameConfig>,
+) {
+    // Start new game with Space, once the player confirms it
+    if config.keys.just_pressed(&keyboard_input, Action::Confirm) {
+        commands.insert_resource(Confirmation::NewGame);
+    }
+    
+    // Return to menu with ESC
+    if config.keys.just_pressed(&keyboard_input, Action::Back) {
+        next_state.set(GameState::Menu);
+        info!("Returning to menu from game over");
+    }
+}
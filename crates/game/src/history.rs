@@ -0,0 +1,370 @@
+use bevy::prelude::*;
+use hex_chess_core::SampleGame;
+use serde::{Deserialize, Serialize};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+use crate::{GameData, GameState};
+use crate::drill::ActiveDrill;
+use crate::platform::{format_timestamp, now_ms, read_storage, save_file, write_storage};
+use crate::replay::SampleReplay;
+use crate::settings::{Action, GameConfig};
+use crate::toast::ShowToast;
+
+/// The archive of finished games and the screen that browses it
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(GameHistory::load())
+            .add_systems(Startup, listen_for_unload)
+            .add_systems(Update, track_unfinished_game)
+            .add_systems(OnEnter(GameState::History), spawn_history_screen)
+            .add_systems(OnExit(GameState::History), cleanup_history_screen)
+            .add_systems(Update, (
+                handle_history_input,
+                refresh_history_screen,
+            ).chain().run_if(in_state(GameState::History)))
+            .add_systems(OnEnter(GameState::GameOver), archive_finished_game);
+    }
+}
+
+/// Storage key (web) or file name (native) for the archive of finished games
+const HISTORY_KEY: &str = "hex-chess-history";
+
+/// Storage key for a game left unfinished when the page closed, archived on the next visit
+const AUTOSAVE_KEY: &str = "hex-chess-autosave";
+
+/// Finished games kept in the archive; the oldest go first
+const HISTORY_LIMIT: usize = 200;
+
+/// A finished game kept in the local archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedGame {
+    /// Milliseconds since the Unix epoch when the game ended
+    pub finished_at_ms: u64,
+    pub result: String,
+    pub game: SampleGame,
+}
+
+impl ArchivedGame {
+    fn new(game: &hex_chess_core::Game, result: String) -> Self {
+        let finished_at_ms = now_ms();
+        let title = format!("{}, {}", game.variant.name, format_timestamp(finished_at_ms));
+        let id = format!("local-{}", finished_at_ms);
+        Self {
+            finished_at_ms,
+            game: SampleGame::from_game(game, &id, &title, &result),
+            result,
+        }
+    }
+}
+
+/// Every finished game played on this device, newest first, and the one picked on the history screen
+#[derive(Resource, Default)]
+pub struct GameHistory {
+    pub games: Vec<ArchivedGame>,
+    pub selected: usize,
+}
+
+impl GameHistory {
+    /// Load the archive, empty if there is none or it can't be read, along with any
+    /// game the page was closed on last time
+    pub fn load() -> Self {
+        let games = read_storage(HISTORY_KEY)
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        let mut history = Self { games, selected: 0 };
+        
+        if let Some(unfinished) = read_storage(AUTOSAVE_KEY).and_then(|data| serde_json::from_str(&data).ok()) {
+            history.games.insert(0, unfinished);
+            history.games.truncate(HISTORY_LIMIT);
+            history.save();
+            write_storage(AUTOSAVE_KEY, "");
+        }
+        history
+    }
+    
+    pub fn save(&self) {
+        match serde_json::to_string(&self.games) {
+            Ok(data) => write_storage(HISTORY_KEY, &data),
+            Err(e) => {
+                info!("Could not save game history: {}", e);
+            }
+        }
+    }
+    
+    /// Add a finished game at the front of the archive
+    pub fn record(&mut self, game: &hex_chess_core::Game) {
+        let result = game.get_result().map(|result| result.reason).unwrap_or_else(|| "Game over".to_string());
+        self.games.insert(0, ArchivedGame::new(game, result));
+        self.games.truncate(HISTORY_LIMIT);
+        self.selected = 0;
+        self.save();
+    }
+    
+    pub fn selected(&self) -> Option<&ArchivedGame> {
+        self.games.get(self.selected)
+    }
+    
+    /// Drop the selected game from the archive
+    pub fn delete_selected(&mut self) {
+        if self.selected < self.games.len() {
+            self.games.remove(self.selected);
+            self.selected = self.selected.min(self.games.len().saturating_sub(1));
+            self.save();
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HistoryScreen;
+
+/// Archived games listed on the history screen at once
+const HISTORY_ROWS: usize = 12;
+
+pub(crate) fn spawn_history_screen(
+    mut commands: Commands,
+    history: Res<GameHistory>,
+    config: Res<GameConfig>,
+) {
+    build_history_screen(&mut commands, &history, &config);
+}
+
+/// Rebuild the list when a game is picked, deleted or added
+pub(crate) fn refresh_history_screen(
+    mut commands: Commands,
+    history: Res<GameHistory>,
+    config: Res<GameConfig>,
+    query: Query<Entity, With<HistoryScreen>>,
+) {
+    if !history.is_changed() {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    build_history_screen(&mut commands, &history, &config);
+}
+
+fn build_history_screen(
+    commands: &mut Commands,
+    history: &GameHistory,
+    config: &GameConfig,
+) {
+    let keys = &config.keys;
+    
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.95).into(),
+            z_index: ZIndex::Global(1000),
+            ..default()
+        },
+        HistoryScreen,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "Game History",
+            TextStyle {
+                font_size: 32.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(20.0)),
+            ..default()
+        }));
+        
+        if history.games.is_empty() {
+            parent.spawn(TextBundle::from_section(
+                "No finished games yet",
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::srgb(0.6, 0.6, 0.6),
+                    ..default()
+                },
+            ));
+        }
+        
+        // A window of rows that keeps the selected game in view
+        let first = history.selected.saturating_sub(HISTORY_ROWS / 2).min(history.games.len().saturating_sub(HISTORY_ROWS));
+        for (index, archived) in history.games.iter().enumerate().skip(first).take(HISTORY_ROWS) {
+            let selected = index == history.selected;
+            parent.spawn(TextBundle::from_section(
+                format!(
+                    "{} {} - {} ({} moves)",
+                    if selected { ">" } else { " " },
+                    archived.game.title,
+                    archived.result,
+                    archived.game.moves.len(),
+                ),
+                TextStyle {
+                    font_size: 16.0,
+                    color: if selected { Color::srgb(1.0, 0.9, 0.2) } else { Color::srgb(0.8, 0.8, 0.8) },
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::bottom(Val::Px(6.0)),
+                ..default()
+            }));
+        }
+        
+        parent.spawn(TextBundle::from_section(
+            format!(
+                "UP/DOWN to pick, {} to replay, {} to export, {} to delete, {} for the menu",
+                keys.name(Action::Confirm),
+                keys.name(Action::ExportGame),
+                keys.name(Action::DeleteGame),
+                keys.name(Action::Back),
+            ),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::top(Val::Px(20.0)),
+            ..default()
+        }));
+    });
+}
+
+pub(crate) fn cleanup_history_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<HistoryScreen>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub(crate) fn handle_history_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut history: ResMut<GameHistory>,
+    config: Res<GameConfig>,
+) {
+    let keys = &config.keys;
+    
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) && history.selected > 0 {
+        history.selected -= 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) && history.selected + 1 < history.games.len() {
+        history.selected += 1;
+    }
+    
+    if keys.just_pressed(&keyboard_input, Action::DeleteGame) {
+        history.delete_selected();
+    }
+    
+    if keys.just_pressed(&keyboard_input, Action::ExportGame) {
+        if let Some(archived) = history.selected() {
+            match serde_json::to_string_pretty(&archived.game) {
+                Ok(data) => save_file(&format!("hex-chess-{}.json", archived.finished_at_ms), &data),
+                Err(e) => {
+                    commands.add(ShowToast::error(format!("Could not export game: {}", e)));
+                }
+            }
+        }
+    }
+    
+    // Open the game in the replay viewer
+    if keys.just_pressed(&keyboard_input, Action::Confirm) {
+        if let Some(archived) = history.selected() {
+            commands.insert_resource(SampleReplay::new(archived.game.clone()));
+            next_state.set(GameState::Playing);
+        }
+    }
+    
+    if keys.just_pressed(&keyboard_input, Action::Back) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// Keep every game played to its end, but not replays or drills
+pub(crate) fn archive_finished_game(
+    game_data: Res<GameData>,
+    replay: Option<Res<SampleReplay>>,
+    drill: Option<Res<ActiveDrill>>,
+    mut history: ResMut<GameHistory>,
+) {
+    if replay.is_some() || drill.is_some() || game_data.game.move_history.is_empty() {
+        return;
+    }
+    history.record(&game_data.game);
+}
+
+/// The game in progress, archived and ready to store should the page close on it;
+/// None when there's nothing to lose
+static UNFINISHED_GAME: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+/// Keep `UNFINISHED_GAME` up to date: a game of our own with moves played and no result
+/// yet, not a replay or a drill
+pub(crate) fn track_unfinished_game(
+    game_data: Option<Res<GameData>>,
+    replay: Option<Res<SampleReplay>>,
+    drill: Option<Res<ActiveDrill>>,
+) {
+    if !game_data.as_ref().is_some_and(|game_data| game_data.is_changed()) {
+        return;
+    }
+    let unfinished = game_data
+        .filter(|game_data| !game_data.game.move_history.is_empty() && game_data.game.get_result().is_none())
+        .filter(|_| replay.is_none() && drill.is_none())
+        .and_then(|game_data| serde_json::to_string(&ArchivedGame::new(&game_data.game, "Left unfinished".to_string())).ok());
+    let Ok(mut saved) = UNFINISHED_GAME.lock() else {
+        return;
+    };
+    
+    // A game that was stored on a page kept in the browser's cache, and has since ended, mustn't come back
+    if saved.is_some() && unfinished.is_none() {
+        write_storage(AUTOSAVE_KEY, "");
+    }
+    *saved = unfinished;
+}
+
+/// Ask before closing the page on a game in progress, and store the game if the player goes anyway
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn listen_for_unload() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    
+    let on_before_unload = Closure::<dyn FnMut(web_sys::BeforeUnloadEvent)>::new(|event: web_sys::BeforeUnloadEvent| {
+        if UNFINISHED_GAME.lock().is_ok_and(|saved| saved.is_some()) {
+            // Browsers show their own wording; the return value only has to be set
+            event.prevent_default();
+            event.set_return_value("A game is in progress");
+        }
+    });
+    let _ = window.add_event_listener_with_callback("beforeunload", on_before_unload.as_ref().unchecked_ref());
+    on_before_unload.forget();
+    
+    // pagehide fires once the player has chosen to leave, and on mobile when the tab is discarded
+    let on_page_hide = Closure::<dyn FnMut(web_sys::Event)>::new(|_: web_sys::Event| {
+        if let Ok(saved) = UNFINISHED_GAME.lock() {
+            if let Some(data) = saved.as_deref() {
+                write_storage(AUTOSAVE_KEY, data);
+            }
+        }
+    });
+    let _ = window.add_event_listener_with_callback("pagehide", on_page_hide.as_ref().unchecked_ref());
+    on_page_hide.forget();
+}
+
+/// Native windows close through Bevy, which has no way to ask first
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn listen_for_unload() {}
@@ -0,0 +1,710 @@
+use bevy::prelude::*;
+use hex_chess_core::{piece_letter, Color as ChessColor};
+
+use crate::{CapturedPieces, DropSelection, GameData, GameState};
+use crate::drill::ActiveDrill;
+use crate::input::{Confirmation, PauseMenu, handle_pause_input};
+use crate::menu::RulesUI;
+use crate::settings::{Action, GameConfig};
+
+/// Clocks, captured pieces, the check warning and the overlays drawn over a game
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CapturedPieces>()
+            .add_systems(Startup, spawn_hud)
+            .add_systems(OnEnter(GameState::Playing), init_game_timer)
+            .add_systems(Update, (
+                update_ui,
+                update_timer.run_if(not(resource_exists::<PauseMenu>).and_then(not(resource_exists::<ActiveDrill>))),
+                update_timer_display,
+                update_captured_pieces_display,
+                update_check_warning,
+                update_pause_overlay.after(handle_pause_input),
+            ).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, update_confirmation_overlay);
+    }
+}
+
+#[derive(Resource)]
+pub struct GameTimer {
+    pub white_time: f32,  // seconds remaining
+    pub black_time: f32,
+    pub white_total: f32, // configured total time
+    pub black_total: f32,
+    pub turn_elapsed: f32, // seconds spent on the current move
+    pub move_limit: Option<f32>, // seconds allowed for each move, on top of the main clock
+    pub timed_out: Option<ChessColor>, // who lost on time, on either clock
+    pub paused: bool,
+}
+
+impl GameTimer {
+    pub fn new(minutes: f32, move_limit: Option<f32>) -> Self {
+        let seconds = minutes * 60.0;
+        Self {
+            white_time: seconds,
+            black_time: seconds,
+            white_total: seconds,
+            black_total: seconds,
+            turn_elapsed: 0.0,
+            move_limit,
+            timed_out: None,
+            paused: false,
+        }
+    }
+    
+    pub fn reset(&mut self, minutes: f32) {
+        let seconds = minutes * 60.0;
+        self.white_time = seconds;
+        self.black_time = seconds;
+        self.white_total = seconds;
+        self.black_total = seconds;
+        self.turn_elapsed = 0.0;
+        self.timed_out = None;
+        self.paused = false;
+    }
+    
+    /// Seconds left for the current move, if moves are capped
+    pub fn move_time_left(&self) -> Option<f32> {
+        self.move_limit.map(|limit| (limit - self.turn_elapsed).max(0.0))
+    }
+    
+    pub fn format_time(seconds: f32) -> String {
+        let mins = (seconds / 60.0).floor() as i32;
+        let secs = (seconds % 60.0).floor() as i32;
+        format!("{:02}:{:02}", mins, secs)
+    }
+}
+
+#[derive(Component)]
+pub struct GameUI;
+
+#[derive(Component)]
+pub struct CapturedPiecesUI {
+    pub color: ChessColor,
+}
+
+#[derive(Component)]
+pub struct TimerUI {
+    pub color: ChessColor,
+}
+
+#[derive(Component)]
+pub struct CheckWarningUI;
+
+#[derive(Component)]
+pub struct PauseOverlay;
+
+#[derive(Component)]
+pub struct ConfirmationOverlay;
+
+/// The game HUD and the trays of captured pieces
+fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_ui(&mut commands, &asset_server);
+    spawn_captured_pieces_areas(&mut commands);
+}
+
+fn spawn_ui(commands: &mut Commands, _asset_server: &Res<AssetServer>) {
+    // Main game UI (top center) - smaller variant name
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Percent(50.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            background_color: bevy::prelude::Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..default()
+        },
+        GameUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "Gliński's Chess",
+            TextStyle {
+                font_size: 16.0,
+                color: bevy::prelude::Color::srgb(0.8, 0.8, 0.8),
+                ..default()
+            },
+        ));
+    });
+    
+    // Black timer (top left)
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            background_color: bevy::prelude::Color::srgba(0.15, 0.15, 0.15, 0.85).into(),
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "Black",
+            TextStyle {
+                font_size: 14.0,
+                color: bevy::prelude::Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ));
+        parent.spawn((
+            TextBundle::from_section(
+                "10:00",
+                TextStyle {
+                    font_size: 24.0,
+                    color: bevy::prelude::Color::WHITE,
+                    ..default()
+                },
+            ),
+            TimerUI { color: ChessColor::Black },
+        ));
+    });
+    
+    // White timer (bottom right)
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(60.0),
+                right: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            background_color: bevy::prelude::Color::srgba(0.15, 0.15, 0.15, 0.85).into(),
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "White",
+            TextStyle {
+                font_size: 14.0,
+                color: bevy::prelude::Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ));
+        parent.spawn((
+            TextBundle::from_section(
+                "10:00",
+                TextStyle {
+                    font_size: 24.0,
+                    color: bevy::prelude::Color::WHITE,
+                    ..default()
+                },
+            ),
+            TimerUI { color: ChessColor::White },
+        ));
+    });
+}
+
+pub(crate) fn update_ui(
+    game_data: Res<GameData>,
+    mut ui_query: Query<&mut Text, With<GameUI>>,
+    mut rules_query: Query<&mut Text, (With<RulesUI>, Without<GameUI>)>,
+    drop_selection: Res<DropSelection>,
+    config: Res<GameConfig>,
+) {
+    if let Ok(mut text) = ui_query.get_single_mut() {
+        let variant = game_data.variant();
+        let current_player = match game_data.game.current_player {
+            ChessColor::White => "White",
+            ChessColor::Black => "Black",
+        };
+        
+        let game = &game_data.game;
+        let mut ui_text = format!("{} - Move {}, {} to move", variant.name, game.fullmove_number, current_player);
+        
+        // Count down to the no-progress draw once it's near
+        if let Some(limit) = variant.draw_rules.halfmove_limit {
+            if game.halfmove_clock * 2 >= limit {
+                ui_text = format!("{} | {}/{} half-moves without a capture or pawn move", ui_text, game.halfmove_clock, limit);
+            }
+        }
+        
+        // Add piece selection information
+        if let Some(selected_coord) = game_data.selected_piece {
+            if let Some(piece) = game_data.game.board.get_piece(selected_coord) {
+                let piece_color = match piece.color {
+                    ChessColor::White => "White",
+                    ChessColor::Black => "Black",
+                };
+                let piece_type = piece.piece_type.name();
+                let move_count = game_data.valid_moves.len();
+                ui_text = format!("{} | Selected: {} {} at {:?} | {} valid moves", 
+                    ui_text, piece_color, piece_type, selected_coord, move_count);
+            }
+        }
+        
+        // Pieces in hand, and the one about to be dropped
+        if variant.allows_drops() {
+            let hand = |color| {
+                let letters: String = game_data.game.reserves.get(color).iter().map(|&piece_type| piece_letter(piece_type)).collect();
+                if letters.is_empty() { "-".to_string() } else { letters }
+            };
+            ui_text = format!("{} | In hand: White {} / Black {}", ui_text, hand(ChessColor::White), hand(ChessColor::Black));
+            match drop_selection.piece {
+                Some(piece_type) => {
+                    ui_text = format!("{} | Dropping {}: click a highlighted cell ({} for next)",
+                        ui_text, piece_type.name(), config.keys.name(Action::NextDrop));
+                }
+                None if !game_data.game.reserves.get(game_data.game.current_player).is_empty() => {
+                    ui_text = format!("{} | {} to drop", ui_text, config.keys.name(Action::NextDrop));
+                }
+                None => {}
+            }
+        }
+        
+        // The piece a selected pawn would promote to
+        if game_data.can_promote() {
+            let promotion = game_data.promotion_piece();
+            ui_text = format!("{} | Promotes to {} ({}) ({} to change)",
+                ui_text, promotion.name(), piece_letter(promotion), config.keys.name(Action::Promotion));
+        }
+        
+        text.sections[0].value = ui_text;
+    }
+    
+    // Update rules UI
+    if let Ok(mut rules_text) = rules_query.get_single_mut() {
+        let variant = game_data.variant();
+        let mut rules_text_content = format!("{}\n{}\n\n", variant.name, variant.description);
+        
+        rules_text_content.push_str("Gliński's Chess Rules:\n\n");
+        rules_text_content.push_str("Piece Movement:\n");
+        rules_text_content.push_str("• Pawn: Moves straight forward, captures diagonally forward (2 directions)\n");
+        rules_text_content.push_str("• Rook: Moves in 6 directions (rank, file, diagonals)\n");
+        rules_text_content.push_str("• Bishop: Moves in 6 diagonal directions\n");
+        rules_text_content.push_str("• Knight: Leaps 3 steps (L-shaped, 12 possible moves)\n");
+        rules_text_content.push_str("• Queen: Rook + Bishop (9 directions)\n");
+        rules_text_content.push_str("• King: One step to any of the 12 adjacent or diagonal hexes\n");
+        
+        rules_text_content.push_str("\nSpecial Rules:\n");
+        if variant.has_en_passant() {
+            rules_text_content.push_str("• Pawns may advance two cells from their starting cells\n");
+            rules_text_content.push_str("• En Passant: a pawn that just did can be taken as if it had moved one\n");
+        }
+        if variant.allows_drops() {
+            rules_text_content.push_str("• Captured pieces join your hand; instead of moving, drop one on any empty cell\n");
+            rules_text_content.push_str("• Pawns can't be dropped where they could never move forward\n");
+        }
+        if variant.has_atomic_captures() {
+            rules_text_content.push_str("• Captures explode: the capturer and every non-pawn next to the target are removed\n");
+            rules_text_content.push_str("• Kings can't capture, and blowing up the enemy king wins\n");
+        }
+        if variant.has_fog_of_war() {
+            rules_text_content.push_str("• You only see cells your pieces stand on or can move to\n");
+            rules_text_content.push_str("• There is no check: the king can be captured, which wins the game\n");
+        }
+        if variant.has_castling() {
+            rules_text_content.push_str("• Castling: an unmoved king steps two cells toward an unmoved rook, which jumps over it\n");
+        } else {
+            rules_text_content.push_str("• No Castling\n");
+        }
+        rules_text_content.push_str("• Pawns promote at opposite border\n");
+        
+        // Add controls info to rules UI
+        rules_text_content.push_str("\n\nControls:\n");
+        rules_text_content.push_str("• Click to select and move pieces\n");
+        rules_text_content.push_str("• Mouse wheel or +/- to zoom\n");
+        rules_text_content.push_str("• Arrow keys to pan camera\n");
+        rules_text_content.push_str("• R to reset camera\n");
+        rules_text_content.push_str("• M to toggle menu\n");
+        
+        rules_text.sections[0].value = rules_text_content;
+    }
+}
+
+pub(crate) fn update_timer(
+    mut timer: ResMut<GameTimer>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if timer.paused {
+        return;
+    }
+    
+    let delta = time.delta_seconds();
+    timer.turn_elapsed += delta;
+    
+    // Decrement current player's time
+    match game_data.game.current_player {
+        ChessColor::White => {
+            timer.white_time -= delta;
+            if timer.white_time <= 0.0 {
+                timer.white_time = 0.0;
+                timer.paused = true;
+                timer.timed_out = Some(ChessColor::White);
+                // Black wins by timeout
+                next_state.set(GameState::GameOver);
+                info!("White ran out of time! Black wins!");
+            }
+        }
+        ChessColor::Black => {
+            timer.black_time -= delta;
+            if timer.black_time <= 0.0 {
+                timer.black_time = 0.0;
+                timer.paused = true;
+                timer.timed_out = Some(ChessColor::Black);
+                // White wins by timeout
+                next_state.set(GameState::GameOver);
+                info!("Black ran out of time! White wins!");
+            }
+        }
+    }
+    
+    // A move over the per-move limit loses too, whatever is left on the main clock
+    if timer.timed_out.is_none() && timer.move_time_left() == Some(0.0) {
+        timer.paused = true;
+        timer.timed_out = Some(game_data.game.current_player);
+        next_state.set(GameState::GameOver);
+    }
+}
+
+pub(crate) fn update_timer_display(
+    timer: Res<GameTimer>,
+    game_data: Res<GameData>,
+    mut query: Query<(&mut Text, &TimerUI)>,
+) {
+    if !timer.is_changed() {
+        return;
+    }
+    
+    for (mut text, timer_ui) in query.iter_mut() {
+        let time = match timer_ui.color {
+            ChessColor::White => timer.white_time,
+            ChessColor::Black => timer.black_time,
+        };
+        // The side to move also sees what is left of this move's limit
+        text.sections[0].value = match timer.move_time_left() {
+            Some(left) if timer_ui.color == game_data.game.current_player => {
+                format!("{} ({}s)", GameTimer::format_time(time), left.ceil() as i32)
+            }
+            _ => GameTimer::format_time(time),
+        };
+    }
+}
+
+pub(crate) fn update_check_warning(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    warning_query: Query<Entity, With<CheckWarningUI>>,
+) {
+    use hex_chess_core::GameState as CoreGameState;
+    
+    // Clean up existing warnings
+    for entity in warning_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    
+    // Show check warning if in check
+    if let CoreGameState::Check(color) = game_data.game.game_state {
+        let color_name = match color {
+            ChessColor::White => "White",
+            ChessColor::Black => "Black",
+        };
+        
+        // Semi-transparent overlay
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.8, 0.0, 0.0, 0.15).into(),
+                z_index: ZIndex::Global(500),
+                ..default()
+            },
+            CheckWarningUI,
+        )).with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("CHECK! - {} King Under Attack!", color_name),
+                TextStyle {
+                    font_size: 32.0,
+                    color: Color::srgb(1.0, 0.2, 0.2),
+                    ..default()
+                },
+            ));
+        });
+        
+        info!("{} is in CHECK!", color_name);
+    }
+}
+
+pub(crate) fn init_game_timer(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    game_data: Res<GameData>,
+    timer: Option<Res<GameTimer>>,
+) {
+    // Coming back from the menu mid-game picks the clocks up where they stopped
+    if timer.is_some() && !game_data.game.move_history.is_empty() {
+        return;
+    }
+    
+    let timer = GameTimer::new(config.timer_minutes, config.move_seconds);
+    commands.insert_resource(timer);
+    
+    info!("Initialized game timer: {} minutes", config.timer_minutes);
+}
+
+fn spawn_captured_pieces_areas(
+    commands: &mut Commands,
+) {
+    info!("Spawning captured pieces areas...");
+    
+    // White's captured pieces (bottom-left) - pieces lost by White
+    commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            width: Val::Px(140.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        background_color: Color::srgba(0.15, 0.15, 0.15, 0.85).into(),
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "White Lost:",
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ));
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::srgb(0.8, 0.8, 0.8),
+                    ..default()
+                },
+            ),
+            CapturedPiecesUI { color: ChessColor::White },
+        ));
+    });
+    
+    // Black's captured pieces (top-right) - pieces lost by Black
+    commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            top: Val::Px(10.0),
+            width: Val::Px(140.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        background_color: Color::srgba(0.15, 0.15, 0.15, 0.85).into(),
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "Black Lost:",
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ));
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::srgb(0.8, 0.8, 0.8),
+                    ..default()
+                },
+            ),
+            CapturedPiecesUI { color: ChessColor::Black },
+        ));
+    });
+    
+    info!("Captured pieces areas spawned");
+}
+
+pub(crate) fn update_captured_pieces_display(
+    captured_pieces: Res<CapturedPieces>,
+    mut query: Query<(&mut Text, &CapturedPiecesUI)>,
+) {
+    // Only update when captured pieces change
+    if !captured_pieces.is_changed() {
+        return;
+    }
+    
+    for (mut text, ui) in query.iter_mut() {
+        let pieces = match ui.color {
+            ChessColor::White => &captured_pieces.white,
+            ChessColor::Black => &captured_pieces.black,
+        };
+        
+        if pieces.is_empty() {
+            text.sections[0].value = String::new();
+        } else {
+            // Format pieces in a compact grid (3 pieces per row)
+            let mut display = String::new();
+            for (i, piece) in pieces.iter().enumerate() {
+                display.push(piece_letter(piece.piece_type));
+                
+                // Add space between pieces in same row
+                if (i + 1) % 3 != 0 && i < pieces.len() - 1 {
+                    display.push(' ');
+                }
+                
+                // New line every 3 pieces
+                if (i + 1) % 3 == 0 && i < pieces.len() - 1 {
+                    display.push('\n');
+                }
+            }
+            text.sections[0].value = display;
+        }
+    }
+}
+
+pub(crate) fn update_pause_overlay(
+    mut commands: Commands,
+    pause: Option<Res<PauseMenu>>,
+    overlay_query: Query<Entity, With<PauseOverlay>>,
+    config: Res<GameConfig>,
+) {
+    let Some(pause) = pause else {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+    if !pause.is_changed() {
+        return;
+    }
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    
+    let (title, options) = match pause.draw_offer {
+        Some(offering) => (
+            format!("{:?} offers a draw", offering),
+            format!("{:?}: Y to accept, N to decline", match offering {
+                ChessColor::White => ChessColor::Black,
+                ChessColor::Black => ChessColor::White,
+            }),
+        ),
+        None => (
+            "Paused".to_string(),
+            format!(
+                "1 - Resume\n2 - Settings\n3 - Offer draw\n4 - Resign\n5 - Exit to menu\n\n{} to resume",
+                config.keys.name(Action::Back),
+            ),
+        ),
+    };
+    
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.8).into(),
+            z_index: ZIndex::Global(900),
+            ..default()
+        },
+        PauseOverlay,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            title,
+            TextStyle {
+                font_size: 40.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(24.0)),
+            ..default()
+        }));
+        parent.spawn(TextBundle::from_section(
+            options,
+            TextStyle {
+                font_size: 20.0,
+                color: Color::srgb(0.8, 0.8, 0.8),
+                ..default()
+            },
+        ));
+    });
+}
+
+pub(crate) fn update_confirmation_overlay(
+    mut commands: Commands,
+    confirmation: Option<Res<Confirmation>>,
+    overlay_query: Query<Entity, With<ConfirmationOverlay>>,
+    game_data: Res<GameData>,
+    config: Res<GameConfig>,
+) {
+    let Some(confirmation) = confirmation else {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+    if !confirmation.is_changed() {
+        return;
+    }
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    
+    // Sits above every other screen, the game over screen included
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+            z_index: ZIndex::Global(3000),
+            ..default()
+        },
+        ConfirmationOverlay,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            confirmation.prompt(game_data.game.current_player),
+            TextStyle {
+                font_size: 32.0,
+                color: Color::srgb(0.95, 0.85, 0.6),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(16.0)),
+            ..default()
+        }));
+        parent.spawn(TextBundle::from_section(
+            format!("Y to confirm, N or {} to cancel", config.keys.name(Action::Back)),
+            TextStyle {
+                font_size: 20.0,
+                color: Color::srgb(0.8, 0.8, 0.8),
+                ..default()
+            },
+        ));
+    });
+}
@@ -0,0 +1,506 @@
+use bevy::prelude::*;
+use hex_chess_core::{HexCoord, Color as ChessColor, ClockSnapshot};
+
+use crate::{CapturedPieces, DropSelection, GameData, GameState};
+use crate::board::{BoardLayout, ChessPiece, HexTile, MainCamera, MinimapCamera, PieceAssets, minimap_contains, spawn_pieces};
+use crate::board3d::{OrbitCamera, pick_cell_3d};
+use crate::drill::ActiveDrill;
+use crate::hud::{GameTimer, PauseOverlay};
+use crate::replay::SampleReplay;
+use crate::settings::{Action, GameConfig, KeyRebinding};
+
+/// Moves, drops and promotions from the keyboard and mouse, plus the pause menu and confirmations
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<DropSelection>()
+            .add_systems(OnExit(GameState::Playing), close_pause_menu)
+            .add_systems(Update, (
+                handle_pause_input.run_if(not(resource_exists::<Confirmation>).and_then(not(resource_exists::<ActiveDrill>))),
+            ).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (
+                handle_drop_keys.run_if(not(resource_exists::<PauseMenu>).and_then(not(resource_exists::<Confirmation>)).and_then(not(resource_exists::<ActiveDrill>))),
+                handle_promotion_key.run_if(not(resource_exists::<PauseMenu>).and_then(not(resource_exists::<Confirmation>)).and_then(not(resource_exists::<ActiveDrill>))),
+                handle_input.run_if(not(resource_exists::<PauseMenu>).and_then(not(resource_exists::<Confirmation>)).and_then(not(resource_exists::<ActiveDrill>))),
+            ).chain().run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (
+                handle_menu_toggle.run_if(not(resource_exists::<Confirmation>)), // Menu toggle works in all states
+                handle_confirmation,
+            ));
+    }
+}
+
+/// The in-game pause menu; while it is open the board and the clocks stand still
+#[derive(Resource, Default)]
+pub struct PauseMenu {
+    pub draw_offer: Option<ChessColor>,  // side waiting on an answer to its draw offer
+}
+
+/// An action that throws away a game, held until the player confirms it
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Confirmation {
+    NewGame,
+    Resign,
+    ExitToMenu,
+}
+
+impl Confirmation {
+    pub fn prompt(&self, player: ChessColor) -> String {
+        match self {
+            Confirmation::NewGame => "Start a new game?".to_string(),
+            Confirmation::Resign => format!("Resign as {:?}?", player),
+            Confirmation::ExitToMenu => "Leave this game for the menu?".to_string(),
+        }
+    }
+}
+
+/// What a click on the board changes besides the game: the piece sprites, the capture
+/// panel, the pick from the reserve and the clocks
+#[derive(bevy::ecs::system::SystemParam)]
+pub(crate) struct ClickEffects<'w, 's> {
+    commands: Commands<'w, 's>,
+    assets: PieceAssets<'w>,
+    piece_query: Query<'w, 's, (Entity, &'static mut ChessPiece)>,
+    captured_pieces: ResMut<'w, CapturedPieces>,
+    drop_selection: ResMut<'w, DropSelection>,
+    timer: Option<ResMut<'w, GameTimer>>,
+}
+
+pub(crate) fn handle_input(
+    mut game_data: ResMut<GameData>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    picker: BoardPicker,
+    replay: Option<Res<SampleReplay>>,
+    effects: ClickEffects,
+) {
+    // The board is read-only while a sample game is being replayed
+    if replay.is_some() {
+        return;
+    }
+    let ClickEffects { mut commands, mut assets, mut piece_query, mut captured_pieces, mut drop_selection, timer } = effects;
+    
+    // Clicks on the overview inset move the camera instead
+    if mouse_buttons.just_pressed(MouseButton::Left) && !picker.over_minimap() {
+        // Debug: log that click was detected
+        info!("Mouse click detected");
+        
+        if let Some(clicked_coord) = picker.clicked_hex() {
+            info!("Clicked hex: {:?}", clicked_coord);
+            let moves_before = game_data.game.move_history.len();
+            match drop_selection.piece.take() {
+                Some(piece_type) if game_data.valid_moves.contains(&clicked_coord) => {
+                    match game_data.game.drop_piece(piece_type, clicked_coord) {
+                        Ok(_) => {
+                            // Rebuild the piece entities to show the dropped piece
+                            for (entity, _) in piece_query.iter() {
+                                commands.entity(entity).despawn_recursive();
+                            }
+                            spawn_pieces(&mut commands, &mut assets.meshes, &mut assets.materials, &assets.layout, &game_data);
+                        }
+                        Err(e) => {
+                            info!("Drop error: {}", e);
+                        }
+                    }
+                    game_data.valid_moves.clear();
+                }
+                // Anywhere else drops the selection and goes on as a normal click
+                picked => {
+                    if picked.is_some() {
+                        game_data.valid_moves.clear();
+                    }
+                    handle_hex_click(&mut game_data, clicked_coord, &mut commands, &mut piece_query, &mut captured_pieces);
+                }
+            }
+            let moved = game_data.game.move_history.len() > moves_before;
+            if moved && game_data.game.move_history.back().is_some_and(|last| last.promoted_to.is_some() || last.castled.is_some()) {
+                // Rebuild the piece entities to show the promoted piece or the castled rook
+                for (entity, _) in piece_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                spawn_pieces(&mut commands, &mut assets.meshes, &mut assets.materials, &assets.layout, &game_data);
+            }
+            if let (true, Some(mut timer)) = (moved, timer) {
+                // Stamp the move with the mover's clock
+                let remaining = match game_data.game.current_player {
+                    ChessColor::White => timer.black_time,
+                    ChessColor::Black => timer.white_time,
+                };
+                let clock = ClockSnapshot {
+                    think_ms: (timer.turn_elapsed * 1000.0) as u64,
+                    remaining_ms: (remaining * 1000.0) as u64,
+                };
+                let _ = game_data.game.record_clock(clock);
+                timer.turn_elapsed = 0.0;
+            }
+        } else {
+            info!("No hex coordinate found for click");
+        }
+    }
+}
+
+/// Step the piece pawns promote to through the variant's choices
+pub(crate) fn handle_promotion_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<GameConfig>,
+    mut game_data: ResMut<GameData>,
+) {
+    if !config.keys.just_pressed(&keyboard_input, Action::Promotion) {
+        return;
+    }
+    let choices = game_data.game.variant.promotion_choices();
+    let current = game_data.promotion_piece();
+    let at = choices.iter().position(|&piece_type| piece_type == current).unwrap_or(0);
+    game_data.promotion = choices.get((at + 1) % choices.len().max(1)).copied();
+}
+
+/// Step through the pieces the side to move holds, highlighting where each can be dropped
+pub(crate) fn handle_drop_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<GameConfig>,
+    mut game_data: ResMut<GameData>,
+    mut drop_selection: ResMut<DropSelection>,
+    replay: Option<Res<SampleReplay>>,
+) {
+    let hand = game_data.game.reserves.get(game_data.game.current_player).to_vec();
+    // Forget a pick that is no longer in hand, e.g. after the turn passed
+    if drop_selection.piece.is_some_and(|piece_type| !hand.contains(&piece_type)) {
+        drop_selection.piece = None;
+        game_data.valid_moves.clear();
+    }
+    if replay.is_some() || game_data.game.is_over() || !config.keys.just_pressed(&keyboard_input, Action::NextDrop) {
+        return;
+    }
+    
+    let mut kinds = hand;
+    kinds.dedup();
+    let next = match drop_selection.piece {
+        Some(current) => kinds.iter().position(|&piece_type| piece_type == current).and_then(|at| kinds.get(at + 1)),
+        None => kinds.first(),
+    };
+    drop_selection.piece = next.copied();
+    game_data.selected_piece = None;
+    game_data.valid_moves = match drop_selection.piece {
+        Some(piece_type) => game_data.game.legal_drops(piece_type),
+        None => Vec::new(),
+    };
+}
+
+pub(crate) fn handle_menu_toggle(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    current_state: Res<State<GameState>>,
+    config: Res<GameConfig>,
+    rebinding: Option<Res<KeyRebinding>>,
+    game_data: Res<GameData>,
+) {
+    // Every key is up for grabs while keys are being rebound
+    if rebinding.is_some() {
+        return;
+    }
+    
+    // Toggle menu with the menu key ('M' by default)
+    if config.keys.just_pressed(&keyboard_input, Action::ToggleMenu) {
+        info!("Menu key pressed - toggling menu");
+        match current_state.get() {
+            GameState::Menu => {
+                next_state.set(GameState::Playing);
+                info!("Switching to Playing state");
+            }
+            GameState::Playing if !game_data.game.move_history.is_empty() && !game_data.game.is_over() => {
+                commands.insert_resource(Confirmation::ExitToMenu);
+            }
+            GameState::Playing => {
+                next_state.set(GameState::Menu);
+                info!("Switching to Menu state");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Finds the cell under the cursor, through whichever camera draws the board
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct BoardPicker<'w, 's> {
+    windows: Query<'w, 's, &'static Window>,
+    camera_2d: Query<'w, 's, (&'static Camera, &'static GlobalTransform), MainCamera>,
+    camera_3d: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<OrbitCamera>>,
+    minimap: Query<'w, 's, &'static Camera, With<MinimapCamera>>,
+    hex_tiles: Query<'w, 's, &'static HexTile>,
+    layout: Res<'w, BoardLayout>,
+    config: Res<'w, GameConfig>,
+}
+
+impl BoardPicker<'_, '_> {
+    /// Whether the cursor is over the overview inset, whose clicks move the camera instead
+    pub fn over_minimap(&self) -> bool {
+        self.windows
+            .get_single()
+            .ok()
+            .and_then(|window| window.cursor_position())
+            .is_some_and(|cursor| self.minimap.iter().any(|camera| minimap_contains(camera, cursor)))
+    }
+    
+    /// The board cell under the cursor, if there is one
+    pub fn clicked_hex(&self) -> Option<HexCoord> {
+        // Get cursor position
+        let window = self.windows.get_single().ok()?;
+        let cursor_pos = window.cursor_position()?;
+        
+        let coord = if self.config.render_3d {
+            // Cast a ray from the orbiting camera onto the tops of the prisms
+            let (camera, camera_transform) = self.camera_3d.get_single().ok()?;
+            pick_cell_3d(camera, camera_transform, cursor_pos, &self.layout)?
+        } else {
+            // Use Bevy's viewport_to_world_2d method for accurate screen-to-world conversion
+            let (camera, camera_transform) = self.camera_2d.get_single().ok()?;
+            let world_pos = camera.viewport_to_world_2d(camera_transform, cursor_pos)?;
+            
+            // Debug: log the click position
+            info!("Click at screen ({:.2}, {:.2}) -> world ({:.2}, {:.2})", cursor_pos.x, cursor_pos.y, world_pos.x, world_pos.y);
+            self.layout.world_to_board(world_pos)
+        };
+        
+        // Only cells that are actually on the board can be clicked
+        if self.hex_tiles.iter().any(|tile| tile.coord == coord) {
+            info!("Clicked tile: {:?}", coord);
+            Some(coord)
+        } else {
+            info!("No tile at {:?}", coord);
+            None
+        }
+    }
+}
+
+fn handle_hex_click(
+    game_data: &mut ResMut<GameData>,
+    coord: HexCoord,
+    commands: &mut Commands,
+    piece_query: &mut Query<(Entity, &mut ChessPiece)>,
+    captured_pieces: &mut CapturedPieces,
+) {
+    info!("handle_hex_click called with coord: {:?}", coord);
+    
+    if let Some(selected) = game_data.selected_piece {
+        info!("Piece already selected at: {:?}", selected);
+        
+        // Try to move the selected piece
+        if game_data.valid_moves.contains(&coord) {
+            info!("Valid move! Attempting to move from {:?} to {:?}", selected, coord);
+            
+            let promotion = game_data.game.is_promotion(selected, coord).then(|| game_data.promotion_piece());
+            match game_data.game.make_move_with_promotion(selected, coord, promotion) {
+                Err(e) => {
+                    info!("Move error: {:?}", e);
+                }
+                Ok(outcome) => {
+                    info!("Move successful! Updating piece entity...");
+                
+                    // Remove captured piece entity if any
+                    if let (Some(captured), Some(captured_at)) = (outcome.captured, outcome.captured_at) {
+                        info!("Capture detected! Removing piece: {:?} at {:?}", captured, captured_at);
+                    
+                        for (entity, chess_piece) in piece_query.iter() {
+                            if chess_piece.coord == captured_at && chess_piece.piece.piece_type == captured.piece_type && chess_piece.piece.color == captured.color {
+                                commands.entity(entity).despawn_recursive();
+                                captured_pieces.add(captured);
+                                info!("Despawned captured piece entity at {:?}", captured_at);
+                                break;
+                            }
+                        }
+                    }
+                
+                    // Update the piece entity's coordinate
+                    let mut found = false;
+                    for (_entity, mut chess_piece) in piece_query.iter_mut() {
+                        if chess_piece.coord == selected {
+                            chess_piece.coord = coord;
+                            found = true;
+                            info!("Updated piece entity from {:?} to {:?}", selected, coord);
+                            break;
+                        }
+                    }
+                
+                    if !found {
+                        info!("WARNING: Could not find piece entity at {:?}", selected);
+                    }
+                
+                    // An atomic capture's blast takes the capturer and its neighbours with it
+                    let exploded = game_data.game.move_history.back().map(|last| last.exploded.clone()).unwrap_or_default();
+                    for (cell, piece) in exploded {
+                        if let Some((entity, _)) = piece_query.iter().find(|(_, chess_piece)| chess_piece.coord == cell && chess_piece.piece == piece) {
+                            commands.entity(entity).despawn_recursive();
+                            captured_pieces.add(piece);
+                        }
+                    }
+                
+                    game_data.selected_piece = None;
+                    game_data.valid_moves.clear();
+                }
+            }
+        } else {
+            // Clicked on invalid move, deselect
+            info!("Invalid move to {:?} ({}), deselecting", coord, game_data.game.explain_move(selected, coord));
+            game_data.selected_piece = None;
+            game_data.valid_moves.clear();
+        }
+    } else {
+        // Select a piece
+        info!("No piece selected. Checking for piece at {:?}", coord);
+        
+        // Debug: log all pieces on the board
+        let all_pieces: Vec<_> = game_data.game.board.pieces.iter().collect();
+        info!("Board has {} pieces total. Checking for piece at {:?}", all_pieces.len(), coord);
+        
+        // Debug: list first few piece coordinates with their world positions
+        let mut piece_info = Vec::new();
+        for (coord, _piece) in game_data.game.board.pieces.iter().take(5) {
+            piece_info.push(format!("{:?}", coord));
+        }
+        info!("Sample piece squares: {}", piece_info.join(", "));
+        
+        if let Some(piece) = game_data.game.board.get_piece(coord) {
+            info!("Found piece: {:?} at {:?}", piece, coord);
+            
+            let current_player_str = match game_data.game.current_player {
+                ChessColor::White => "White",
+                ChessColor::Black => "Black",
+            };
+            let piece_color_str = match piece.color {
+                ChessColor::White => "White",
+                ChessColor::Black => "Black",
+            };
+            
+            info!("Current player: {}, Piece color: {}", current_player_str, piece_color_str);
+            
+            if piece.color == game_data.game.current_player {
+                game_data.selected_piece = Some(coord);
+                
+                game_data.valid_moves = game_data.game.legal_moves(coord);
+                info!("Piece selected! Legal moves (escaping check): {:?}", game_data.valid_moves);
+            } else {
+                info!("Piece belongs to other player, cannot select");
+            }
+        } else {
+            info!("No piece found at {:?}", coord);
+        }
+    }
+}
+
+pub(crate) fn handle_pause_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<GameConfig>,
+    pause: Option<ResMut<PauseMenu>>,
+    replay: Option<Res<SampleReplay>>,
+    mut game_data: ResMut<GameData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // The replay viewer already uses Back to hand the game over
+    if replay.is_some() {
+        return;
+    }
+    let back = config.keys.just_pressed(&keyboard_input, Action::Back);
+    let Some(mut pause) = pause else {
+        if back {
+            commands.init_resource::<PauseMenu>();
+        }
+        return;
+    };
+    
+    // A pending draw offer waits on the opponent's answer
+    if let Some(offering) = pause.draw_offer {
+        if keyboard_input.just_pressed(KeyCode::KeyY) {
+            if game_data.game.agree_draw().is_ok() {
+                info!("Draw agreed");
+            }
+            commands.remove_resource::<PauseMenu>();
+        } else if keyboard_input.just_pressed(KeyCode::KeyN) || back {
+            pause.draw_offer = None;
+            info!("{:?}'s draw offer declined", offering);
+        }
+        return;
+    }
+    
+    if back || keyboard_input.just_pressed(KeyCode::Digit1) {
+        commands.remove_resource::<PauseMenu>();
+    } else if keyboard_input.just_pressed(KeyCode::Digit2) {
+        // Clock and key settings live on the main menu, which resumes this game
+        next_state.set(GameState::Menu);
+    } else if keyboard_input.just_pressed(KeyCode::Digit3) {
+        pause.draw_offer = Some(game_data.game.current_player);
+    } else if keyboard_input.just_pressed(KeyCode::Digit4) {
+        commands.insert_resource(Confirmation::Resign);
+    } else if keyboard_input.just_pressed(KeyCode::Digit5) {
+        commands.insert_resource(Confirmation::ExitToMenu);
+    }
+}
+
+pub(crate) fn handle_confirmation(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<GameConfig>,
+    confirmation: Option<Res<Confirmation>>,
+    mut game_data: ResMut<GameData>,
+    mut captured_pieces: ResMut<CapturedPieces>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(confirmation) = confirmation else {
+        return;
+    };
+    if keyboard_input.just_pressed(KeyCode::KeyN) || config.keys.just_pressed(&keyboard_input, Action::Back) {
+        commands.remove_resource::<Confirmation>();
+        return;
+    }
+    if !keyboard_input.just_pressed(KeyCode::KeyY) {
+        return;
+    }
+    commands.remove_resource::<Confirmation>();
+    
+    match *confirmation {
+        Confirmation::NewGame => {
+            // Reset the game
+            game_data.game = hex_chess_core::Game::new(config.variant());
+            game_data.selected_piece = None;
+            game_data.valid_moves.clear();
+            
+            // Reset captured pieces
+            captured_pieces.white.clear();
+            captured_pieces.black.clear();
+            
+            commands.remove_resource::<SampleReplay>();
+            
+            // Reset and start timer
+            let timer = GameTimer::new(config.timer_minutes, config.move_seconds);
+            commands.insert_resource(timer);
+            
+            next_state.set(GameState::Playing);
+            
+            info!("Starting new game");
+        }
+        Confirmation::Resign => {
+            let player = game_data.game.current_player;
+            if game_data.game.resign(player).is_ok() {
+                game_data.selected_piece = None;
+                game_data.valid_moves.clear();
+            }
+            commands.remove_resource::<PauseMenu>();
+        }
+        Confirmation::ExitToMenu => {
+            next_state.set(GameState::Menu);
+            info!("Switching to Menu state");
+        }
+    }
+}
+
+pub(crate) fn close_pause_menu(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<PauseOverlay>>,
+) {
+    commands.remove_resource::<PauseMenu>();
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
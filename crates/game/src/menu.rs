@@ -0,0 +1,840 @@
+use bevy::prelude::*;
+use bevy::input::mouse::MouseWheel;
+use hex_chess_core::{GameLibrary, SampleGame};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+use crate::{CapturedPieces, GameData, GameState};
+use crate::board::{ChessPiece, PieceAssets, spawn_pieces};
+use crate::drill::ActiveDrill;
+use crate::piece_theme::piece_theme_name;
+use crate::replay::{SampleReplay, play_sample_replay, sync_sample_replay};
+use crate::settings::{Action, GameConfig, KeyRebinding, PIECE_SCALE_RANGE, UI_SCALE_RANGE, step_scale};
+use crate::toast::ShowToast;
+
+/// The main menu with its attract mode, the rules screen and game imports
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AttractMode>()
+            .init_resource::<ImportStatus>()
+            .add_systems(Startup, listen_for_imports)
+            .add_systems(OnEnter(GameState::Menu), spawn_menu_screen)
+            .add_systems(OnExit(GameState::Menu), cleanup_menu_screen)
+            .add_systems(Update, (
+                track_menu_idle,
+                start_attract_mode,
+                play_sample_replay,
+                sync_sample_replay,
+                stop_attract_mode,
+                update_menu_backdrop,
+                handle_key_rebinding,
+                handle_menu_input,
+                refresh_menu_screen,
+            ).chain().run_if(in_state(GameState::Menu)))
+            .add_systems(OnEnter(GameState::Rules), spawn_rules_screen)
+            .add_systems(OnExit(GameState::Rules), cleanup_rules_screen)
+            .add_systems(Update, (
+                handle_rules_input,
+            ).run_if(in_state(GameState::Rules)))
+            .add_systems(Update, (receive_dropped_files, import_games).chain());
+    }
+}
+
+/// Text pasted or dropped onto the page, waiting for `import_games` to read it
+static PENDING_IMPORTS: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+fn queue_import(text: String) {
+    if let Ok(mut pending) = PENDING_IMPORTS.lock() {
+        pending.push(text);
+    }
+}
+
+/// Listen for games pasted into the page or dropped onto it as files
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn listen_for_imports() {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    
+    let on_paste = Closure::<dyn FnMut(web_sys::ClipboardEvent)>::new(|event: web_sys::ClipboardEvent| {
+        if let Some(text) = event.clipboard_data().and_then(|data| data.get_data("text").ok()) {
+            queue_import(text);
+        }
+    });
+    let _ = document.add_event_listener_with_callback("paste", on_paste.as_ref().unchecked_ref());
+    on_paste.forget();
+    
+    // The page only accepts drops when dragover is cancelled
+    let on_drag_over = Closure::<dyn FnMut(web_sys::DragEvent)>::new(|event: web_sys::DragEvent| {
+        event.prevent_default();
+    });
+    let _ = document.add_event_listener_with_callback("dragover", on_drag_over.as_ref().unchecked_ref());
+    on_drag_over.forget();
+    
+    let on_drop = Closure::<dyn FnMut(web_sys::DragEvent)>::new(|event: web_sys::DragEvent| {
+        event.prevent_default();
+        let Some(files) = event.data_transfer().and_then(|data| data.files()) else {
+            return;
+        };
+        for index in 0..files.length() {
+            if let Some(file) = files.get(index) {
+                let on_text = Closure::once(|text: JsValue| {
+                    if let Some(text) = text.as_string() {
+                        queue_import(text);
+                    }
+                });
+                let _ = file.text().then(&on_text);
+                on_text.forget();
+            }
+        }
+    });
+    let _ = document.add_event_listener_with_callback("drop", on_drop.as_ref().unchecked_ref());
+    on_drop.forget();
+}
+
+/// Native windows get dropped files from `receive_dropped_files` instead
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn listen_for_imports() {}
+
+#[derive(Component)]
+pub struct RulesScreen;
+
+#[derive(Component)]
+pub struct MenuScreen;
+
+/// Sample games played behind the menu once it has sat idle for a while
+#[derive(Resource, Default)]
+pub struct AttractMode {
+    pub idle: f32,                               // seconds since the last input on the menu
+    pub saved: Option<hex_chess_core::Game>,     // the real game, put back when attract mode stops
+    pub next_sample: usize,
+    pub finished_for: f32,                       // seconds the current sample has been over
+}
+
+/// Seconds of menu inactivity before attract mode starts
+const ATTRACT_IDLE_SECONDS: f32 = 30.0;
+
+/// Seconds to linger on a finished sample game before starting the next
+const ATTRACT_PAUSE_SECONDS: f32 = 4.0;
+
+/// Playback speed of attract mode games
+const ATTRACT_SPEED: f32 = 4.0;
+
+// 3D mesh creation functions removed - using 2D shapes instead
+
+#[derive(Component)]
+pub struct RulesUI;
+
+pub(crate) fn spawn_menu_screen(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    rebinding: Option<Res<KeyRebinding>>,
+    import_status: Res<ImportStatus>,
+) {
+    info!("Spawning menu screen...");
+    
+    build_menu_screen(&mut commands, &config, rebinding.as_deref(), &import_status);
+}
+
+/// Rebuild the menu when the settings it shows change
+pub(crate) fn refresh_menu_screen(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    rebinding: Option<Res<KeyRebinding>>,
+    import_status: Res<ImportStatus>,
+    menu_query: Query<Entity, With<MenuScreen>>,
+    mut shown_rebinding: Local<Option<usize>>,
+) {
+    let rebinding_step = rebinding.as_deref().map(|rebinding| rebinding.next);
+    if !config.is_changed() && !import_status.is_changed() && rebinding_step == *shown_rebinding {
+        return;
+    }
+    *shown_rebinding = rebinding_step;
+    
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    build_menu_screen(&mut commands, &config, rebinding.as_deref(), &import_status);
+}
+
+fn build_menu_screen(
+    commands: &mut Commands,
+    config: &GameConfig,
+    rebinding: Option<&KeyRebinding>,
+    import_status: &ImportStatus,
+) {
+    let keys = &config.keys;
+    
+    // Full screen menu background
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.95).into(),
+            z_index: ZIndex::Global(1000),
+            ..default()
+        },
+        MenuScreen,
+    )).with_children(|parent| {
+        // Title
+        parent.spawn(TextBundle::from_section(
+            "Hexagonal Chess",
+            TextStyle {
+                font_size: 48.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(40.0)),
+            ..default()
+        }));
+        
+        // Timer configuration
+        parent.spawn(TextBundle::from_section(
+            format!("Timer: {} minutes per player", config.timer_minutes as i32),
+            TextStyle {
+                font_size: 20.0,
+                color: Color::srgb(0.8, 0.8, 0.8),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(10.0)),
+            ..default()
+        }));
+        
+        let move_limit = match config.move_seconds {
+            Some(seconds) => format!("{} seconds per move", seconds as i32),
+            None => "no limit per move".to_string(),
+        };
+        parent.spawn(TextBundle::from_section(
+            format!(
+                "Use UP/DOWN arrows to adjust (1-60 min)\nEach move: {} ({} changes)",
+                move_limit,
+                keys.name(Action::MoveTimeLimit),
+            ),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(10.0)),
+            ..default()
+        }));
+        
+        parent.spawn(TextBundle::from_section(
+            format!("Variant: {} ({} switches)", config.variant().name, keys.name(Action::Variant)),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(10.0)),
+            ..default()
+        }));
+        
+        // Display sizes and motion
+        let motion = match config.reduced_motion {
+            None if config.reduced_motion() => "on (browser)",
+            None => "off (browser)",
+            Some(true) => "on",
+            Some(false) => "off",
+        };
+        parent.spawn(TextBundle::from_section(
+            format!(
+                "UI size {:.2}x (LEFT/RIGHT), pieces {:.1}x (,/.), piece letters {:.1}x (;/')\nPieces: {} ({} changes)\nReduced motion: {} ({})\nBoard: {} ({} switches)\nBoard images ({} in game): {} ({} changes)",
+                config.ui_scale,
+                config.piece_scale,
+                config.label_scale,
+                piece_theme_name(&config.piece_theme),
+                keys.name(Action::PieceTheme),
+                motion,
+                keys.name(Action::ReducedMotion),
+                if config.render_3d { "3D, right-drag to orbit" } else { "2D" },
+                keys.name(Action::Board3d),
+                keys.name(Action::CopyBoardImage),
+                match (config.image_labels, config.image_from_mover) {
+                    (true, false) => "labelled, White at the bottom",
+                    (true, true) => "labelled, side to move at the bottom",
+                    (false, false) => "plain, White at the bottom",
+                    (false, true) => "plain, side to move at the bottom",
+                },
+                keys.name(Action::BoardImageStyle),
+            ),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(30.0)),
+            ..default()
+        }));
+        
+        // Menu options
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} or {} to Start Game", keys.name(Action::Confirm), keys.name(Action::ToggleMenu)),
+            TextStyle {
+                font_size: 20.0,
+                color: Color::srgb(0.4, 0.8, 0.4),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(15.0)),
+            ..default()
+        }));
+        
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} to View Rules", keys.name(Action::ShowRules)),
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(15.0)),
+            ..default()
+        }));
+        
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} for Training Drills", keys.name(Action::Training)),
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(15.0)),
+            ..default()
+        }));
+        
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} to Watch Sample Games", keys.name(Action::WatchSamples)),
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(15.0)),
+            ..default()
+        }));
+        
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} for Game History", keys.name(Action::History)),
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(15.0)),
+            ..default()
+        }));
+        
+        // Pasting or dropping a game opens it in the replay viewer
+        let (import_text, import_color) = match &import_status.message {
+            Some(message) => (message.clone(), Color::srgb(1.0, 0.4, 0.4)),
+            None => ("Paste or drop a game (JSON or a move list) to replay it".to_string(), Color::srgb(0.6, 0.6, 0.6)),
+        };
+        parent.spawn(TextBundle::from_section(
+            import_text,
+            TextStyle {
+                font_size: 14.0,
+                color: import_color,
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(15.0)),
+            ..default()
+        }));
+        
+        // Key bindings, or the binding currently being asked for
+        let (bindings_text, bindings_color) = match rebinding {
+            Some(rebinding) => {
+                let action = Action::ALL[rebinding.next.min(Action::ALL.len() - 1)];
+                (
+                    format!(
+                        "Press a key for: {} (now {}) - {}/{}",
+                        action.label(),
+                        keys.name(action),
+                        rebinding.next + 1,
+                        Action::ALL.len(),
+                    ),
+                    Color::srgb(1.0, 0.9, 0.2),
+                )
+            }
+            None => (
+                format!(
+                    "Press {} to Rebind Keys, {} to Reset Settings",
+                    keys.name(Action::RebindKeys),
+                    keys.name(Action::ResetSettings),
+                ),
+                Color::srgb(0.6, 0.6, 0.6),
+            ),
+        };
+        parent.spawn(TextBundle::from_section(
+            bindings_text,
+            TextStyle {
+                font_size: 14.0,
+                color: bindings_color,
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::top(Val::Px(15.0)),
+            ..default()
+        }));
+    });
+}
+
+pub(crate) fn cleanup_menu_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<MenuScreen>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    
+    info!("Cleaned up menu screen");
+}
+
+pub(crate) fn handle_menu_input(
+    mut commands: Commands,
+    mut game_state: ResMut<NextState<GameState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<GameConfig>,
+    rebinding: Option<Res<KeyRebinding>>,
+    mut next_sample: Local<usize>,
+    mut game_data: ResMut<GameData>,
+) {
+    if rebinding.is_some() {
+        return;
+    }
+    
+    // Adjust timer with up/down arrows
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        config.timer_minutes = (config.timer_minutes + 1.0).min(60.0);
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        config.timer_minutes = (config.timer_minutes - 1.0).max(1.0);
+    }
+    
+    // Display sizes: left/right for the UI, comma/period for pieces, semicolon/quote for piece letters
+    if keyboard_input.any_just_pressed([KeyCode::ArrowLeft, KeyCode::ArrowRight]) {
+        let up = keyboard_input.just_pressed(KeyCode::ArrowRight);
+        config.ui_scale = step_scale(config.ui_scale, up, UI_SCALE_RANGE);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Comma, KeyCode::Period]) {
+        let up = keyboard_input.just_pressed(KeyCode::Period);
+        config.piece_scale = step_scale(config.piece_scale, up, PIECE_SCALE_RANGE);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Semicolon, KeyCode::Quote]) {
+        let up = keyboard_input.just_pressed(KeyCode::Quote);
+        config.label_scale = step_scale(config.label_scale, up, PIECE_SCALE_RANGE);
+    }
+    
+    // Press Space or M to start/return to game
+    if config.keys.just_pressed(&keyboard_input, Action::Confirm) || config.keys.just_pressed(&keyboard_input, Action::ToggleMenu) {
+        game_state.set(GameState::Playing);
+    }
+    
+    // Start walking through the actions, binding each to the next key pressed
+    if config.keys.just_pressed(&keyboard_input, Action::RebindKeys) {
+        commands.insert_resource(KeyRebinding::default());
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::ReducedMotion) {
+        config.cycle_reduced_motion();
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::MoveTimeLimit) {
+        config.cycle_move_limit();
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::Board3d) {
+        config.render_3d = !config.render_3d;
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::BoardImageStyle) {
+        config.cycle_board_image();
+    }
+    
+    // The switch applies to the next game, or to this one if nobody has moved yet
+    if config.keys.just_pressed(&keyboard_input, Action::Variant) {
+        config.cycle_variant();
+        if game_data.game.move_history.is_empty() {
+            game_data.game = hex_chess_core::Game::new(config.variant());
+        }
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::ResetSettings) {
+        *config = GameConfig::default();
+        info!("Settings reset to defaults");
+    }
+    
+    // Press R to view rules
+    if config.keys.just_pressed(&keyboard_input, Action::ShowRules) {
+        game_state.set(GameState::Rules);
+        info!("Switching to Rules state");
+    }
+    
+    // Press W to watch the next sample game from the library
+    if config.keys.just_pressed(&keyboard_input, Action::Training) {
+        match ActiveDrill::new(0) {
+            Ok(drill) => {
+                commands.remove_resource::<SampleReplay>();
+                commands.insert_resource(drill);
+                game_state.set(GameState::Playing);
+            }
+            Err(e) => commands.add(ShowToast::error(e)),
+        }
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::History) {
+        game_state.set(GameState::History);
+    }
+    
+    if config.keys.just_pressed(&keyboard_input, Action::WatchSamples) {
+        let games = GameLibrary::list();
+        if !games.is_empty() {
+            let info = &games[*next_sample % games.len()];
+            *next_sample += 1;
+            match GameLibrary::load(&info.id) {
+                Ok(sample) => {
+                    commands.insert_resource(SampleReplay::new(sample));
+                    game_state.set(GameState::Playing);
+                }
+                Err(e) => {
+                    commands.add(ShowToast::error(format!("Could not load sample game {}: {}", info.id, e)));
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn handle_key_rebinding(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<GameConfig>,
+    rebinding: Option<ResMut<KeyRebinding>>,
+) {
+    let Some(mut rebinding) = rebinding else {
+        return;
+    };
+    let Some(&key) = keyboard_input.get_just_pressed().next() else {
+        return;
+    };
+    
+    config.keys.set(Action::ALL[rebinding.next], key);
+    rebinding.next += 1;
+    if rebinding.next >= Action::ALL.len() {
+        commands.remove_resource::<KeyRebinding>();
+    }
+}
+
+pub(crate) fn track_menu_idle(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mut attract: ResMut<AttractMode>,
+) {
+    let moved = cursor_moved.read().count() > 0;
+    let scrolled = scroll_events.read().count() > 0;
+    let pressed = keyboard_input.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some();
+    
+    if moved || scrolled || pressed {
+        attract.idle = 0.0;
+    } else {
+        attract.idle += time.delta_seconds();
+    }
+}
+
+pub(crate) fn start_attract_mode(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut attract: ResMut<AttractMode>,
+    game_data: Res<GameData>,
+    replay: Option<Res<SampleReplay>>,
+    config: Res<GameConfig>,
+) {
+    // A board that starts playing itself is exactly what reduced motion asks to avoid
+    if attract.idle < ATTRACT_IDLE_SECONDS || config.reduced_motion() {
+        return;
+    }
+    
+    match (&attract.saved, replay) {
+        // Leave a sample game the player opened themselves alone
+        (None, Some(_)) => return,
+        (None, None) => {
+            attract.saved = Some(game_data.game.clone());
+            attract.finished_for = 0.0;
+        }
+        (Some(_), Some(replay)) => {
+            if replay.playing || replay.ply < replay.game.moves.len() {
+                return;
+            }
+            attract.finished_for += time.delta_seconds();
+            if attract.finished_for < ATTRACT_PAUSE_SECONDS {
+                return;
+            }
+            attract.finished_for = 0.0;
+        }
+        (Some(_), None) => {}
+    }
+    
+    let games = GameLibrary::list();
+    if games.is_empty() {
+        return;
+    }
+    let info = &games[attract.next_sample % games.len()];
+    attract.next_sample += 1;
+    match GameLibrary::load(&info.id) {
+        Ok(sample) => {
+            let mut replay = SampleReplay::new(sample);
+            replay.playing = true;
+            replay.speed = ATTRACT_SPEED;
+            commands.insert_resource(replay);
+        }
+        Err(e) => {
+            commands.add(ShowToast::error(format!("Could not load sample game {}: {}", info.id, e)));
+        }
+    }
+}
+
+pub(crate) fn stop_attract_mode(
+    mut commands: Commands,
+    mut attract: ResMut<AttractMode>,
+    mut game_data: ResMut<GameData>,
+    mut assets: PieceAssets,
+    piece_query: Query<Entity, With<ChessPiece>>,
+    mut captured_pieces: ResMut<CapturedPieces>,
+) {
+    if attract.idle >= ATTRACT_IDLE_SECONDS {
+        return;
+    }
+    let Some(saved) = attract.saved.take() else {
+        return;
+    };
+    
+    // Put the interrupted game back exactly as it was
+    commands.remove_resource::<SampleReplay>();
+    captured_pieces.white.clear();
+    captured_pieces.black.clear();
+    for game_move in &saved.move_history {
+        if let Some(captured) = game_move.captured_piece {
+            captured_pieces.add(captured);
+        }
+    }
+    game_data.game = saved;
+    
+    for entity in piece_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_pieces(&mut commands, &mut assets.meshes, &mut assets.materials, &assets.layout, &game_data);
+}
+
+pub(crate) fn update_menu_backdrop(
+    attract: Res<AttractMode>,
+    mut backdrop_query: Query<&mut BackgroundColor, (With<MenuScreen>, Without<Text>)>,
+) {
+    // Thin the menu backdrop so the attract game shows through
+    let alpha = if attract.saved.is_some() { 0.6 } else { 0.95 };
+    for mut background in backdrop_query.iter_mut() {
+        if background.0.alpha() != alpha {
+            background.0.set_alpha(alpha);
+        }
+    }
+}
+
+pub(crate) fn spawn_rules_screen(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+) {
+    let keys = &config.keys;
+    info!("Spawning rules screen...");
+    
+    // Full screen dark background
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                top: Val::Px(0.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.95).into(),
+            z_index: ZIndex::Global(1000),
+            ..default()
+        },
+        RulesScreen,
+    )).with_children(|parent| {
+        // Title
+        parent.spawn(TextBundle::from_section(
+            "Hexagonal Chess - Rules",
+            TextStyle {
+                font_size: 32.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::bottom(Val::Px(20.0)),
+            ..default()
+        }));
+        
+        // Rules content
+        let rules_text = format!("\
+Hexagonal Chess (Gliński's Chess)
+
+OBJECTIVE:
+Checkmate your opponent's king.
+
+PIECES:
+• King (K) - Moves one space in any direction
+• Queen (Q) - Moves any distance in any direction
+• Rook (R) - Moves along straight lines
+• Bishop (B) - Moves diagonally (per color)
+• Knight (N) - Moves in an L-shape
+• Pawn (P) - Moves forward, captures diagonally
+
+SPECIAL RULES:
+• Pawns move forward toward opponent
+• En passant capture is allowed
+• Check: Your king is under attack
+• Checkmate: Your king has no legal moves to escape check
+• Stalemate: No legal moves available (¾ – ¼ to the stalemating side)
+
+CONTROLS:
+• Click to select and move pieces
+• Mouse wheel or +/- to zoom
+• Arrow keys to pan camera
+• {} to reset camera
+• {} to open the menu
+
+Press {} or {} to return to menu",
+            keys.name(Action::ResetCamera),
+            keys.name(Action::ToggleMenu),
+            keys.name(Action::Back),
+            keys.name(Action::Confirm),
+        );
+        
+        parent.spawn(TextBundle::from_section(
+            rules_text,
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(0.85, 0.85, 0.85),
+                ..default()
+            },
+        ).with_style(Style {
+            max_width: Val::Px(700.0),
+            ..default()
+        }).with_text_justify(JustifyText::Left));
+        
+        // Back button hint
+        parent.spawn(TextBundle::from_section(
+            format!("Press {} or {} to go back", keys.name(Action::Back), keys.name(Action::Confirm)),
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.7, 0.7, 0.7),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::top(Val::Px(30.0)),
+            ..default()
+        }));
+    });
+}
+
+pub(crate) fn cleanup_rules_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<RulesScreen>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    
+    info!("Cleaned up rules screen");
+}
+
+pub(crate) fn handle_rules_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    config: Res<GameConfig>,
+) {
+    // Return to menu with ESC or Space
+    if config.keys.just_pressed(&keyboard_input, Action::Back) || config.keys.just_pressed(&keyboard_input, Action::Confirm) {
+        next_state.set(GameState::Menu);
+        info!("Returning to menu from rules");
+    }
+}
+
+/// What became of the last game the player pasted or dropped in, shown on the menu
+#[derive(Resource, Default)]
+pub struct ImportStatus {
+    pub message: Option<String>,
+}
+
+/// Queue files dropped onto the native window
+pub(crate) fn receive_dropped_files(mut commands: Commands, mut drops: EventReader<FileDragAndDrop>) {
+    for drop in drops.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = drop {
+            match std::fs::read_to_string(path_buf) {
+                Ok(text) => queue_import(text),
+                Err(e) => {
+                    commands.add(ShowToast::error(format!("Could not read {}: {}", path_buf.display(), e)));
+                }
+            }
+        }
+    }
+}
+
+/// Open pasted or dropped games in the replay viewer; only the menu and the history screen take them
+pub(crate) fn import_games(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    state: Res<State<GameState>>,
+    mut status: ResMut<ImportStatus>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let pending = match PENDING_IMPORTS.lock() {
+        Ok(mut pending) => std::mem::take(&mut *pending),
+        Err(_) => return,
+    };
+    if !matches!(state.get(), GameState::Menu | GameState::History) {
+        return;
+    }
+    
+    // Only the last one can be watched
+    if let Some(text) = pending.last() {
+        match SampleGame::import(text, config.variant()) {
+            Ok(game) => {
+                status.message = None;
+                commands.insert_resource(SampleReplay::new(game));
+                next_state.set(GameState::Playing);
+            }
+            Err(e) => status.message = Some(format!("Could not import game: {}", e)),
+        }
+    }
+}
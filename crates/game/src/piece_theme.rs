@@ -0,0 +1,269 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use hex_chess_core::Color as ChessColor;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::board::{BoardLayout, ChessPiece, PieceLabel};
+use crate::settings::{Action, GameConfig, KeyRebinding};
+use crate::GameState;
+
+/// Piece themes drawn without images, by id and name, in the order the menu steps through them
+pub const BUILT_IN_THEMES: [(&str, &str); 4] = [
+    ("classic", "Classic"),
+    ("minimalist", "Minimalist"),
+    ("letters", "Letters"),
+    ("high_contrast", "High contrast"),
+];
+
+/// Where the list of installed piece packs lives, under the asset folder
+pub const PACK_INDEX_PATH: &str = "pieces/packs.json";
+
+/// How pieces look: the built-in themes, and image packs listed in `pieces/packs.json`
+///
+/// A pack names an image for each piece by its letter, upper case for White and
+/// lower case for Black, with paths relative to `pieces/`:
+///
+/// ```json
+/// { "packs": [{
+///     "id": "staunton",
+///     "name": "Staunton",
+///     "author": "A. Artist",
+///     "images": { "K": "staunton/white-king.png", "k": "staunton/black-king.png" }
+/// }] }
+/// ```
+///
+/// The Capablanca pieces use `C` for the chancellor and `A` for the archbishop.
+/// Pieces a pack has no image for keep their letters. Images are only fetched once
+/// their pack is picked.
+pub struct PieceThemePlugin;
+
+impl Plugin for PieceThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<PiecePackIndex>()
+            .init_asset_loader::<PiecePackIndexLoader>()
+            .init_resource::<PieceThemes>()
+            .add_systems(Startup, load_piece_packs)
+            .add_systems(Update, cycle_piece_theme.run_if(in_state(GameState::Menu)))
+            .add_systems(Update, (watch_piece_packs, restyle_pieces).chain());
+    }
+}
+
+/// An installed image pack, as listed in the pack index
+#[derive(Debug, Clone, Deserialize)]
+pub struct PiecePack {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    pub images: HashMap<char, String>, // image path by piece letter
+}
+
+/// The pack index: every image pack the game can offer
+#[derive(Asset, TypePath, Debug, Default, Deserialize)]
+pub struct PiecePackIndex {
+    pub packs: Vec<PiecePack>,
+}
+
+#[derive(Default)]
+pub struct PiecePackIndexLoader;
+
+impl AssetLoader for PiecePackIndexLoader {
+    type Asset = PiecePackIndex;
+    type Settings = ();
+    type Error = std::io::Error;
+    
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<PiecePackIndex, std::io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+    
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
+
+/// The pack index, and the images of each pack picked so far
+#[derive(Resource, Default)]
+pub struct PieceThemes {
+    pub index: Handle<PiecePackIndex>,
+    images: HashMap<String, HashMap<char, Handle<Image>>>,
+}
+
+impl PieceThemes {
+    /// Images for a pack's pieces, fetching them the first time the pack is used
+    fn images(&mut self, pack: &PiecePack, server: &AssetServer) -> &HashMap<char, Handle<Image>> {
+        self.images.entry(pack.id.clone()).or_insert_with(|| {
+            pack.images
+                .iter()
+                .map(|(&letter, path)| (letter, server.load(format!("pieces/{}", path))))
+                .collect()
+        })
+    }
+}
+
+/// Display name of a theme id; packs that aren't built in show their id
+pub fn piece_theme_name(id: &str) -> &str {
+    BUILT_IN_THEMES
+        .iter()
+        .find(|(theme, _)| *theme == id)
+        .map_or(id, |(_, name)| name)
+}
+
+/// An image drawn over a piece by an image pack
+#[derive(Component)]
+pub struct PieceSprite;
+
+/// Token and letter colours of a built-in theme, for White's pieces then Black's
+struct PieceColors {
+    token: [Color; 2],
+    ink: [Color; 2],
+}
+
+impl PieceColors {
+    fn for_theme(id: &str) -> Self {
+        match id {
+            "minimalist" => Self {
+                token: [Color::srgb(0.88, 0.86, 0.8), Color::srgb(0.3, 0.3, 0.32)],
+                ink: [Color::srgb(0.35, 0.35, 0.35), Color::srgb(0.75, 0.75, 0.75)],
+            },
+            // No token at all, just the letter in its side's colour
+            "letters" => Self {
+                token: [Color::NONE, Color::NONE],
+                ink: [Color::srgb(0.1, 0.2, 0.55), Color::srgb(0.55, 0.1, 0.1)],
+            },
+            "high_contrast" => Self {
+                token: [Color::WHITE, Color::BLACK],
+                ink: [Color::BLACK, Color::srgb(1.0, 0.85, 0.0)],
+            },
+            // Classic, which is also what pieces are spawned with
+            _ => Self {
+                token: [Color::srgb(1.0, 1.0, 1.0), Color::srgb(0.2, 0.2, 0.2)],
+                ink: [Color::srgb(0.0, 0.0, 0.0), Color::srgb(1.0, 1.0, 1.0)],
+            },
+        }
+    }
+}
+
+/// Asset access needed to restyle pieces
+#[derive(SystemParam)]
+pub struct PieceThemeAssets<'w> {
+    pub server: Res<'w, AssetServer>,
+    pub indexes: Res<'w, Assets<PiecePackIndex>>,
+    pub materials: ResMut<'w, Assets<ColorMaterial>>,
+    pub layout: Res<'w, BoardLayout>,
+}
+
+pub(crate) fn load_piece_packs(mut themes: ResMut<PieceThemes>, server: Res<AssetServer>) {
+    themes.index = server.load(PACK_INDEX_PATH);
+}
+
+/// Restyle everything once the pack index arrives, in case a pack is already picked
+pub(crate) fn watch_piece_packs(mut events: EventReader<AssetEvent<PiecePackIndex>>, mut themes: ResMut<PieceThemes>) {
+    for event in events.read() {
+        if matches!(event, AssetEvent::LoadedWithDependencies { .. } | AssetEvent::Modified { .. }) {
+            themes.set_changed();
+        }
+    }
+}
+
+/// Step to the next theme: the built-in ones, then any installed packs
+pub(crate) fn cycle_piece_theme(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<GameConfig>,
+    themes: Res<PieceThemes>,
+    indexes: Res<Assets<PiecePackIndex>>,
+    rebinding: Option<Res<KeyRebinding>>,
+) {
+    if rebinding.is_some() || !config.keys.just_pressed(&keyboard_input, Action::PieceTheme) {
+        return;
+    }
+    let packs = indexes.get(&themes.index).map(|index| index.packs.as_slice()).unwrap_or_default();
+    let ids: Vec<&str> = BUILT_IN_THEMES
+        .iter()
+        .map(|(id, _)| *id)
+        .chain(packs.iter().map(|pack| pack.id.as_str()))
+        .collect();
+    let at = ids.iter().position(|&id| id == config.piece_theme);
+    let next = at.map_or(0, |at| (at + 1) % ids.len());
+    config.piece_theme = ids[next].to_string();
+}
+
+/// Give new pieces the chosen theme, and every piece a new one when the choice changes
+pub(crate) fn restyle_pieces(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut themes: ResMut<PieceThemes>,
+    mut assets: PieceThemeAssets,
+    pieces: Query<(Entity, Ref<ChessPiece>, &Handle<ColorMaterial>, &Children)>,
+    mut labels: Query<(&mut Text, &mut Visibility), With<PieceLabel>>,
+    sprites: Query<Entity, With<PieceSprite>>,
+) {
+    let restyle_all = config.is_changed() || themes.is_changed();
+    if !restyle_all && !pieces.iter().any(|(_, piece, ..)| piece.is_added()) {
+        return;
+    }
+    
+    // A pack that hasn't loaded, or has been removed, draws as Classic meanwhile
+    let pack = assets
+        .indexes
+        .get(&themes.index)
+        .and_then(|index| index.packs.iter().find(|pack| pack.id == config.piece_theme))
+        .cloned();
+    let images = match &pack {
+        // Fetching images doesn't change the choice, so it mustn't trigger another restyle
+        Some(pack) => themes.bypass_change_detection().images(pack, &assets.server).clone(),
+        None => HashMap::new(),
+    };
+    let colors = PieceColors::for_theme(&config.piece_theme);
+    let sprite_size = Vec2::splat(assets.layout.tile_radius() * 1.4);
+    
+    for (entity, piece, material, children) in pieces.iter() {
+        if !restyle_all && !piece.is_added() {
+            continue;
+        }
+        let side = match piece.piece.color {
+            ChessColor::White => 0,
+            ChessColor::Black => 1,
+        };
+        let image = images.get(&piece.piece.symbol()).cloned();
+        
+        if let Some(material) = assets.materials.get_mut(material) {
+            material.color = if image.is_some() { Color::NONE } else { colors.token[side] };
+        }
+        for &child in children.iter() {
+            if sprites.contains(child) {
+                commands.entity(child).despawn_recursive();
+            } else if let Ok((mut text, mut visibility)) = labels.get_mut(child) {
+                for section in &mut text.sections {
+                    section.style.color = colors.ink[side];
+                }
+                *visibility = if image.is_some() { Visibility::Hidden } else { Visibility::Inherited };
+            }
+        }
+        if let Some(texture) = image {
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        texture,
+                        sprite: Sprite { custom_size: Some(sprite_size), ..default() },
+                        // Undo the token's turn to flat-top, so images stand upright
+                        transform: Transform::from_xyz(0.0, 0.0, 0.2)
+                            .with_rotation(Quat::from_rotation_z(-std::f32::consts::PI / 6.0)),
+                        ..default()
+                    },
+                    PieceSprite,
+                ));
+            });
+        }
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn read_storage(key: &str) -> Option<String> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.get_item(key).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn write_storage(key: &str, data: &str) {
+    let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+    if let Some(storage) = storage {
+        let _ = storage.set_item(key, data);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now_ms() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Local date and time of a timestamp, as the browser formats it
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn format_timestamp(ms: u64) -> String {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(ms as f64));
+    String::from(date.to_locale_string("default", &wasm_bindgen::JsValue::UNDEFINED))
+}
+
+/// Offer a file to the player as a download
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save_file(name: &str, data: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let Ok(link) = document.create_element("a") else {
+        return;
+    };
+    let href = format!("data:application/json;charset=utf-8,{}", String::from(js_sys::encode_uri_component(data)));
+    let _ = link.set_attribute("href", &href);
+    let _ = link.set_attribute("download", name);
+    if let Ok(link) = link.dyn_into::<web_sys::HtmlElement>() {
+        link.click();
+    }
+}
+
+/// Draw an SVG picture onto a canvas and put it on the clipboard as a PNG, or offer
+/// the PNG as `name`.png where the browser won't allow that
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn copy_image(name: &str, svg: &str) {
+    let Ok(image) = web_sys::HtmlImageElement::new() else {
+        return;
+    };
+    let name = format!("{}.png", name);
+    let loaded = image.clone();
+    let on_load = Closure::once_into_js(move || {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let Some(canvas) = document.create_element("canvas").ok().and_then(|canvas| canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok()) else {
+            return;
+        };
+        canvas.set_width(loaded.natural_width());
+        canvas.set_height(loaded.natural_height());
+        let context = canvas.get_context("2d").ok().flatten().and_then(|context| context.dyn_into::<web_sys::CanvasRenderingContext2d>().ok());
+        let Some(context) = context else {
+            return;
+        };
+        let _ = context.draw_image_with_html_image_element(&loaded, 0.0, 0.0);
+        let on_blob = Closure::once_into_js(move |blob: Option<web_sys::Blob>| {
+            if let Some(blob) = blob {
+                copy_png(name, blob);
+            }
+        });
+        let _ = canvas.to_blob(on_blob.unchecked_ref());
+    });
+    image.set_onload(Some(on_load.unchecked_ref()));
+    image.set_src(&format!("data:image/svg+xml;charset=utf-8,{}", String::from(js_sys::encode_uri_component(svg))));
+}
+
+/// Put a PNG on the clipboard, falling back to a download when there's no clipboard
+/// (pages served over plain http) or the browser refuses the write
+#[cfg(target_arch = "wasm32")]
+fn copy_png(name: String, blob: web_sys::Blob) {
+    let items = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&items, &JsValue::from_str("image/png"), &blob);
+    let clipboard = web_sys::window()
+        .and_then(|window| js_sys::Reflect::get(&window.navigator(), &JsValue::from_str("clipboard")).ok())
+        .filter(|clipboard| !clipboard.is_undefined())
+        .map(|clipboard| clipboard.unchecked_into::<web_sys::Clipboard>());
+    let written = clipboard
+        .zip(web_sys::ClipboardItem::new_with_record_from_str_to_blob_promise(&items).ok())
+        .map(|(clipboard, item)| clipboard.write(&js_sys::Array::of1(&item)));
+    match written {
+        Some(written) => {
+            let fallback = Closure::once(move |_: JsValue| download_blob(&name, &blob));
+            let _ = written.catch(&fallback);
+            fallback.forget();
+        }
+        None => download_blob(&name, &blob),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn download_blob(name: &str, blob: &web_sys::Blob) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let (Ok(link), Ok(href)) = (document.create_element("a"), web_sys::Url::create_object_url_with_blob(blob)) else {
+        return;
+    };
+    let _ = link.set_attribute("href", &href);
+    let _ = link.set_attribute("download", name);
+    if let Ok(link) = link.dyn_into::<web_sys::HtmlElement>() {
+        link.click();
+    }
+    let _ = web_sys::Url::revoke_object_url(&href);
+}
+
+/// Tell the page the board is up, so it can take down its loading screen
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn announce_ready() {
+    if let Some(window) = web_sys::window() {
+        if let Ok(event) = web_sys::Event::new("hex-chess-ready") {
+            let _ = window.dispatch_event(&event);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn announce_ready() {}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+        .is_some_and(|query| query.matches())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn prefers_reduced_motion() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn storage_path(key: &str) -> std::path::PathBuf {
+    let dir = std::env::var_os("HOME").map(std::path::PathBuf::from).unwrap_or_default();
+    dir.join(format!(".{}.json", key))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_storage(key: &str) -> Option<String> {
+    std::fs::read_to_string(storage_path(key)).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write_storage(key: &str, data: &str) {
+    let _ = std::fs::write(storage_path(key), data);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn format_timestamp(ms: u64) -> String {
+    format!("{}s after the epoch", ms / 1000)
+}
+
+/// Write a file next to the native settings
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save_file(name: &str, data: &str) {
+    let dir = std::env::var_os("HOME").map(std::path::PathBuf::from).unwrap_or_default();
+    let _ = std::fs::write(dir.join(name), data);
+}
+
+/// Without a browser to turn it into a PNG, keep the picture as `name`.svg
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn copy_image(name: &str, svg: &str) {
+    save_file(&format!("{}.svg", name), svg);
+}
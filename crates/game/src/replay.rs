@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+use hex_chess_core::SampleGame;
+
+use crate::{CapturedPieces, GameData, GameState};
+use crate::board::{ChessPiece, PieceAssets, spawn_pieces};
+use crate::hud::GameTimer;
+use crate::settings::{Action, GameConfig};
+use crate::toast::ShowToast;
+
+/// Watching recorded games move by move
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            handle_replay_input,
+            play_sample_replay,
+            sync_sample_replay,
+            update_replay_ui,
+        ).chain().run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Component)]
+pub struct ReplayUI;
+
+/// A sample game from the library being stepped through in the replay viewer
+#[derive(Resource)]
+pub struct SampleReplay {
+    pub game: SampleGame,
+    pub ply: usize,
+    pub playing: bool,   // advancing on its own, at the recorded pace
+    pub speed: f32,      // playback speed multiplier
+    pub elapsed: f32,    // scaled seconds spent on the current move
+}
+
+impl SampleReplay {
+    pub fn new(game: SampleGame) -> Self {
+        Self {
+            game,
+            ply: 0,
+            playing: false,
+            speed: 1.0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Seconds each move takes during playback when the game has no clock readings
+const UNTIMED_REPLAY_STEP: f32 = 1.5;
+
+pub(crate) fn handle_replay_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    replay: Option<ResMut<SampleReplay>>,
+    timer: Option<ResMut<GameTimer>>,
+    config: Res<GameConfig>,
+) {
+    let Some(mut replay) = replay else {
+        return;
+    };
+    let keys = &config.keys;
+    
+    if keys.just_pressed(&keyboard_input, Action::ReplayForward) && replay.ply < replay.game.moves.len() {
+        replay.ply += 1;
+        replay.elapsed = 0.0;
+    }
+    if keys.just_pressed(&keyboard_input, Action::ReplayBack) && replay.ply > 0 {
+        replay.ply -= 1;
+        replay.elapsed = 0.0;
+    }
+    
+    // Play or pause the game at its recorded pace, and change the speed
+    if keys.just_pressed(&keyboard_input, Action::ReplayPlayPause) {
+        replay.playing = !replay.playing && replay.ply < replay.game.moves.len();
+    }
+    if keys.just_pressed(&keyboard_input, Action::ReplayFaster) {
+        replay.speed = (replay.speed * 2.0).min(16.0);
+    }
+    if keys.just_pressed(&keyboard_input, Action::ReplaySlower) {
+        replay.speed = (replay.speed / 2.0).max(0.25);
+    }
+    
+    // Escape leaves the viewer and hands the current position over to the players
+    if keys.just_pressed(&keyboard_input, Action::Back) {
+        commands.remove_resource::<SampleReplay>();
+        if let Some(mut timer) = timer {
+            timer.paused = false;
+        }
+        info!("Leaving sample game replay");
+    }
+}
+
+pub(crate) fn play_sample_replay(
+    replay: Option<ResMut<SampleReplay>>,
+    timer: Option<ResMut<GameTimer>>,
+    time: Res<Time>,
+) {
+    let Some(mut replay) = replay else {
+        return;
+    };
+    
+    if replay.playing {
+        let think = replay.game.moves.get(replay.ply).map(|sample_move| {
+            sample_move.clock.map_or(UNTIMED_REPLAY_STEP, |clock| clock.think_ms as f32 / 1000.0)
+        });
+        match think {
+            Some(think) => {
+                let elapsed = replay.elapsed + time.delta_seconds() * replay.speed;
+                if elapsed >= think {
+                    replay.ply += 1;
+                    replay.elapsed = 0.0;
+                } else {
+                    // Ticking the clock is not a new position, so don't trigger a rebuild
+                    replay.bypass_change_detection().elapsed = elapsed;
+                }
+            }
+            None => replay.playing = false,
+        }
+    }
+    
+    // The live clocks stand still; timed games show the recorded clocks instead
+    let Some(mut timer) = timer else {
+        return;
+    };
+    timer.paused = true;
+    if let Some((white, black)) = replay.game.clocks_at(replay.ply) {
+        timer.white_time = white as f32 / 1000.0;
+        timer.black_time = black as f32 / 1000.0;
+        if replay.ply % 2 == 0 {
+            timer.white_time = (timer.white_time - replay.elapsed).max(0.0);
+        } else {
+            timer.black_time = (timer.black_time - replay.elapsed).max(0.0);
+        }
+    }
+}
+
+pub(crate) fn sync_sample_replay(
+    mut commands: Commands,
+    replay: Option<Res<SampleReplay>>,
+    mut game_data: ResMut<GameData>,
+    mut assets: PieceAssets,
+    piece_query: Query<Entity, With<ChessPiece>>,
+    mut captured_pieces: ResMut<CapturedPieces>,
+) {
+    let Some(replay) = replay else {
+        return;
+    };
+    if !replay.is_changed() {
+        return;
+    }
+    
+    let game = match replay.game.replay_to(replay.ply) {
+        Ok(game) => game,
+        Err(e) => {
+            commands.add(ShowToast::error(format!("Could not replay {}: {}", replay.game.id, e)));
+            return;
+        }
+    };
+    
+    captured_pieces.white.clear();
+    captured_pieces.black.clear();
+    for game_move in &game.move_history {
+        if let Some(captured) = game_move.captured_piece {
+            captured_pieces.add(captured);
+        }
+    }
+    
+    game_data.game = game;
+    game_data.selected_piece = None;
+    game_data.valid_moves.clear();
+    
+    // Rebuild the piece entities from the replayed position
+    for entity in piece_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_pieces(&mut commands, &mut assets.meshes, &mut assets.materials, &assets.layout, &game_data);
+}
+
+pub(crate) fn update_replay_ui(
+    mut commands: Commands,
+    replay: Option<Res<SampleReplay>>,
+    mut ui_query: Query<(Entity, &mut Text), With<ReplayUI>>,
+    config: Res<GameConfig>,
+) {
+    let Some(replay) = replay else {
+        for (entity, _) in ui_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+    if !replay.is_changed() {
+        return;
+    }
+    
+    let comment = replay
+        .ply
+        .checked_sub(1)
+        .and_then(|index| replay.game.moves[index].comment.as_deref())
+        .unwrap_or(&replay.game.description);
+    let playback = if replay.playing { "playing" } else { "paused" };
+    let keys = &config.keys;
+    let content = format!(
+        "{} - move {}/{} ({}, {}x)\n{}\n{}/{} to step forward/back, {} to play/pause, {}/{} for speed, {} to take over",
+        replay.game.title,
+        replay.ply,
+        replay.game.moves.len(),
+        playback,
+        replay.speed,
+        comment,
+        keys.name(Action::ReplayForward),
+        keys.name(Action::ReplayBack),
+        keys.name(Action::ReplayPlayPause),
+        keys.name(Action::ReplayFaster),
+        keys.name(Action::ReplaySlower),
+        keys.name(Action::Back),
+    );
+    
+    if let Ok((_, mut text)) = ui_query.get_single_mut() {
+        text.sections[0].value = content;
+        return;
+    }
+    
+    commands.spawn((
+        TextBundle::from_section(
+            content,
+            TextStyle {
+                font_size: 16.0,
+                color: bevy::prelude::Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            left: Val::Px(20.0),
+            max_width: Val::Px(480.0),
+            ..default()
+        }).with_background_color(bevy::prelude::Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ReplayUI,
+    ));
+}
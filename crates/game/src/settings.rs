@@ -0,0 +1,296 @@
+use bevy::prelude::*;
+use hex_chess_core::Variants;
+use serde::{Deserialize, Serialize};
+
+use crate::platform::{prefers_reduced_motion, read_storage, write_storage};
+
+/// Player settings, saved between sessions
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub timer_minutes: f32, // Timer duration in minutes
+    pub move_seconds: Option<f32>, // cap on each move, None for no cap
+    pub keys: KeyBindings,
+    pub ui_scale: f32,      // multiplier on every HUD and menu size
+    pub piece_scale: f32,   // multiplier on piece size, leaving the board alone
+    pub label_scale: f32,   // multiplier on piece letters, relative to the piece
+    pub reduced_motion: Option<bool>, // None follows the browser's preference
+    pub variant_id: String, // variant new games are played in
+    pub piece_theme: String, // id of a built-in piece theme or an installed pack
+    pub render_3d: bool,    // draw the board in 3D with an orbiting camera
+    pub image_labels: bool, // name the files and ranks in copied board images
+    pub image_from_mover: bool, // turn copied board images to the side to move
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            timer_minutes: 10.0, // Default 10 minutes per player
+            move_seconds: None,
+            keys: KeyBindings::default(),
+            ui_scale: 1.0,
+            piece_scale: 1.0,
+            label_scale: 1.0,
+            reduced_motion: None,
+            variant_id: PLAYABLE_VARIANTS[0].to_string(),
+            piece_theme: crate::piece_theme::BUILT_IN_THEMES[0].0.to_string(),
+            render_3d: false,
+            image_labels: true,
+            image_from_mover: false,
+        }
+    }
+}
+
+/// Range and step of the UI scale setting
+pub(crate) const UI_SCALE_RANGE: (f32, f32, f32) = (0.75, 2.0, 0.25);
+
+/// Range and step of the piece and label size settings
+pub(crate) const PIECE_SCALE_RANGE: (f32, f32, f32) = (0.6, 1.6, 0.1);
+
+/// Step a scale setting up or down within its range
+pub(crate) fn step_scale(value: f32, up: bool, (min, max, step): (f32, f32, f32)) -> f32 {
+    let value = if up { value + step } else { value - step };
+    // Round so repeated steps don't drift off the grid
+    ((value / step).round() * step).clamp(min, max)
+}
+
+/// Per-move limits the menu steps through
+const MOVE_LIMITS: [Option<f32>; 4] = [None, Some(10.0), Some(30.0), Some(60.0)];
+
+/// Variants the board can play, by id, in the order the menu steps through them
+const PLAYABLE_VARIANTS: [&str; 4] = ["glinski", "glinski_crazyhouse", "glinski_atomic", "glinski_dark"];
+
+/// Storage key (web) or file name (native) for saved settings
+const SETTINGS_KEY: &str = "hex-chess-settings";
+
+impl GameConfig {
+    /// Load saved settings, falling back to the defaults
+    pub fn load() -> Self {
+        read_storage(SETTINGS_KEY)
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+    
+    /// Whether to leave out effects and autoplay that move on their own
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion.unwrap_or_else(prefers_reduced_motion)
+    }
+    
+    /// Variant new games are played in
+    pub fn variant(&self) -> hex_chess_core::VariantConfig {
+        Variants::find(&self.variant_id).unwrap_or_else(Variants::glinski_chess)
+    }
+    
+    /// Step to the next playable variant
+    pub fn cycle_variant(&mut self) {
+        let at = PLAYABLE_VARIANTS.iter().position(|&id| id == self.variant_id);
+        let next = at.map_or(0, |at| (at + 1) % PLAYABLE_VARIANTS.len());
+        self.variant_id = PLAYABLE_VARIANTS[next].to_string();
+    }
+    
+    /// Step to the next per-move time limit
+    pub fn cycle_move_limit(&mut self) {
+        let at = MOVE_LIMITS.iter().position(|&limit| limit == self.move_seconds);
+        let next = at.map_or(0, |at| (at + 1) % MOVE_LIMITS.len());
+        self.move_seconds = MOVE_LIMITS[next];
+    }
+    
+    /// Step the reduced motion setting through follow-the-browser, on and off
+    pub fn cycle_reduced_motion(&mut self) {
+        self.reduced_motion = match self.reduced_motion {
+            None => Some(true),
+            Some(true) => Some(false),
+            Some(false) => None,
+        };
+    }
+    
+    /// Step through the board image styles: labels on or off, White or the side to move at the bottom
+    pub fn cycle_board_image(&mut self) {
+        (self.image_labels, self.image_from_mover) = match (self.image_labels, self.image_from_mover) {
+            (true, false) => (true, true),
+            (true, true) => (false, false),
+            (false, false) => (false, true),
+            (false, true) => (true, false),
+        };
+    }
+    
+    pub fn save(&self) {
+        match serde_json::to_string(self) {
+            Ok(data) => write_storage(SETTINGS_KEY, &data),
+            Err(e) => {
+                info!("Could not save settings: {}", e);
+            }
+        }
+    }
+}
+
+/// Something the player does from the keyboard with a remappable key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    ToggleMenu,
+    Confirm,
+    Back,
+    ShowRules,
+    WatchSamples,
+    ResetCamera,
+    ReplayForward,
+    ReplayBack,
+    ReplayPlayPause,
+    ReplaySlower,
+    ReplayFaster,
+    ReducedMotion,
+    Training,
+    Variant,
+    NextDrop,
+    Promotion,
+    History,
+    DeleteGame,
+    ExportGame,
+    RebindKeys,
+    ResetSettings,
+    MoveTimeLimit,
+    PieceTheme,
+    Board3d,
+    CopyBoardImage,
+    BoardImageStyle,
+}
+
+impl Action {
+    pub const ALL: [Action; 26] = [
+        Action::ToggleMenu,
+        Action::Confirm,
+        Action::Back,
+        Action::ShowRules,
+        Action::WatchSamples,
+        Action::ResetCamera,
+        Action::ReplayForward,
+        Action::ReplayBack,
+        Action::ReplayPlayPause,
+        Action::ReplaySlower,
+        Action::ReplayFaster,
+        Action::ReducedMotion,
+        Action::Training,
+        Action::Variant,
+        Action::NextDrop,
+        Action::Promotion,
+        Action::History,
+        Action::DeleteGame,
+        Action::ExportGame,
+        Action::RebindKeys,
+        Action::ResetSettings,
+        Action::MoveTimeLimit,
+        Action::PieceTheme,
+        Action::Board3d,
+        Action::CopyBoardImage,
+        Action::BoardImageStyle,
+    ];
+    
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::ToggleMenu => "Open/close menu",
+            Action::Confirm => "Start / new game",
+            Action::Back => "Back",
+            Action::ShowRules => "View rules",
+            Action::WatchSamples => "Watch sample games",
+            Action::ResetCamera => "Reset camera",
+            Action::ReplayForward => "Replay: step forward",
+            Action::ReplayBack => "Replay: step back",
+            Action::ReplayPlayPause => "Replay: play/pause",
+            Action::ReplaySlower => "Replay: slower",
+            Action::ReplayFaster => "Replay: faster",
+            Action::ReducedMotion => "Reduced motion",
+            Action::Training => "Training drills",
+            Action::Variant => "Switch variant",
+            Action::NextDrop => "Pick a piece to drop",
+            Action::Promotion => "Change promotion piece",
+            Action::History => "Game history",
+            Action::DeleteGame => "History: delete game",
+            Action::ExportGame => "History: export game",
+            Action::RebindKeys => "Rebind keys",
+            Action::ResetSettings => "Reset settings",
+            Action::MoveTimeLimit => "Time limit per move",
+            Action::PieceTheme => "Piece theme",
+            Action::Board3d => "2D/3D board",
+            Action::CopyBoardImage => "Copy board image",
+            Action::BoardImageStyle => "Board image style",
+        }
+    }
+    
+    fn default_key(self) -> KeyCode {
+        match self {
+            Action::ToggleMenu => KeyCode::KeyM,
+            Action::Confirm => KeyCode::Space,
+            Action::Back => KeyCode::Escape,
+            Action::ShowRules => KeyCode::KeyR,
+            Action::WatchSamples => KeyCode::KeyW,
+            Action::ResetCamera => KeyCode::KeyR,
+            Action::ReplayForward => KeyCode::KeyN,
+            Action::ReplayBack => KeyCode::KeyB,
+            Action::ReplayPlayPause => KeyCode::KeyP,
+            Action::ReplaySlower => KeyCode::BracketLeft,
+            Action::ReplayFaster => KeyCode::BracketRight,
+            Action::ReducedMotion => KeyCode::KeyO,
+            Action::Training => KeyCode::KeyT,
+            Action::Variant => KeyCode::KeyH,
+            Action::NextDrop => KeyCode::Tab,
+            Action::Promotion => KeyCode::KeyU,
+            Action::History => KeyCode::KeyG,
+            Action::DeleteGame => KeyCode::Delete,
+            Action::ExportGame => KeyCode::KeyE,
+            Action::RebindKeys => KeyCode::KeyK,
+            Action::ResetSettings => KeyCode::Backspace,
+            Action::MoveTimeLimit => KeyCode::KeyL,
+            Action::PieceTheme => KeyCode::KeyI,
+            Action::Board3d => KeyCode::KeyD,
+            Action::CopyBoardImage => KeyCode::KeyC,
+            Action::BoardImageStyle => KeyCode::KeyJ,
+        }
+    }
+}
+
+/// Keys bound to each action; actions missing from saved settings keep their default
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyBindings {
+    keys: std::collections::HashMap<Action, KeyCode>,
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys.get(&action).copied().unwrap_or_else(|| action.default_key())
+    }
+    
+    pub fn set(&mut self, action: Action, key: KeyCode) {
+        self.keys.insert(action, key);
+    }
+    
+    pub fn just_pressed(&self, input: &ButtonInput<KeyCode>, action: Action) -> bool {
+        input.just_pressed(self.key(action))
+    }
+    
+    /// Short name of the key bound to an action, for on-screen hints
+    pub fn name(&self, action: Action) -> String {
+        match self.key(action) {
+            KeyCode::Space => "SPACE".to_string(),
+            KeyCode::Escape => "ESC".to_string(),
+            KeyCode::BracketLeft => "[".to_string(),
+            KeyCode::BracketRight => "]".to_string(),
+            key => {
+                let name = format!("{:?}", key);
+                let short = name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")).unwrap_or(&name);
+                short.to_uppercase()
+            }
+        }
+    }
+}
+
+/// Walks through `Action::ALL`, binding each to the next key pressed
+#[derive(Resource, Default)]
+pub struct KeyRebinding {
+    pub next: usize,
+}
+
+pub(crate) fn save_settings(config: Res<GameConfig>) {
+    if config.is_changed() && !config.is_added() {
+        config.save();
+    }
+}
@@ -0,0 +1,201 @@
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+
+/// Seconds a toast stays up unless it is clicked away first
+pub const TOAST_SECONDS: f32 = 6.0;
+/// Toasts shown at once; older ones drop off the bottom of the stack
+pub const MAX_TOASTS: usize = 4;
+
+/// Short messages stacked in the corner of the screen, whatever state the game is in
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Toasts>()
+            .add_systems(Update, (expire_toasts, dismiss_toasts, update_toast_stack).chain());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+impl ToastKind {
+    fn background(self) -> Color {
+        match self {
+            ToastKind::Info => Color::srgba(0.12, 0.14, 0.2, 0.92),
+            ToastKind::Error => Color::srgba(0.45, 0.1, 0.1, 0.92),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub text: String,
+    pub remaining: f32, // seconds until it goes away on its own
+}
+
+/// Messages waiting to be read, newest first
+#[derive(Resource, Default)]
+pub struct Toasts {
+    pub shown: Vec<Toast>,
+    next_id: u64,
+}
+
+impl Toasts {
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Info, text.into());
+    }
+    
+    /// Show a failure, and log it too so it still turns up in bug reports
+    pub fn error(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        info!("{}", text);
+        self.push(ToastKind::Error, text);
+    }
+    
+    fn push(&mut self, kind: ToastKind, text: String) {
+        self.next_id += 1;
+        self.shown.insert(0, Toast { id: self.next_id, kind, text, remaining: TOAST_SECONDS });
+        self.shown.truncate(MAX_TOASTS);
+    }
+    
+    pub fn dismiss(&mut self, id: u64) {
+        self.shown.retain(|toast| toast.id != id);
+    }
+}
+
+/// Queue a toast from a system that only has `Commands` to hand
+///
+/// ```ignore
+/// commands.add(ShowToast::error(format!("Could not export game: {}", e)));
+/// ```
+pub struct ShowToast {
+    pub kind: ToastKind,
+    pub text: String,
+}
+
+impl ShowToast {
+    pub fn info(text: impl Into<String>) -> Self {
+        Self { kind: ToastKind::Info, text: text.into() }
+    }
+    
+    pub fn error(text: impl Into<String>) -> Self {
+        Self { kind: ToastKind::Error, text: text.into() }
+    }
+}
+
+impl Command for ShowToast {
+    fn apply(self, world: &mut World) {
+        let mut toasts = world.get_resource_or_insert_with(Toasts::default);
+        match self.kind {
+            ToastKind::Info => toasts.info(self.text),
+            ToastKind::Error => toasts.error(self.text),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ToastStack;
+
+#[derive(Component)]
+pub struct ToastCard {
+    pub id: u64,
+}
+
+pub(crate) fn expire_toasts(mut toasts: ResMut<Toasts>, time: Res<Time>) {
+    if toasts.shown.is_empty() {
+        return;
+    }
+    // Counting down doesn't change what is drawn, so it mustn't mark the stack for a rebuild
+    let delta = time.delta_seconds();
+    for toast in &mut toasts.bypass_change_detection().shown {
+        toast.remaining -= delta;
+    }
+    if toasts.shown.iter().any(|toast| toast.remaining <= 0.0) {
+        toasts.shown.retain(|toast| toast.remaining > 0.0);
+    }
+}
+
+pub(crate) fn dismiss_toasts(
+    mut toasts: ResMut<Toasts>,
+    cards: Query<(&ToastCard, &Interaction), Changed<Interaction>>,
+) {
+    for (card, interaction) in cards.iter() {
+        if *interaction == Interaction::Pressed {
+            toasts.dismiss(card.id);
+        }
+    }
+}
+
+pub(crate) fn update_toast_stack(
+    mut commands: Commands,
+    toasts: Res<Toasts>,
+    stack_query: Query<Entity, With<ToastStack>>,
+) {
+    if !toasts.is_changed() || toasts.shown.is_empty() && stack_query.is_empty() {
+        return;
+    }
+    for entity in stack_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if toasts.shown.is_empty() {
+        return;
+    }
+    
+    // Above the game and the menus, below a confirmation that needs an answer
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(16.0),
+                bottom: Val::Px(16.0),
+                max_width: Val::Px(360.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(2500),
+            ..default()
+        },
+        ToastStack,
+    )).with_children(|parent| {
+        for toast in &toasts.shown {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(14.0), Val::Px(10.0)),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    background_color: toast.kind.background().into(),
+                    ..default()
+                },
+                Interaction::default(),
+                ToastCard { id: toast.id },
+            )).with_children(|card| {
+                card.spawn(TextBundle::from_section(
+                    toast.text.clone(),
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::srgb(0.95, 0.95, 0.95),
+                        ..default()
+                    },
+                ));
+                card.spawn(TextBundle::from_section(
+                    "Click to dismiss",
+                    TextStyle {
+                        font_size: 12.0,
+                        color: Color::srgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                ));
+            });
+        }
+    });
+}
@@ -0,0 +1,187 @@
+//! The client run headlessly, on MinimalPlugins with no window or GPU: clicks and key
+//! presses go in as input events and the tests read back the game and the HUD
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::{ButtonState, InputPlugin};
+use bevy::render::camera::CameraPlugin;
+use bevy::state::app::StatesPlugin;
+use bevy::window::{ExitCondition, PrimaryWindow};
+use bevy::prelude::*;
+use hex_chess_core::{piece_letter, Color as ChessColor, HexCoord, PieceType};
+use hex_chess_game::{BoardLayout, CapturedPieces, CapturedPiecesUI, ChessPiece, GameData, GameState, HexChessPlugin, Mode};
+
+fn headless_app(mode: Mode) -> App {
+    // Settings and finished games are written to a scratch home, not the real one
+    std::env::set_var("HOME", std::env::temp_dir().join("hex-chess-headless"));
+    let _ = std::fs::create_dir_all(std::env::temp_dir().join("hex-chess-headless"));
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        HierarchyPlugin,
+        InputPlugin,
+        StatesPlugin,
+        AssetPlugin::default(),
+        WindowPlugin {
+            primary_window: Some(Window::default()),
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        },
+        CameraPlugin,
+    ));
+    app.init_asset::<Mesh>()
+        .init_asset::<Image>()
+        .init_asset::<Font>()
+        .init_asset::<ColorMaterial>()
+        .init_asset::<StandardMaterial>()
+        .init_resource::<UiScale>();
+    app.add_plugins(HexChessPlugin::new().mode(mode));
+    app.update();
+    app.update();
+    app
+}
+
+fn window(app: &mut App) -> Entity {
+    app.world_mut().query_filtered::<Entity, With<PrimaryWindow>>().single(app.world())
+}
+
+fn cell(name: &str) -> HexCoord {
+    let mut chars = name.chars();
+    let file = chars.next().unwrap();
+    HexCoord::from_file_rank(file, chars.as_str().parse().unwrap()).unwrap()
+}
+
+/// Point at a cell and click it with the left button
+fn click(app: &mut App, coord: HexCoord) {
+    let camera = app.world().resource::<GameData>().camera_entity;
+    let world = app.world().resource::<BoardLayout>().board_to_world(coord);
+    let (camera, transform) = app.world_mut().query::<(&Camera, &GlobalTransform)>().get(app.world(), camera).unwrap();
+    let cursor = camera.world_to_viewport(transform, world.extend(0.0)).unwrap();
+
+    let window = window(app);
+    app.world_mut().get_mut::<Window>(window).unwrap().set_cursor_position(Some(cursor));
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().send_event(MouseButtonInput { button: MouseButton::Left, state, window });
+        app.update();
+    }
+}
+
+fn press(app: &mut App, key_code: KeyCode, logical_key: Key) {
+    let window = window(app);
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().send_event(KeyboardInput { key_code, logical_key: logical_key.clone(), state, window });
+        app.update();
+    }
+}
+
+/// Play a move the way a player would: click the piece, then where it goes
+fn play(app: &mut App, from: &str, to: &str) {
+    click(app, cell(from));
+    click(app, cell(to));
+}
+
+fn state(app: &App) -> GameState {
+    app.world().resource::<State<GameState>>().get().clone()
+}
+
+fn piece_entities(app: &mut App) -> Vec<ChessPiece> {
+    let mut query = app.world_mut().query::<&ChessPiece>();
+    query.iter(app.world()).map(|piece| ChessPiece { coord: piece.coord, piece: piece.piece }).collect()
+}
+
+#[test]
+fn test_menu_starts_a_game() {
+    let mut app = headless_app(Mode::Menu);
+    assert_eq!(state(&app), GameState::Menu);
+
+    press(&mut app, KeyCode::Space, Key::Space);
+    app.update();
+    assert_eq!(state(&app), GameState::Playing);
+}
+
+#[test]
+fn test_local_mode_opens_on_the_board() {
+    let mut app = headless_app(Mode::Local);
+    assert_eq!(state(&app), GameState::Playing);
+
+    let game_data = app.world().resource::<GameData>();
+    assert_eq!(game_data.game.current_player, ChessColor::White);
+    let on_board = game_data.game.board.pieces.len();
+    assert_eq!(piece_entities(&mut app).len(), on_board);
+}
+
+#[test]
+fn test_click_selects_then_moves() {
+    let mut app = headless_app(Mode::Local);
+
+    click(&mut app, cell("b5"));
+    let game_data = app.world().resource::<GameData>();
+    assert_eq!(game_data.selected_piece, Some(cell("b5")));
+    assert!(game_data.valid_moves.contains(&cell("b6")));
+
+    click(&mut app, cell("b6"));
+    let game_data = app.world().resource::<GameData>();
+    assert_eq!(game_data.selected_piece, None);
+    assert!(game_data.valid_moves.is_empty());
+    assert_eq!(game_data.game.current_player, ChessColor::Black);
+    assert_eq!(game_data.game.board.get_piece(cell("b6")).map(|piece| piece.piece_type), Some(PieceType::Pawn));
+
+    // The pawn's sprite followed it
+    let pieces = piece_entities(&mut app);
+    assert!(pieces.iter().any(|piece| piece.coord == cell("b6")));
+    assert!(!pieces.iter().any(|piece| piece.coord == cell("b5")));
+}
+
+#[test]
+fn test_clicking_the_other_side_selects_nothing() {
+    let mut app = headless_app(Mode::Local);
+
+    click(&mut app, cell("g7"));
+    let game_data = app.world().resource::<GameData>();
+    assert_eq!(game_data.selected_piece, None);
+    assert!(game_data.valid_moves.is_empty());
+}
+
+#[test]
+fn test_capture_fills_the_capture_panel() {
+    let mut app = headless_app(Mode::Local);
+    let before = piece_entities(&mut app).len();
+
+    play(&mut app, "b5", "b6");
+    play(&mut app, "g7", "g5");
+    play(&mut app, "f5", "g5");
+
+    let game_data = app.world().resource::<GameData>();
+    assert_eq!(game_data.game.board.get_piece(cell("g5")).map(|piece| piece.color), Some(ChessColor::White));
+    let captured = app.world().resource::<CapturedPieces>();
+    assert!(captured.white.is_empty());
+    assert_eq!(captured.black.iter().map(|piece| piece.piece_type).collect::<Vec<_>>(), vec![PieceType::Pawn]);
+    assert_eq!(piece_entities(&mut app).len(), before - 1);
+
+    // Black's losses are listed on the panel, White's stays empty
+    let mut panels = app.world_mut().query::<(&Text, &CapturedPiecesUI)>();
+    for (text, panel) in panels.iter(app.world()) {
+        let expected = match panel.color {
+            ChessColor::White => String::new(),
+            ChessColor::Black => piece_letter(PieceType::Pawn).to_string(),
+        };
+        assert_eq!(text.sections[0].value, expected);
+    }
+}
+
+#[test]
+fn test_escape_pauses_and_resumes() {
+    let mut app = headless_app(Mode::Local);
+    play(&mut app, "b5", "b6");
+
+    press(&mut app, KeyCode::Escape, Key::Escape);
+    assert_eq!(state(&app), GameState::Playing);
+    click(&mut app, cell("g7"));
+    assert_eq!(app.world().resource::<GameData>().selected_piece, None, "the board ignores clicks while paused");
+
+    press(&mut app, KeyCode::Escape, Key::Escape);
+    click(&mut app, cell("g7"));
+    assert_eq!(app.world().resource::<GameData>().selected_piece, Some(cell("g7")));
+}
@@ -0,0 +1,123 @@
+use hex_chess_core::PieceType;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Default time events are kept for, when AUDIT_RETENTION_SECS is not set
+const DEFAULT_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// Something that happened in a room, as recorded in its audit log
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditKind {
+    Join { player_name: String, variant: String },
+    JoinRejected { reason: String },
+    Spectate { spectator_name: String },
+    Leave,
+    Disconnect,
+    /// A move, and whether it was passed on to the other players
+    Move { from: (i32, i32), to: (i32, i32), outcome: RelayOutcome },
+    /// A drop from the reserve, and whether it was passed on
+    Drop { piece: PieceType, to: (i32, i32), outcome: RelayOutcome },
+    /// The player to move ran out of time without moving, and lost on the clock
+    Flagged,
+    /// A player asked for a rematch
+    Rematch,
+    /// A client's position hash disagreed with the server's after `ply` moves, and it was sent the game
+    Resync { ply: usize },
+    /// WebRTC offer, answer or ICE candidate sent to one player
+    Signal { signal: &'static str, target_player: String, outcome: RelayOutcome },
+}
+
+/// What the server did with a message meant for other players
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum RelayOutcome {
+    Relayed { recipients: usize },
+    NoSuchRoom,
+    NoSuchPlayer,
+    /// Dropped: the sender moved twice in a row
+    OutOfTurn,
+    /// Dropped: the sender's clock had run out
+    Flagged,
+    /// Dropped: the move broke the rules, or the game was over
+    Illegal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    /// Milliseconds since the Unix epoch
+    pub at_ms: u64,
+    pub player_id: String,
+    #[serde(flatten)]
+    pub kind: AuditKind,
+}
+
+/// Timestamped room events, kept for a fixed window
+#[derive(Debug)]
+pub struct AuditLog {
+    retention: Duration,
+    rooms: HashMap<String, VecDeque<AuditEvent>>,
+}
+
+impl AuditLog {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            rooms: HashMap::new(),
+        }
+    }
+
+    /// Retention from AUDIT_RETENTION_SECS, or an hour
+    pub fn from_env() -> Self {
+        let retention = std::env::var("AUDIT_RETENTION_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_RETENTION);
+        Self::new(retention)
+    }
+
+    pub fn record(&mut self, room_id: &str, player_id: &str, kind: AuditKind) {
+        let at_ms = now_ms();
+        tracing::debug!(room_id, player_id, ?kind, "audit");
+        self.rooms
+            .entry(room_id.to_string())
+            .or_default()
+            .push_back(AuditEvent {
+                at_ms,
+                player_id: player_id.to_string(),
+                kind,
+            });
+        self.prune(at_ms);
+    }
+
+    /// Events for a room still inside the retention window, oldest first
+    pub fn events(&self, room_id: &str) -> Vec<AuditEvent> {
+        let cutoff = now_ms().saturating_sub(self.retention.as_millis() as u64);
+        self.rooms
+            .get(room_id)
+            .map(|events| events.iter().filter(|event| event.at_ms >= cutoff).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop expired events, and rooms left with none
+    fn prune(&mut self, now: u64) {
+        let cutoff = now.saturating_sub(self.retention.as_millis() as u64);
+        self.rooms.retain(|_, events| {
+            while events.front().is_some_and(|event| event.at_ms < cutoff) {
+                events.pop_front();
+            }
+            !events.is_empty()
+        });
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
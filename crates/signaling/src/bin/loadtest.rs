@@ -0,0 +1,233 @@
+//! Load test for the signaling server: opens many rooms at once and replays the
+//! bundled sample games through each, two WebSocket clients per room
+//!
+//! Usage: loadtest [--url ws://127.0.0.1:3001/ws] [--rooms 500] [--rate 2]
+//!
+//! `--rate` is moves per second in each room. Latency is measured from a move
+//! being sent to the opponent receiving it.
+
+use futures::{SinkExt, StreamExt};
+use hex_chess_core::{GameLibrary, SampleGame, SampleMove, VariantRegistry};
+use hex_chess_signaling::SignalingMessage;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::{net::TcpStream, task::JoinSet};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How long a client waits for a relayed move before counting it lost
+const RELAY_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Options {
+    url: String,
+    rooms: usize,
+    rate: f64,
+}
+
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Self { url: "ws://127.0.0.1:3001/ws".to_string(), rooms: 500, rate: 2.0 };
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+            match flag.as_str() {
+                "--url" => options.url = value,
+                "--rooms" => options.rooms = value.parse().map_err(|_| format!("Bad room count {}", value))?,
+                "--rate" => options.rate = value.parse().map_err(|_| format!("Bad rate {}", value))?,
+                _ => return Err(format!("Unknown option {}", flag)),
+            }
+        }
+        if options.rate <= 0.0 {
+            return Err("Rate must be above zero".to_string());
+        }
+        Ok(options)
+    }
+}
+
+/// What happened in one room
+#[derive(Default)]
+struct RoomReport {
+    latencies: Vec<Duration>,
+    sent: usize,
+    /// Error messages from the server and moves that never arrived
+    errors: usize,
+}
+
+/// One side of a room
+struct Client {
+    socket: Socket,
+    errors: usize,
+}
+
+impl Client {
+    async fn connect(url: &str) -> Result<Self, String> {
+        let (socket, _) = connect_async(url).await.map_err(|e| format!("Can't connect: {}", e))?;
+        Ok(Self { socket, errors: 0 })
+    }
+
+    async fn send(&mut self, message: &SignalingMessage) -> Result<(), String> {
+        let json = serde_json::to_string(message).map_err(|e| e.to_string())?;
+        self.socket.send(Message::Text(json)).await.map_err(|e| format!("Send failed: {}", e))
+    }
+
+    /// Wait for the first message `pick` accepts, counting any errors on the way
+    async fn expect(&mut self, pick: impl Fn(&SignalingMessage) -> bool) -> Result<(), String> {
+        let wait = async {
+            while let Some(frame) = self.socket.next().await {
+                let Ok(Message::Text(text)) = frame else {
+                    continue;
+                };
+                match serde_json::from_str::<SignalingMessage>(&text) {
+                    Ok(SignalingMessage::Error { .. }) => self.errors += 1,
+                    Ok(message) if pick(&message) => return Ok(()),
+                    _ => {}
+                }
+            }
+            Err("Server closed the connection".to_string())
+        };
+        tokio::time::timeout(RELAY_TIMEOUT, wait).await.map_err(|_| "Timed out".to_string())?
+    }
+}
+
+fn relayed_message(room_id: &str, sample_move: &SampleMove) -> SignalingMessage {
+    let to = (sample_move.to.q, sample_move.to.r);
+    match sample_move.drop {
+        Some(piece) => SignalingMessage::GameDrop { room_id: room_id.to_string(), piece, to },
+        None => SignalingMessage::GameMove {
+            room_id: room_id.to_string(),
+            from: (sample_move.from.q, sample_move.from.r),
+            to,
+            promotion: sample_move.promotion,
+        },
+    }
+}
+
+/// Seat two players in a fresh room and play `game` through it at `rate` moves per second
+async fn replay_room(url: String, room_id: String, game: SampleGame, rate: f64) -> Result<RoomReport, String> {
+    // The server knows variants by id rather than display name
+    let variant = VariantRegistry::new()
+        .find_by_name(&game.variant)
+        .map(|(id, _)| id.to_string())
+        .ok_or_else(|| format!("Unknown variant {}", game.variant))?;
+    let mut players = [Client::connect(&url).await?, Client::connect(&url).await?];
+    // White must be seated before Black asks to join
+    for (seat, name) in players.iter_mut().zip(["White", "Black"]) {
+        seat.send(&SignalingMessage::JoinRoom {
+            room_id: room_id.clone(),
+            variant: variant.clone(),
+            player_name: name.to_string(),
+            time_control: None,
+            best_of: None,
+        })
+        .await?;
+        seat.expect(|message| matches!(message, SignalingMessage::RoomInfo { .. })).await?;
+    }
+
+    let mut report = RoomReport::default();
+    let pace = Duration::from_secs_f64(1.0 / rate);
+    for (ply, sample_move) in game.moves.iter().enumerate() {
+        let [white, black] = &mut players;
+        let (mover, watcher) = if ply % 2 == 0 { (white, black) } else { (black, white) };
+        let sent_at = Instant::now();
+        mover.send(&relayed_message(&room_id, sample_move)).await?;
+        report.sent += 1;
+        let relayed = |message: &SignalingMessage| {
+            matches!(message, SignalingMessage::GameMove { .. } | SignalingMessage::GameDrop { .. })
+        };
+        match watcher.expect(relayed).await {
+            Ok(()) => report.latencies.push(sent_at.elapsed()),
+            // A lost move leaves the room out of step, so stop here
+            Err(_) => {
+                report.errors += 1;
+                break;
+            }
+        }
+        tokio::time::sleep(pace.saturating_sub(sent_at.elapsed())).await;
+    }
+    report.errors += players.iter().map(|player| player.errors).sum::<usize>();
+    Ok(report)
+}
+
+/// The latency below which `fraction` of the samples fall
+fn percentile(sorted: &[Duration], fraction: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    sorted[((sorted.len() - 1) as f64 * fraction).round() as usize]
+}
+
+#[tokio::main]
+async fn main() {
+    let options = match Options::parse() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\nUsage: loadtest [--url ws://127.0.0.1:3001/ws] [--rooms 500] [--rate 2]", e);
+            std::process::exit(2);
+        }
+    };
+    let games: Vec<SampleGame> = GameLibrary::list()
+        .iter()
+        .filter_map(|info| GameLibrary::load(&info.id).ok())
+        .collect();
+    if games.is_empty() {
+        eprintln!("No sample games to replay");
+        std::process::exit(1);
+    }
+
+    // Rooms are never cleared out, so every run gets names of its own
+    let run = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_millis()).unwrap_or(0);
+    println!(
+        "Replaying {} games across {} rooms ({} connections) at {} moves/s",
+        games.len(),
+        options.rooms,
+        options.rooms * 2,
+        options.rate
+    );
+
+    let started = Instant::now();
+    let mut rooms = JoinSet::new();
+    for index in 0..options.rooms {
+        let room_id = format!("loadtest-{}-{}", run, index);
+        let game = games[index % games.len()].clone();
+        rooms.spawn(replay_room(options.url.clone(), room_id, game, options.rate));
+    }
+
+    let mut latencies = Vec::new();
+    let (mut sent, mut errors, mut failed) = (0, 0, 0);
+    while let Some(result) = rooms.join_next().await {
+        match result {
+            Ok(Ok(report)) => {
+                latencies.extend(report.latencies);
+                sent += report.sent;
+                errors += report.errors;
+            }
+            Ok(Err(e)) => {
+                eprintln!("Room failed: {}", e);
+                failed += 1;
+            }
+            Err(e) => {
+                eprintln!("Room task panicked: {}", e);
+                failed += 1;
+            }
+        }
+    }
+    latencies.sort();
+
+    println!("Finished in {:.1}s", started.elapsed().as_secs_f64());
+    println!("Rooms: {} completed, {} failed", options.rooms - failed, failed);
+    println!(
+        "Moves: {} sent, {} relayed, {} errors ({:.2}%)",
+        sent,
+        latencies.len(),
+        errors,
+        100.0 * errors as f64 / sent.max(1) as f64
+    );
+    let ms = |latency: Duration| latency.as_secs_f64() * 1000.0;
+    println!(
+        "Relay latency: p50 {:.1}ms, p90 {:.1}ms, p99 {:.1}ms, max {:.1}ms",
+        ms(percentile(&latencies, 0.5)),
+        ms(percentile(&latencies, 0.9)),
+        ms(percentile(&latencies, 0.99)),
+        ms(latencies.last().copied().unwrap_or_default())
+    );
+}
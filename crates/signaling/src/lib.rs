@@ -0,0 +1,1081 @@
+pub mod audit;
+
+use audit::{AuditKind, AuditLog, RelayOutcome};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+    routing::get,
+    Router,
+};
+use futures::{sink::SinkExt, stream::StreamExt};
+use hex_chess_core::{board_svg, Color, FogView, GameResult, GameSession, HexCoord, MatchSeries, MoveOutcome, PieceType, SampleGame, SessionError, TimeControl, VariantRegistry};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{broadcast, RwLock};
+use tower_http::cors::CorsLayer;
+use uuid::Uuid;
+
+/// Everything clients and the server say to each other over the WebSocket, one JSON
+/// object per text frame; the same type goes both ways
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub enum SignalingMessage {
+    /// Client wants to join a game room
+    JoinRoom {
+        room_id: String,
+        variant: String,
+        player_name: String,
+        /// Sets the room's time control when creating it; must match when joining
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+        /// Makes the room a best-of-N match when creating it; rooms without one
+        /// rematch for as long as the players like
+        #[serde(default)]
+        best_of: Option<u32>,
+    },
+    /// Sent to a player on joining: what the room plays
+    RoomInfo {
+        room_id: String,
+        variant: String,
+        time_control: Option<TimeControl>,
+    },
+    /// A player's clock ran out, whether they then tried to move or not; they lose on time
+    Timeout {
+        room_id: String,
+        player_id: String,
+    },
+    /// Client wants to watch a game room without playing
+    Spectate {
+        room_id: String,
+        spectator_name: String,
+    },
+    /// Client is leaving a room
+    LeaveRoom {
+        room_id: String,
+    },
+    /// WebRTC offer
+    Offer {
+        room_id: String,
+        target_player: String,
+        sdp: String,
+    },
+    /// WebRTC answer
+    Answer {
+        room_id: String,
+        target_player: String,
+        sdp: String,
+    },
+    /// ICE candidate
+    IceCandidate {
+        room_id: String,
+        target_player: String,
+        candidate: String,
+    },
+    /// Game move
+    GameMove {
+        room_id: String,
+        from: (i32, i32),
+        to: (i32, i32),
+        /// Piece a pawn reaching the far edge becomes
+        #[serde(default)]
+        promotion: Option<PieceType>,
+    },
+    /// Drop of a piece from the mover's reserve, in variants with drops
+    GameDrop {
+        room_id: String,
+        piece: PieceType,
+        to: (i32, i32),
+    },
+    /// What a player can see in a fog-of-war room, sent after every move in place of the move itself
+    FogView {
+        room_id: String,
+        view: FogView,
+    },
+    /// A client's hash of its position after `ply` moves, sent after each move it plays
+    /// or receives; the server checks it against the refereed game
+    PositionHash {
+        room_id: String,
+        ply: usize,
+        hash: u64,
+    },
+    /// Every move of the refereed game, sent to anyone arriving mid-game and to a
+    /// client whose position hash didn't match; replaying them replaces the client's copy
+    Resync {
+        room_id: String,
+        game: SampleGame,
+    },
+    /// A player asks to play again, colours swapped, once the game is over; the others
+    /// hear the request, and the new game starts when both players have asked
+    Rematch {
+        room_id: String,
+    },
+    /// The players' score across the room's games, sent to everyone in the room when
+    /// a game ends and when a rematch starts
+    SeriesUpdate {
+        room_id: String,
+        series: MatchSeries,
+        /// Player ids in the series' order: White in the first game, then Black
+        players: Vec<String>,
+        /// Id of the player who has won the match, once one has
+        winner: Option<String>,
+        /// Whether the match is over; no more rematches are played
+        decided: bool,
+        /// How the game that just ended came out, as refereed; None as a rematch starts
+        result: Option<GameResult>,
+    },
+    /// A request the server refused
+    Error {
+        code: ErrorCode,
+        /// Explanation for people; clients should act on the code
+        #[serde(default)]
+        message: Option<String>,
+    },
+    /// Success message
+    Success {
+        message: String,
+    },
+}
+
+/// Why the server refused a request, for clients to act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    RoomFull,
+    AlreadyInRoom,
+    RoomNotFound,
+    UnknownVariant,
+    /// The room plays a different time control than the joiner asked for
+    TimeControlMismatch,
+    /// Spectators can't move
+    NotAPlayer,
+    NotYourTurn,
+    InvalidMove,
+    /// Fog-of-war rooms never share whole positions
+    StateWithheld,
+    /// Rematches wait for the game to end
+    GameInProgress,
+    /// The room's best-of-N match has been decided
+    SeriesOver,
+    UnknownMessage,
+}
+
+/// A refused request: what kind of refusal, and why in words
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refusal {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl Refusal {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl From<Refusal> for SignalingMessage {
+    fn from(refusal: Refusal) -> Self {
+        SignalingMessage::Error { code: refusal.code, message: Some(refusal.message) }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub id: String,
+    pub name: String,
+    pub variant: String,
+    pub sender: broadcast::Sender<SignalingMessage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameRoom {
+    pub id: String,
+    pub variant: String,
+    pub players: HashMap<String, Player>,
+    pub spectators: HashMap<String, Player>,
+    pub max_players: usize,
+    /// The refereed game; moves it rejects are not relayed
+    pub session: GameSession,
+    /// Color each player is playing, by player id
+    pub seats: HashMap<String, Color>,
+    /// Zero point for the session's clock readings
+    pub opened_at: Instant,
+    /// Score across the room's games, which alternate colours
+    pub series: MatchSeries,
+    /// Player ids in the series' order, set when the first game ends
+    pub series_players: Vec<String>,
+    /// Players who have asked for a rematch of the finished game
+    pub rematch_requests: HashSet<String>,
+    /// Whether the current game's result has gone into the series
+    pub scored: bool,
+}
+
+impl GameRoom {
+    /// Open a room for one of the registry's variants, by its id
+    pub fn new(
+        id: String,
+        variant: String,
+        time_control: Option<TimeControl>,
+        variants: &VariantRegistry,
+    ) -> Result<Self, Refusal> {
+        let config = variants
+            .get(&variant)
+            .cloned()
+            .ok_or_else(|| Refusal::new(ErrorCode::UnknownVariant, format!("Unknown variant {}", variant)))?;
+        Ok(Self {
+            id,
+            variant,
+            players: HashMap::new(),
+            spectators: HashMap::new(),
+            max_players: 2,
+            session: GameSession::new(config, time_control),
+            seats: HashMap::new(),
+            opened_at: Instant::now(),
+            series: MatchSeries::default(),
+            series_players: Vec::new(),
+            rematch_requests: HashSet::new(),
+            scored: false,
+        })
+    }
+
+    pub fn add_player(&mut self, player: Player) -> Result<(), Refusal> {
+        if self.players.len() >= self.max_players {
+            return Err(Refusal::new(ErrorCode::RoomFull, "Room is full"));
+        }
+        if self.players.contains_key(&player.id) {
+            return Err(Refusal::new(ErrorCode::AlreadyInRoom, "Player already in room"));
+        }
+        let color = if self.seats.values().any(|&color| color == Color::White) {
+            Color::Black
+        } else {
+            Color::White
+        };
+        self.seats.insert(player.id.clone(), color);
+        self.players.insert(player.id.clone(), player);
+        Ok(())
+    }
+
+    pub fn remove_player(&mut self, player_id: &str) {
+        self.players.remove(player_id);
+        self.spectators.remove(player_id);
+        self.seats.remove(player_id);
+        self.rematch_requests.remove(player_id);
+    }
+
+    /// Everyone in the room other than `player_id`, players and spectators alike
+    pub fn others<'a>(&'a self, player_id: &'a str) -> impl Iterator<Item = &'a Player> + 'a {
+        self.players
+            .values()
+            .chain(self.spectators.values())
+            .filter(move |player| player.id != player_id)
+    }
+
+    /// Bring a newcomer up to date with the refereed game, or in a fog-of-war room
+    /// with only what they are allowed to see
+    pub fn send_snapshot(&self, player_id: &str, to: &broadcast::Sender<SignalingMessage>) {
+        if self.is_foggy() {
+            if let Some(view) = self.fog_view(player_id) {
+                let _ = to.send(SignalingMessage::FogView { room_id: self.id.clone(), view });
+            }
+            return;
+        }
+        if !self.session.game.move_history.is_empty() {
+            let _ = to.send(SignalingMessage::Resync { room_id: self.id.clone(), game: self.game_record() });
+        }
+    }
+
+    /// The refereed game's moves so far, for clients to replay
+    pub fn game_record(&self) -> SampleGame {
+        SampleGame::from_game(&self.session.game, &self.id, &format!("Room {}", self.id), "")
+    }
+
+    /// Whether the room plays a fog-of-war variant, where moves are never relayed
+    pub fn is_foggy(&self) -> bool {
+        self.session.game.variant.has_fog_of_war()
+    }
+
+    /// What `player_id` may see in a fog-of-war room: their side's view, or for
+    /// spectators the whole board once the game is over and nothing before
+    pub fn fog_view(&self, player_id: &str) -> Option<FogView> {
+        let game = &self.session.game;
+        match self.seats.get(player_id) {
+            Some(&color) => Some(game.fog_view(color)),
+            None if game.is_over() => Some(game.fog_view(Color::White)),
+            None => None,
+        }
+    }
+
+    /// The current position as an SVG picture; fog-of-war rooms withhold it until the game is over
+    pub fn thumbnail(&self) -> Option<String> {
+        let game = &self.session.game;
+        (!self.is_foggy() || game.is_over()).then(|| board_svg(&game.board, THUMBNAIL_WIDTH))
+    }
+
+    /// Count the game into the series if it has just ended; true if it had
+    pub fn score_finished_game(&mut self) -> bool {
+        let Some(score) = self.session.game.score().filter(|_| !self.scored) else {
+            return false;
+        };
+        if self.series_players.is_empty() {
+            let mut players: Vec<(Color, String)> = self.seats.iter().map(|(id, &color)| (color, id.clone())).collect();
+            players.sort_by_key(|(color, _)| *color == Color::Black);
+            self.series_players = players.into_iter().map(|(_, id)| id).collect();
+        }
+        self.series.record(score);
+        self.scored = true;
+        true
+    }
+
+    /// Start the next game of the series with the same rules, colours swapped
+    pub fn start_rematch(&mut self) {
+        self.session = GameSession::new(self.session.game.variant.clone(), self.session.time_control);
+        for color in self.seats.values_mut() {
+            *color = match color {
+                Color::White => Color::Black,
+                Color::Black => Color::White,
+            };
+        }
+        self.rematch_requests.clear();
+        self.scored = false;
+    }
+
+    pub fn series_update(&self) -> SignalingMessage {
+        SignalingMessage::SeriesUpdate {
+            room_id: self.id.clone(),
+            series: self.series.clone(),
+            players: self.series_players.clone(),
+            winner: self.series.winner().and_then(|player| self.series_players.get(player).cloned()),
+            decided: self.series.is_decided(),
+            result: self.session.game.get_result(),
+        }
+    }
+
+    /// Tell everyone in the room that `color` ran out of time, and count the result;
+    /// returns the flagged player's id
+    pub fn announce_flag(&mut self, color: Color) -> String {
+        let player_id = self
+            .seats
+            .iter()
+            .find(|(_, &seat)| seat == color)
+            .map(|(id, _)| id.clone())
+            .unwrap_or_default();
+        self.broadcast(&SignalingMessage::Timeout { room_id: self.id.clone(), player_id: player_id.clone() });
+        if self.score_finished_game() {
+            self.broadcast(&self.series_update());
+        }
+        player_id
+    }
+
+    /// Send a message to every player and spectator in the room
+    pub fn broadcast(&self, msg: &SignalingMessage) {
+        for player in self.players.values().chain(self.spectators.values()) {
+            let _ = player.sender.send(msg.clone());
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.players.len() >= self.max_players
+    }
+
+    pub fn time_control(&self) -> Option<TimeControl> {
+        self.session.time_control
+    }
+
+    /// Milliseconds since the room opened, the session's notion of now
+    pub fn now_ms(&self) -> u64 {
+        self.opened_at.elapsed().as_millis() as u64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AppState {
+    pub rooms: Arc<RwLock<HashMap<String, GameRoom>>>,
+    pub players: Arc<RwLock<HashMap<String, String>>>, // player_id -> room_id
+    pub audit: Arc<RwLock<AuditLog>>,
+    pub admin_token: Option<Arc<str>>, // from ADMIN_TOKEN; admin routes are off without it
+    /// Variants rooms can be opened for, by id
+    pub variants: Arc<VariantRegistry>,
+}
+
+impl AppState {
+    /// State serving the built-in variants
+    pub fn new() -> Self {
+        Self::with_variants(VariantRegistry::new())
+    }
+
+    /// State serving the variants in `variants`, e.g. built-in ones plus the host's own
+    pub fn with_variants(variants: VariantRegistry) -> Self {
+        Self {
+            rooms: Arc::new(RwLock::new(HashMap::new())),
+            players: Arc::new(RwLock::new(HashMap::new())),
+            audit: Arc::new(RwLock::new(AuditLog::from_env())),
+            admin_token: std::env::var("ADMIN_TOKEN").ok().filter(|token| !token.is_empty()).map(Arc::from),
+            variants: Arc::new(variants),
+        }
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Width in pixels of the board pictures the REST routes hand out
+pub const THUMBNAIL_WIDTH: u32 = 240;
+
+/// The server's routes, ready to serve
+pub fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(health_check))
+        .route("/ws", get(websocket_handler))
+        .route("/rooms", get(list_rooms))
+        .route("/rooms/:room_id", get(get_room))
+        .route("/rooms/:room_id/board.svg", get(get_room_board))
+        .route("/schema", get(get_schema))
+        .route("/admin/rooms/:room_id/audit", get(get_room_audit))
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+async fn health_check() -> &'static str {
+    "Hex Chess Signaling Server is running"
+}
+
+/// JSON Schema of `SignalingMessage`, for writing clients and bots in other languages
+pub fn protocol_schema() -> RootSchema {
+    schema_for!(SignalingMessage)
+}
+
+async fn get_schema() -> Result<Response<String>, StatusCode> {
+    let schema = serde_json::to_string_pretty(&protocol_schema())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/schema+json")
+        .body(schema)
+        .unwrap())
+}
+
+/// Which rooms `/rooms` lists and in what order, from its query string,
+/// e.g. `/rooms?variant=glinski&time_control=5%2B3&open=true&sort=newest`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RoomFilter {
+    /// Only rooms playing this variant id
+    pub variant: Option<String>,
+    /// Only rooms with this time control as the server writes it, e.g. "5+3", or "none" for untimed
+    pub time_control: Option<String>,
+    /// Only rooms with a seat free
+    #[serde(default)]
+    pub open: bool,
+    #[serde(default)]
+    pub sort: RoomSort,
+}
+
+/// Order of the room list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoomSort {
+    /// By room id
+    #[default]
+    Id,
+    /// Most recently opened first
+    Newest,
+}
+
+impl RoomFilter {
+    pub fn matches(&self, room: &GameRoom) -> bool {
+        let time_control = room.time_control().map_or("none".to_string(), |control| control.to_string());
+        self.variant.as_ref().is_none_or(|variant| *variant == room.variant)
+            && self.time_control.as_ref().is_none_or(|wanted| *wanted == time_control)
+            && (!self.open || !room.is_full())
+    }
+
+    /// The rooms that match, in the requested order
+    pub fn apply<'a>(&self, rooms: impl IntoIterator<Item = &'a GameRoom>) -> Vec<&'a GameRoom> {
+        let mut rooms: Vec<_> = rooms.into_iter().filter(|room| self.matches(room)).collect();
+        match self.sort {
+            RoomSort::Id => rooms.sort_by(|a, b| a.id.cmp(&b.id)),
+            RoomSort::Newest => rooms.sort_by_key(|room| std::cmp::Reverse(room.opened_at)),
+        }
+        rooms
+    }
+}
+
+async fn list_rooms(
+    State(state): State<AppState>,
+    Query(filter): Query<RoomFilter>,
+) -> Result<Response<String>, StatusCode> {
+    let rooms = state.rooms.read().await;
+    let room_list: Vec<_> = filter
+        .apply(rooms.values())
+        .into_iter()
+        .map(|room| {
+            serde_json::json!({
+                "id": room.id,
+                "variant": room.variant,
+                "player_count": room.players.len(),
+                "spectator_count": room.spectators.len(),
+                "max_players": room.max_players,
+                "is_full": room.is_full(),
+                "time_control": room.time_control()
+            })
+        })
+        .collect();
+
+    let response = serde_json::to_string(&room_list)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(response)
+        .unwrap())
+}
+
+async fn get_room(
+    State(state): State<AppState>,
+    axum::extract::Path(room_id): axum::extract::Path<String>,
+) -> Result<Response<String>, StatusCode> {
+    let rooms = state.rooms.read().await;
+    let room = rooms
+        .get(&room_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let room_info = serde_json::json!({
+        "id": room.id,
+        "variant": room.variant,
+        "player_count": room.players.len(),
+        "spectator_count": room.spectators.len(),
+        "max_players": room.max_players,
+        "is_full": room.is_full(),
+        "time_control": room.time_control(),
+        "thumbnail": room.thumbnail(),
+        "players": room.players.values().map(|p| {
+            serde_json::json!({
+                "id": p.id,
+                "name": p.name
+            })
+        }).collect::<Vec<_>>()
+    });
+
+    let response = serde_json::to_string(&room_info)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(response)
+        .unwrap())
+}
+
+/// The room's position as an SVG picture, for pages to show with an `<img>`
+async fn get_room_board(
+    State(state): State<AppState>,
+    axum::extract::Path(room_id): axum::extract::Path<String>,
+) -> Result<Response<String>, StatusCode> {
+    let rooms = state.rooms.read().await;
+    let room = rooms
+        .get(&room_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let svg = room.thumbnail().ok_or(StatusCode::FORBIDDEN)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "image/svg+xml")
+        .body(svg)
+        .unwrap())
+}
+
+async fn get_room_audit(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Path(room_id): axum::extract::Path<String>,
+) -> Result<Response<String>, StatusCode> {
+    let expected = state.admin_token.as_deref().ok_or(StatusCode::FORBIDDEN)?;
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if presented != Some(expected) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let events = state.audit.read().await.events(&room_id);
+    let response = serde_json::to_string(&events)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(response)
+        .unwrap())
+}
+
+async fn websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+) -> Response {
+    ws.on_upgrade(|socket| websocket_connection(socket, state))
+}
+
+async fn websocket_connection(socket: WebSocket, state: AppState) {
+    let (mut sender, mut receiver) = socket.split();
+    let (tx, mut rx) = broadcast::channel(100);
+    let player_id = Uuid::new_v4().to_string();
+
+    // Send messages from the broadcast channel to the WebSocket
+    let tx_clone = tx.clone();
+    let send_task = tokio::spawn(async move {
+        while let Ok(msg) = rx.recv().await {
+            if let Ok(json) = serde_json::to_string(&msg) {
+                if sender.send(Message::Text(json)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    // Handle incoming messages
+    while let Some(msg) = receiver.next().await {
+        let msg = match msg {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => break,
+            _ => continue,
+        };
+
+        if let Ok(signaling_msg) = serde_json::from_str::<SignalingMessage>(&msg) {
+            if let Err(refusal) = handle_signaling_message(
+                &state,
+                &player_id,
+                &tx_clone,
+                signaling_msg,
+            ).await {
+                let _ = tx_clone.send(refusal.into());
+            }
+        }
+    }
+
+    // Cleanup when connection closes
+    cleanup_player(&state, &player_id).await;
+    send_task.abort();
+}
+
+async fn handle_signaling_message(
+    state: &AppState,
+    player_id: &str,
+    tx: &broadcast::Sender<SignalingMessage>,
+    msg: SignalingMessage,
+) -> Result<(), Refusal> {
+    match msg {
+        SignalingMessage::JoinRoom {
+            room_id,
+            variant,
+            player_name,
+            time_control,
+            best_of,
+        } => {
+            let mut rooms = state.rooms.write().await;
+            let mut players = state.players.write().await;
+
+            // Remove player from any existing room
+            if let Some(old_room_id) = players.get(player_id) {
+                if let Some(room) = rooms.get_mut(old_room_id) {
+                    room.remove_player(player_id);
+                }
+            }
+
+            // Get or create room
+            let room = match rooms.entry(room_id.clone()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => match GameRoom::new(room_id.clone(), variant.clone(), time_control, &state.variants) {
+                    Ok(mut room) => {
+                        room.series = MatchSeries::new(best_of);
+                        entry.insert(room)
+                    }
+                    Err(refusal) => {
+                        let kind = AuditKind::JoinRejected { reason: refusal.message.clone() };
+                        state.audit.write().await.record(&room_id, player_id, kind);
+                        return Err(refusal);
+                    }
+                },
+            };
+
+            // A joiner asking for a different time control is turned away rather than silently switched
+            if time_control.is_some() && time_control != room.time_control() {
+                let reason = match room.time_control() {
+                    Some(room_control) => format!("Room plays {}", room_control),
+                    None => "Room is untimed".to_string(),
+                };
+                let kind = AuditKind::JoinRejected { reason: reason.clone() };
+                state.audit.write().await.record(&room_id, player_id, kind);
+                return Err(Refusal::new(ErrorCode::TimeControlMismatch, reason));
+            }
+
+            // Create player
+            let player = Player {
+                id: player_id.to_string(),
+                name: player_name.clone(),
+                variant: variant.clone(),
+                sender: tx.clone(),
+            };
+
+            // Add player to room
+            if let Err(refusal) = room.add_player(player) {
+                let kind = AuditKind::JoinRejected { reason: refusal.message.clone() };
+                state.audit.write().await.record(&room_id, player_id, kind);
+                return Err(refusal);
+            }
+            players.insert(player_id.to_string(), room_id.clone());
+            let kind = AuditKind::Join { player_name: player_name.clone(), variant };
+            state.audit.write().await.record(&room_id, player_id, kind);
+
+            // Notify other players in the room
+            for (other_player_id, other_player) in &room.players {
+                if other_player_id != player_id {
+                    let join_msg = SignalingMessage::Success {
+                        message: format!("Player {} joined the room", player_name),
+                    };
+                    let _ = other_player.sender.send(join_msg);
+                }
+            }
+
+            // Send success message to joining player
+            let success_msg = SignalingMessage::Success {
+                message: "Successfully joined room".to_string(),
+            };
+            let _ = tx.send(success_msg);
+            let _ = tx.send(SignalingMessage::RoomInfo {
+                room_id: room_id.clone(),
+                variant: room.variant.clone(),
+                time_control: room.time_control(),
+            });
+            room.send_snapshot(player_id, tx);
+        }
+
+        SignalingMessage::Spectate {
+            room_id,
+            spectator_name,
+        } => {
+            let mut rooms = state.rooms.write().await;
+            let mut players = state.players.write().await;
+
+            if let Some(old_room_id) = players.get(player_id) {
+                if let Some(room) = rooms.get_mut(old_room_id) {
+                    room.remove_player(player_id);
+                }
+            }
+
+            let room = rooms
+                .get_mut(&room_id)
+                .ok_or_else(|| Refusal::new(ErrorCode::RoomNotFound, "Room not found"))?;
+            let spectator = Player {
+                id: player_id.to_string(),
+                name: spectator_name.clone(),
+                variant: room.variant.clone(),
+                sender: tx.clone(),
+            };
+            room.spectators.insert(player_id.to_string(), spectator);
+            players.insert(player_id.to_string(), room_id.clone());
+
+            let success_msg = SignalingMessage::Success {
+                message: "Now spectating".to_string(),
+            };
+            let _ = tx.send(success_msg);
+            // Don't make a spectator wait for a player to sync
+            room.send_snapshot(player_id, tx);
+
+            let kind = AuditKind::Spectate { spectator_name };
+            state.audit.write().await.record(&room_id, player_id, kind);
+        }
+
+        SignalingMessage::LeaveRoom { room_id } => {
+            let mut rooms = state.rooms.write().await;
+            let mut players = state.players.write().await;
+
+            if let Some(room) = rooms.get_mut(&room_id) {
+                room.remove_player(player_id);
+                
+                // Notify other players
+                for other_player in room.players.values() {
+                    let leave_msg = SignalingMessage::Success {
+                        message: "A player left the room".to_string(),
+                    };
+                    let _ = other_player.sender.send(leave_msg);
+                }
+            }
+
+            players.remove(player_id);
+            state.audit.write().await.record(&room_id, player_id, AuditKind::Leave);
+        }
+
+        SignalingMessage::Offer {
+            room_id,
+            target_player,
+            sdp,
+        } => {
+            let rooms = state.rooms.read().await;
+            let outcome = match rooms.get(&room_id) {
+                Some(room) => match room.players.get(&target_player) {
+                    Some(target) => {
+                        let offer_msg = SignalingMessage::Offer {
+                            room_id: room_id.clone(),
+                            target_player: player_id.to_string(),
+                            sdp,
+                        };
+                        let _ = target.sender.send(offer_msg);
+                        RelayOutcome::Relayed { recipients: 1 }
+                    }
+                    None => RelayOutcome::NoSuchPlayer,
+                },
+                None => RelayOutcome::NoSuchRoom,
+            };
+            let kind = AuditKind::Signal { signal: "offer", target_player, outcome };
+            state.audit.write().await.record(&room_id, player_id, kind);
+        }
+
+        SignalingMessage::Answer {
+            room_id,
+            target_player,
+            sdp,
+        } => {
+            let rooms = state.rooms.read().await;
+            let outcome = match rooms.get(&room_id) {
+                Some(room) => match room.players.get(&target_player) {
+                    Some(target) => {
+                        let answer_msg = SignalingMessage::Answer {
+                            room_id: room_id.clone(),
+                            target_player: player_id.to_string(),
+                            sdp,
+                        };
+                        let _ = target.sender.send(answer_msg);
+                        RelayOutcome::Relayed { recipients: 1 }
+                    }
+                    None => RelayOutcome::NoSuchPlayer,
+                },
+                None => RelayOutcome::NoSuchRoom,
+            };
+            let kind = AuditKind::Signal { signal: "answer", target_player, outcome };
+            state.audit.write().await.record(&room_id, player_id, kind);
+        }
+
+        SignalingMessage::IceCandidate {
+            room_id,
+            target_player,
+            candidate,
+        } => {
+            let rooms = state.rooms.read().await;
+            let outcome = match rooms.get(&room_id) {
+                Some(room) => match room.players.get(&target_player) {
+                    Some(target) => {
+                        let candidate_msg = SignalingMessage::IceCandidate {
+                            room_id: room_id.clone(),
+                            target_player: player_id.to_string(),
+                            candidate,
+                        };
+                        let _ = target.sender.send(candidate_msg);
+                        RelayOutcome::Relayed { recipients: 1 }
+                    }
+                    None => RelayOutcome::NoSuchPlayer,
+                },
+                None => RelayOutcome::NoSuchRoom,
+            };
+            let kind = AuditKind::Signal { signal: "ice_candidate", target_player, outcome };
+            state.audit.write().await.record(&room_id, player_id, kind);
+        }
+
+        SignalingMessage::GameMove {
+            ref room_id,
+            from,
+            to,
+            promotion,
+        } => {
+            let room_id = room_id.clone();
+            let audit = |outcome| AuditKind::Move { from, to, outcome };
+            let (from, to) = (HexCoord::new(from.0, from.1), HexCoord::new(to.0, to.1));
+            referee_turn(state, player_id, &room_id, msg, audit, |session, color, now_ms| {
+                session.play(color, from, to, promotion, now_ms)
+            })
+            .await?;
+        }
+
+        SignalingMessage::GameDrop {
+            ref room_id,
+            piece,
+            to,
+        } => {
+            let room_id = room_id.clone();
+            let audit = |outcome| AuditKind::Drop { piece, to, outcome };
+            let to = HexCoord::new(to.0, to.1);
+            referee_turn(state, player_id, &room_id, msg, audit, |session, color, now_ms| {
+                session.drop_piece(color, piece, to, now_ms)
+            })
+            .await?;
+        }
+
+        SignalingMessage::Rematch { ref room_id } => {
+            let room_id = room_id.clone();
+            let mut rooms = state.rooms.write().await;
+            let room = rooms
+                .get_mut(&room_id)
+                .ok_or_else(|| Refusal::new(ErrorCode::RoomNotFound, "Room not found"))?;
+            if !room.seats.contains_key(player_id) {
+                return Err(Refusal::new(ErrorCode::NotAPlayer, "Only players can ask for a rematch"));
+            }
+            if !room.session.game.is_over() {
+                return Err(Refusal::new(ErrorCode::GameInProgress, "The game isn't over yet"));
+            }
+            if room.series.is_decided() {
+                return Err(Refusal::new(ErrorCode::SeriesOver, "The match has been decided"));
+            }
+            room.rematch_requests.insert(player_id.to_string());
+            for other_player in room.others(player_id) {
+                let _ = other_player.sender.send(msg.clone());
+            }
+            if room.is_full() && room.players.keys().all(|id| room.rematch_requests.contains(id)) {
+                room.start_rematch();
+                room.broadcast(&room.series_update());
+            }
+            state.audit.write().await.record(&room_id, player_id, AuditKind::Rematch);
+        }
+
+        SignalingMessage::PositionHash { room_id, ply, hash } => {
+            let rooms = state.rooms.read().await;
+            let room = rooms
+                .get(&room_id)
+                .ok_or_else(|| Refusal::new(ErrorCode::RoomNotFound, "Room not found"))?;
+            if room.is_foggy() {
+                return Err(Refusal::new(ErrorCode::StateWithheld, "Fog-of-war rooms don't share game state"));
+            }
+            // A ply the game hasn't reached, or has taken back, can't match either
+            let game = &room.session.game;
+            if game.position_history.get(ply) != Some(&hash) {
+                let _ = tx.send(SignalingMessage::Resync { room_id: room_id.clone(), game: room.game_record() });
+                state.audit.write().await.record(&room_id, player_id, AuditKind::Resync { ply });
+            }
+        }
+
+        _ => {
+            let _ = tx.send(Refusal::new(ErrorCode::UnknownMessage, "Unknown message type").into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Put a move or drop to the room's referee, then relay it to everyone else if it stands
+async fn referee_turn(
+    state: &AppState,
+    player_id: &str,
+    room_id: &str,
+    msg: SignalingMessage,
+    audit: impl Fn(RelayOutcome) -> AuditKind,
+    turn: impl FnOnce(&mut GameSession, Color, u64) -> Result<MoveOutcome, SessionError>,
+) -> Result<(), Refusal> {
+    let mut rooms = state.rooms.write().await;
+    let outcome = match rooms.get_mut(room_id) {
+        Some(room) => {
+            let color = *room
+                .seats
+                .get(player_id)
+                .ok_or_else(|| Refusal::new(ErrorCode::NotAPlayer, "Only players can move"))?;
+            let now_ms = room.now_ms();
+            match turn(&mut room.session, color, now_ms) {
+                Ok(_) => {}
+                Err(SessionError::OutOfTime(flagged)) => {
+                    room.announce_flag(flagged);
+                    state.audit.write().await.record(room_id, player_id, audit(RelayOutcome::Flagged));
+                    return Ok(());
+                }
+                Err(error) => {
+                    let (outcome, code) = match error {
+                        SessionError::NotYourTurn => (RelayOutcome::OutOfTurn, ErrorCode::NotYourTurn),
+                        _ => (RelayOutcome::Illegal, ErrorCode::InvalidMove),
+                    };
+                    state.audit.write().await.record(room_id, player_id, audit(outcome));
+                    return Err(Refusal::new(code, error.to_string()));
+                }
+            }
+            watch_clock(state, room_id, room);
+            let mut recipients = 0;
+            let outcome = if room.is_foggy() {
+                // Everyone hears only what they can now see; the move itself stays secret
+                for player in room.players.values().chain(room.spectators.values()) {
+                    if let Some(view) = room.fog_view(&player.id) {
+                        let _ = player.sender.send(SignalingMessage::FogView { room_id: room_id.to_string(), view });
+                        recipients += usize::from(player.id != player_id);
+                    }
+                }
+                RelayOutcome::Relayed { recipients }
+            } else {
+                for other_player in room.others(player_id) {
+                    let _ = other_player.sender.send(msg.clone());
+                    recipients += 1;
+                }
+                RelayOutcome::Relayed { recipients }
+            };
+            if room.score_finished_game() {
+                room.broadcast(&room.series_update());
+            }
+            outcome
+        }
+        None => RelayOutcome::NoSuchRoom,
+    };
+    state.audit.write().await.record(room_id, player_id, audit(outcome));
+    Ok(())
+}
+
+/// Wake when the side to move runs out of time, so a player who stops moving
+/// still loses on time rather than stalling the game
+fn watch_clock(state: &AppState, room_id: &str, room: &GameRoom) {
+    let Some(flag_at_ms) = room.session.flag_at_ms() else {
+        return;
+    };
+    let deadline = tokio::time::Instant::from_std(room.opened_at) + Duration::from_millis(flag_at_ms);
+    let state = state.clone();
+    let room_id = room_id.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep_until(deadline).await;
+        let mut rooms = state.rooms.write().await;
+        let Some(room) = rooms.get_mut(&room_id) else {
+            return;
+        };
+        // A move or a rematch since leaves nothing to flag
+        let now_ms = room.now_ms();
+        if room.session.check_flag(now_ms) {
+            let flagged = room.session.game.current_player;
+            let player_id = room.announce_flag(flagged);
+            state.audit.write().await.record(&room_id, &player_id, AuditKind::Flagged);
+        }
+    });
+}
+
+async fn cleanup_player(state: &AppState, player_id: &str) {
+    let mut rooms = state.rooms.write().await;
+    let mut players = state.players.write().await;
+
+    if let Some(room_id) = players.get(player_id) {
+        state.audit.write().await.record(room_id, player_id, AuditKind::Disconnect);
+        if let Some(room) = rooms.get_mut(room_id) {
+            room.remove_player(player_id);
+            
+            // Notify other players
+            for other_player in room.players.values() {
+                let leave_msg = SignalingMessage::Success {
+                    message: "A player disconnected".to_string(),
+                };
+                let _ = other_player.sender.send(leave_msg);
+            }
+        }
+    }
+
+    players.remove(player_id);
+}
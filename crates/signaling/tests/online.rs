@@ -0,0 +1,694 @@
+//! Runs the signaling server in-process and plays through it with real WebSocket
+//! clients, each checking every relayed move against its own hex-chess-core game
+
+use futures::{SinkExt, StreamExt};
+use hex_chess_core::{parse_square, BoardType, Color, DrawRules, Game, HexCoord, InsufficientMaterial, PieceType, TimeControl, VariantBuilder, VariantConfig, VariantRegistry, Variants};
+use hex_chess_signaling::{app, protocol_schema, AppState, ErrorCode, GameRoom, RoomFilter, RoomSort, SignalingMessage};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+const ROOM: &str = "integration";
+
+/// A connected player with its local copy of the game
+struct Client {
+    socket: Socket,
+    variant: String,
+    game: Game,
+}
+
+impl Client {
+    async fn connect(url: &str, variant: &str) -> Self {
+        Self::connect_with(url, variant, Variants::find(variant).expect("known variant")).await
+    }
+
+    /// Connect to play a variant the server was given, rather than a built-in one
+    async fn connect_with(url: &str, variant: &str, config: VariantConfig) -> Self {
+        let (socket, _) = connect_async(url).await.expect("connect to server");
+        Self {
+            socket,
+            variant: variant.to_string(),
+            game: Game::new(config),
+        }
+    }
+
+    async fn send(&mut self, message: SignalingMessage) {
+        let json = serde_json::to_string(&message).unwrap();
+        self.socket.send(Message::Text(json)).await.unwrap();
+    }
+
+    /// Wait for the first message `pick` accepts, skipping any others
+    async fn expect<T>(&mut self, mut pick: impl FnMut(SignalingMessage) -> Option<T>) -> T {
+        let wait = async {
+            while let Some(frame) = self.socket.next().await {
+                if let Message::Text(text) = frame.unwrap() {
+                    let message = serde_json::from_str(&text).unwrap();
+                    if let Some(found) = pick(message) {
+                        return found;
+                    }
+                }
+            }
+            panic!("server closed the connection");
+        };
+        tokio::time::timeout(Duration::from_secs(5), wait).await.expect("message from server")
+    }
+
+    async fn join(&mut self, name: &str, time_control: Option<TimeControl>) -> Option<TimeControl> {
+        self.send(SignalingMessage::JoinRoom {
+            room_id: ROOM.to_string(),
+            variant: self.variant.clone(),
+            player_name: name.to_string(),
+            time_control,
+            best_of: None,
+        })
+        .await;
+        self.expect(|message| match message {
+            SignalingMessage::RoomInfo { time_control, .. } => Some(time_control),
+            _ => None,
+        })
+        .await
+    }
+
+    /// Make a move locally, which must be legal, and send it
+    async fn play(&mut self, from: HexCoord, to: HexCoord) {
+        self.promote(from, to, None).await;
+    }
+
+    /// Make a move naming the piece a pawn promotes to, as `play` does
+    async fn promote(&mut self, from: HexCoord, to: HexCoord, promotion: Option<PieceType>) {
+        self.game.make_move_with_promotion(from, to, promotion).expect("legal move");
+        self.send(SignalingMessage::GameMove {
+            room_id: ROOM.to_string(),
+            from: (from.q, from.r),
+            to: (to.q, to.r),
+            promotion,
+        })
+        .await;
+    }
+
+    /// Take the next relayed move or drop and apply it, which must be legal
+    async fn receive_move(&mut self) {
+        let relayed = self
+            .expect(|message| match message {
+                SignalingMessage::GameMove { .. } | SignalingMessage::GameDrop { .. } => Some(message),
+                _ => None,
+            })
+            .await;
+        let applied = match relayed {
+            SignalingMessage::GameMove { from, to, promotion, .. } => self.game.make_move_with_promotion(
+                HexCoord::new(from.0, from.1),
+                HexCoord::new(to.0, to.1),
+                promotion,
+            ),
+            SignalingMessage::GameDrop { piece, to, .. } => self.game.drop_piece(piece, HexCoord::new(to.0, to.1)),
+            _ => unreachable!(),
+        };
+        applied.expect("relayed move is legal");
+    }
+
+    /// Send the server the hash of the local position, for it to check
+    async fn check_position(&mut self) {
+        self.send(SignalingMessage::PositionHash {
+            room_id: ROOM.to_string(),
+            ply: self.game.move_history.len(),
+            hash: self.game.position_hash(),
+        })
+        .await;
+    }
+}
+
+async fn start_server() -> String {
+    start_server_with(AppState::new()).await
+}
+
+async fn start_server_with(state: AppState) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app(state)).await.unwrap();
+    });
+    format!("ws://{}/ws", addr)
+}
+
+#[tokio::test]
+async fn test_timed_game_through_the_server() {
+    let url = start_server().await;
+    let blitz = TimeControl { initial_secs: 1, increment_secs: 0, move_secs: None };
+
+    let mut white = Client::connect(&url, "glinski").await;
+    let mut black = Client::connect(&url, "glinski").await;
+    assert_eq!(white.join("White", Some(blitz)).await, Some(blitz));
+    // Joining without asking for a time control picks up the room's
+    assert_eq!(black.join("Black", None).await, Some(blitz));
+
+    // f5-f6, e8-e7, f6xe7
+    let moves = [
+        ((0, -1), (0, 0)),
+        ((-1, 2), (-1, 1)),
+        ((0, 0), (-1, 1)),
+    ];
+    for (ply, (from, to)) in moves.into_iter().enumerate() {
+        let (from, to) = (HexCoord::new(from.0, from.1), HexCoord::new(to.0, to.1));
+        let (mover, watcher) = if ply % 2 == 0 { (&mut white, &mut black) } else { (&mut black, &mut white) };
+        mover.play(from, to).await;
+        watcher.receive_move().await;
+    }
+    assert_eq!(white.game.position_hash(), black.game.position_hash());
+    assert!(white.game.move_history[2].captured_piece.is_some());
+
+    // Moving twice in a row is refused
+    white
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (1, -1), to: (1, 0), promotion: None })
+        .await;
+    let refusal = white
+        .expect(|message| match message {
+            SignalingMessage::Error { code, message } => Some((code, message.unwrap_or_default())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, (ErrorCode::NotYourTurn, "Not your turn".to_string()));
+
+    // So is a move the rules don't allow
+    black
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (0, 0), to: (0, -1), promotion: None })
+        .await;
+    let refusal = black
+        .expect(|message| match message {
+            SignalingMessage::Error { code, message } => Some((code, message.unwrap_or_default())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, (ErrorCode::InvalidMove, "No piece at the specified coordinate".to_string()));
+
+    // A late spectator catches up from the refereed game, refused moves and all left out
+    let mut spectator = Client::connect(&url, "glinski").await;
+    spectator
+        .send(SignalingMessage::Spectate { room_id: ROOM.to_string(), spectator_name: "Watcher".to_string() })
+        .await;
+    let record = spectator
+        .expect(|message| match message {
+            SignalingMessage::Resync { game, .. } => Some(game),
+            _ => None,
+        })
+        .await;
+    assert_eq!(record.moves.len(), moves.len());
+    spectator.game = record.replay().unwrap();
+    assert_eq!(spectator.game.position_hash(), white.game.position_hash());
+
+    // Black lets a second pass on a one-second clock; the move is dropped and everyone hears of the timeout
+    tokio::time::sleep(Duration::from_millis(1_200)).await;
+    black
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (1, 2), to: (1, 1), promotion: None })
+        .await;
+    for client in [&mut white, &mut black, &mut spectator] {
+        client
+            .expect(|message| match message {
+                SignalingMessage::Timeout { room_id, .. } => Some(room_id),
+                SignalingMessage::GameMove { .. } => panic!("a flagged move was relayed"),
+                _ => None,
+            })
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_silent_player_loses_on_time() {
+    let url = start_server().await;
+    let blitz = TimeControl { initial_secs: 1, increment_secs: 0, move_secs: None };
+
+    let mut white = Client::connect(&url, "glinski").await;
+    let mut black = Client::connect(&url, "glinski").await;
+    white.join("White", Some(blitz)).await;
+    black.join("Black", None).await;
+
+    // White moves and Black never answers: the room's own timer flags Black
+    let started = std::time::Instant::now();
+    white.play(HexCoord::new(0, -1), HexCoord::new(0, 0)).await;
+    black.receive_move().await;
+    for client in [&mut white, &mut black] {
+        client
+            .expect(|message| match message {
+                SignalingMessage::Timeout { room_id, .. } => Some(room_id),
+                _ => None,
+            })
+            .await;
+    }
+    assert!(started.elapsed() >= Duration::from_millis(1_000));
+    let series = white
+        .expect(|message| match message {
+            SignalingMessage::SeriesUpdate { series, .. } => Some(series),
+            _ => None,
+        })
+        .await;
+    assert_eq!(series.scores, [1.0, 0.0]);
+
+    // Black's move after the flag doesn't bring the game back
+    black
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (-1, 2), to: (-1, 1), promotion: None })
+        .await;
+    let refusal = black
+        .expect(|message| match message {
+            SignalingMessage::Error { code, .. } => Some(code),
+            SignalingMessage::GameMove { .. } => panic!("a move after the flag was relayed"),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, ErrorCode::InvalidMove);
+}
+
+#[tokio::test]
+async fn test_move_time_limit_through_the_server() {
+    let url = start_server().await;
+    let casual = TimeControl::per_move(1);
+
+    let mut white = Client::connect(&url, "glinski").await;
+    let mut black = Client::connect(&url, "glinski").await;
+    assert_eq!(white.join("White", Some(casual)).await, Some(casual));
+    assert_eq!(black.join("Black", None).await, Some(casual));
+
+    // Quick moves stand, however many are played
+    white.play(HexCoord::new(0, -1), HexCoord::new(0, 0)).await;
+    black.receive_move().await;
+    black.play(HexCoord::new(-1, 2), HexCoord::new(-1, 1)).await;
+    white.receive_move().await;
+
+    // White takes longer than a move allows, with no main clock to run down
+    tokio::time::sleep(Duration::from_millis(1_200)).await;
+    white
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (0, 0), to: (-1, 1), promotion: None })
+        .await;
+    for client in [&mut white, &mut black] {
+        client
+            .expect(|message| match message {
+                SignalingMessage::Timeout { room_id, .. } => Some(room_id),
+                SignalingMessage::GameMove { .. } => panic!("a flagged move was relayed"),
+                _ => None,
+            })
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_move_time_limit_expires_without_a_move() {
+    let url = start_server().await;
+    let casual = TimeControl::per_move(1);
+
+    let mut white = Client::connect(&url, "glinski").await;
+    let mut black = Client::connect(&url, "glinski").await;
+    white.join("White", Some(casual)).await;
+    black.join("Black", None).await;
+
+    // Black lets the move limit run out without ever sending a move
+    let started = std::time::Instant::now();
+    white.play(HexCoord::new(0, -1), HexCoord::new(0, 0)).await;
+    black.receive_move().await;
+    for client in [&mut white, &mut black] {
+        client
+            .expect(|message| match message {
+                SignalingMessage::Timeout { room_id, .. } => Some(room_id),
+                _ => None,
+            })
+            .await;
+    }
+    assert!(started.elapsed() >= Duration::from_millis(1_000));
+
+    black
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (-1, 2), to: (-1, 1), promotion: None })
+        .await;
+    let refusal = black
+        .expect(|message| match message {
+            SignalingMessage::Error { code, .. } => Some(code),
+            SignalingMessage::GameMove { .. } => panic!("a move after the flag was relayed"),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, ErrorCode::InvalidMove);
+}
+
+#[tokio::test]
+async fn test_crazyhouse_drop_through_the_server() {
+    let url = start_server().await;
+    let mut white = Client::connect(&url, "glinski_crazyhouse").await;
+    let mut black = Client::connect(&url, "glinski_crazyhouse").await;
+    white.join("White", None).await;
+    black.join("Black", None).await;
+
+    // f5-f6, e8-e7, f6xe7
+    for (ply, (from, to)) in [((0, -1), (0, 0)), ((-1, 2), (-1, 1)), ((0, 0), (-1, 1))].into_iter().enumerate() {
+        let (from, to) = (HexCoord::new(from.0, from.1), HexCoord::new(to.0, to.1));
+        let (mover, watcher) = if ply % 2 == 0 { (&mut white, &mut black) } else { (&mut black, &mut white) };
+        mover.play(from, to).await;
+        watcher.receive_move().await;
+    }
+    // Black answers with any quiet move away from f6
+    let (from, to) = black
+        .game
+        .all_legal_moves()
+        .into_iter()
+        .find(|&(_, to)| !black.game.board.is_occupied(to) && to != HexCoord::new(0, 0))
+        .unwrap();
+    black.play(from, to).await;
+    white.receive_move().await;
+
+    // A piece White doesn't hold is refused; the captured pawn goes back in
+    white
+        .send(SignalingMessage::GameDrop { room_id: ROOM.to_string(), piece: PieceType::Queen, to: (0, 0) })
+        .await;
+    let refusal = white
+        .expect(|message| match message {
+            SignalingMessage::Error { code, message } => Some((code, message.unwrap_or_default())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, (ErrorCode::InvalidMove, "No Queen in reserve".to_string()));
+
+    white.game.drop_piece(PieceType::Pawn, HexCoord::new(0, 0)).unwrap();
+    white
+        .send(SignalingMessage::GameDrop { room_id: ROOM.to_string(), piece: PieceType::Pawn, to: (0, 0) })
+        .await;
+    black.receive_move().await;
+    assert_eq!(white.game.position_hash(), black.game.position_hash());
+    assert!(black.game.reserves.is_empty());
+}
+
+#[tokio::test]
+async fn test_fog_of_war_hides_the_opponent() {
+    let url = start_server().await;
+    let mut white = Client::connect(&url, "glinski_dark").await;
+    let mut black = Client::connect(&url, "glinski_dark").await;
+    white.join("White", None).await;
+    black.join("Black", None).await;
+    let fog_view = |message| match message {
+        SignalingMessage::FogView { view, .. } => Some(view),
+        _ => None,
+    };
+
+    // Each side starts out seeing its own army and none of the other's
+    for (client, color) in [(&mut white, Color::White), (&mut black, Color::Black)] {
+        let view = client.expect(fog_view).await;
+        assert_eq!(view.viewer, color);
+        assert!(view.pieces.iter().all(|(_, piece)| piece.color == color));
+    }
+
+    // Black learns what it can now see, never the move itself
+    white.play(HexCoord::new(0, -1), HexCoord::new(0, 0)).await;
+    let view = black
+        .expect(|message| match message {
+            SignalingMessage::GameMove { .. } => panic!("a move was relayed in the fog"),
+            message => fog_view(message),
+        })
+        .await;
+    assert_eq!(view.to_move, Color::Black);
+    assert!(view.pieces.iter().all(|&(cell, _)| view.sees(cell)));
+    assert!(view.pieces.len() < white.game.board.pieces.len());
+    let view = white.expect(fog_view).await;
+    assert!(view.sees(HexCoord::new(0, 0)) && !view.sees(HexCoord::new(0, -1)));
+
+    // Nor does checking a position hash hand out the whole game
+    black.check_position().await;
+    let refusal = black
+        .expect(|message| match message {
+            SignalingMessage::Error { code, message } => Some((code, message.unwrap_or_default())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, (ErrorCode::StateWithheld, "Fog-of-war rooms don't share game state".to_string()));
+}
+
+#[tokio::test]
+async fn test_diverged_client_is_resynced() {
+    let url = start_server().await;
+    let mut white = Client::connect(&url, "glinski").await;
+    let mut black = Client::connect(&url, "glinski").await;
+    white.join("White", None).await;
+    black.join("Black", None).await;
+
+    white.play(HexCoord::new(0, -1), HexCoord::new(0, 0)).await;
+    black.receive_move().await;
+
+    // White agrees with the server, so the next thing it hears is the refusal of a second move
+    white.check_position().await;
+    white
+        .send(SignalingMessage::GameMove { room_id: ROOM.to_string(), from: (1, -1), to: (1, 0), promotion: None })
+        .await;
+    let reply = white
+        .expect(|message| match message {
+            SignalingMessage::Resync { .. } => Some(None),
+            SignalingMessage::Error { code, .. } => Some(Some(code)),
+            _ => None,
+        })
+        .await;
+    assert_eq!(reply, Some(ErrorCode::NotYourTurn));
+
+    // Black's copy has lost a pawn the rules never took, and is sent the real game
+    black.game.board.remove_piece(HexCoord::new(-1, 2));
+    black.check_position().await;
+    let game = black
+        .expect(|message| match message {
+            SignalingMessage::Resync { game, .. } => Some(game),
+            _ => None,
+        })
+        .await;
+    black.game = game.replay().expect("refereed moves replay");
+    assert_eq!(black.game.position_hash(), white.game.position_hash());
+
+    // Play carries on from the resynced position
+    black.play(HexCoord::new(-1, 2), HexCoord::new(-1, 1)).await;
+    white.receive_move().await;
+    assert_eq!(white.game.position_hash(), black.game.position_hash());
+}
+
+#[tokio::test]
+async fn test_registered_variant_through_the_server() {
+    let pawns = VariantBuilder::new("Kings and Pawns")
+        .board(BoardType::Small { radius: 3 })
+        .place('d', 1, PieceType::King, Color::White)
+        .place('d', 2, PieceType::Pawn, Color::White)
+        .place('d', 7, PieceType::King, Color::Black)
+        .place('d', 6, PieceType::Pawn, Color::Black)
+        .build()
+        .unwrap();
+    let mut variants = VariantRegistry::new();
+    variants.register("kings_and_pawns", pawns.clone()).unwrap();
+    let url = start_server_with(AppState::with_variants(variants)).await;
+
+    let mut white = Client::connect_with(&url, "kings_and_pawns", pawns.clone()).await;
+    let mut black = Client::connect_with(&url, "kings_and_pawns", pawns).await;
+    white.join("White", None).await;
+    black.join("Black", None).await;
+
+    // d2-d3 is refereed by the registered rules and relayed
+    white.play(HexCoord::new(0, -2), HexCoord::new(0, -1)).await;
+    black.receive_move().await;
+    assert_eq!(white.game.position_hash(), black.game.position_hash());
+
+    // Variants nobody registered are still refused
+    let mut stranger = Client::connect(&url, "glinski").await;
+    stranger
+        .send(SignalingMessage::JoinRoom {
+            room_id: "elsewhere".to_string(),
+            variant: "kings_and_knights".to_string(),
+            player_name: "Stranger".to_string(),
+            time_control: None,
+            best_of: None,
+        })
+        .await;
+    let refusal = stranger
+        .expect(|message| match message {
+            SignalingMessage::Error { code, .. } => Some(code),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, ErrorCode::UnknownVariant);
+}
+
+#[tokio::test]
+async fn test_match_series_with_rematches() {
+    // White's king takes Black's knight, leaving too little to mate with
+    let quick_draw = VariantBuilder::new("Quick Draw")
+        .board(BoardType::Small { radius: 3 })
+        .place('d', 1, PieceType::King, Color::White)
+        .place('c', 2, PieceType::Knight, Color::White)
+        .place('d', 2, PieceType::Knight, Color::Black)
+        .place('d', 7, PieceType::King, Color::Black)
+        .draw_rules(DrawRules { insufficient_material: InsufficientMaterial::SingleMinorPiece, ..DrawRules::default() })
+        .build()
+        .unwrap();
+    let mut variants = VariantRegistry::new();
+    variants.register("quick_draw", quick_draw.clone()).unwrap();
+    let url = start_server_with(AppState::with_variants(variants)).await;
+
+    let mut first = Client::connect_with(&url, "quick_draw", quick_draw.clone()).await;
+    let mut second = Client::connect_with(&url, "quick_draw", quick_draw.clone()).await;
+    first
+        .send(SignalingMessage::JoinRoom {
+            room_id: ROOM.to_string(),
+            variant: "quick_draw".to_string(),
+            player_name: "First".to_string(),
+            time_control: None,
+            best_of: Some(2),
+        })
+        .await;
+    first.expect(|message| matches!(message, SignalingMessage::RoomInfo { .. }).then_some(())).await;
+    second.join("Second", None).await;
+
+    let series_update = |message| match message {
+        SignalingMessage::SeriesUpdate { series, players, decided, .. } => Some((series, players, decided)),
+        _ => None,
+    };
+    let d1_takes_d2 = (HexCoord::new(0, -3), HexCoord::new(0, -2));
+    let rematch = SignalingMessage::Rematch { room_id: ROOM.to_string() };
+
+    // A rematch waits for the game to end
+    first.send(rematch.clone()).await;
+    let refusal = first
+        .expect(|message| match message {
+            SignalingMessage::Error { code, .. } => Some(code),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, ErrorCode::GameInProgress);
+
+    first.play(d1_takes_d2.0, d1_takes_d2.1).await;
+    for client in [&mut first, &mut second] {
+        let (series, players, decided) = client.expect(series_update).await;
+        assert_eq!((series.scores, series.played, decided), ([0.5, 0.5], 1, false));
+        assert_eq!(players.len(), 2);
+    }
+
+    // Once both ask, the second game starts with the colours swapped
+    first.send(rematch.clone()).await;
+    second.expect(|message| matches!(message, SignalingMessage::Rematch { .. }).then_some(())).await;
+    second.send(rematch.clone()).await;
+    for client in [&mut first, &mut second] {
+        let (series, _, decided) = client.expect(series_update).await;
+        assert_eq!((series.played, series.white_player(), decided), (1, 1, false));
+        client.game = Game::new(quick_draw.clone());
+    }
+    second.play(d1_takes_d2.0, d1_takes_d2.1).await;
+    first.receive_move().await;
+
+    // Two draws of two settle the match level, with no more games to play
+    let (series, _, decided) = first.expect(series_update).await;
+    assert_eq!((series.scores, series.played, decided), ([1.0, 1.0], 2, true));
+    first.send(rematch).await;
+    let refusal = first
+        .expect(|message| match message {
+            SignalingMessage::Error { code, .. } => Some(code),
+            _ => None,
+        })
+        .await;
+    assert_eq!(refusal, ErrorCode::SeriesOver);
+}
+
+#[tokio::test]
+async fn test_promotion_through_the_server() {
+    // g3-g4 promotes White's pawn on the short g-file, and a queen there mates
+    let promotion_mate = VariantBuilder::new("Promotion Mate")
+        .board(BoardType::Small { radius: 3 })
+        .place('d', 5, PieceType::King, Color::White)
+        .place('e', 5, PieceType::Queen, Color::White)
+        .place('g', 3, PieceType::Pawn, Color::White)
+        .place('d', 7, PieceType::King, Color::Black)
+        .place('c', 3, PieceType::Queen, Color::Black)
+        .place('f', 4, PieceType::Pawn, Color::Black)
+        .build()
+        .unwrap();
+    let mut variants = VariantRegistry::new();
+    variants.register("promotion_mate", promotion_mate.clone()).unwrap();
+    let url = start_server_with(AppState::with_variants(variants)).await;
+
+    let mut white = Client::connect_with(&url, "promotion_mate", promotion_mate.clone()).await;
+    let mut black = Client::connect_with(&url, "promotion_mate", promotion_mate).await;
+    white.join("White", None).await;
+    black.join("Black", None).await;
+
+    let board = &white.game.board.board_type;
+    let (from, to) = (parse_square(board, "g3").unwrap(), parse_square(board, "g4").unwrap());
+
+    // Pawns only become pieces the variant has, and must name one
+    for promotion in [None, Some(PieceType::Knight), Some(PieceType::King)] {
+        white
+            .send(SignalingMessage::GameMove {
+                room_id: ROOM.to_string(),
+                from: (from.q, from.r),
+                to: (to.q, to.r),
+                promotion,
+            })
+            .await;
+        let refusal = white
+            .expect(|message| match message {
+                SignalingMessage::Error { code, .. } => Some(code),
+                _ => None,
+            })
+            .await;
+        assert_eq!(refusal, ErrorCode::InvalidMove, "{:?}", promotion);
+    }
+
+    white.promote(from, to, Some(PieceType::Queen)).await;
+    black.receive_move().await;
+    assert_eq!(black.game.board.get_piece(to).map(|piece| piece.piece_type), Some(PieceType::Queen));
+    assert_eq!(black.game.position_hash(), white.game.position_hash());
+
+    // Both hear the refereed result, which matches their own copies
+    for client in [&mut white, &mut black] {
+        let result = client
+            .expect(|message| match message {
+                SignalingMessage::SeriesUpdate { result, .. } => Some(result),
+                _ => None,
+            })
+            .await
+            .expect("a result once the game is over");
+        assert_eq!((result.white_score, result.black_score), (1.0, 0.0));
+        assert_eq!(Some(result), client.game.get_result());
+    }
+}
+
+#[test]
+fn test_protocol_schema_covers_the_messages() {
+    let schema = serde_json::to_value(protocol_schema()).unwrap();
+
+    // Each message is an object keyed by its kind
+    let kinds: Vec<&str> = schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|message| message["required"][0].as_str())
+        .collect();
+    for message in [
+        SignalingMessage::LeaveRoom { room_id: ROOM.to_string() },
+        SignalingMessage::PositionHash { room_id: ROOM.to_string(), ply: 0, hash: 0 },
+        SignalingMessage::Error { code: ErrorCode::RoomFull, message: None },
+    ] {
+        let json = serde_json::to_value(&message).unwrap();
+        let kind = json.as_object().unwrap().keys().next().unwrap().clone();
+        assert!(kinds.contains(&kind.as_str()), "{} is missing from the schema", kind);
+    }
+
+    // Error codes are spelled as sent
+    assert!(schema["definitions"]["ErrorCode"].to_string().contains("\"STATE_WITHHELD\""));
+    assert!(schema["definitions"]["TimeControl"]["properties"]["move_secs"].is_object());
+}
+
+#[test]
+fn test_room_filters() {
+    let variants = VariantRegistry::new();
+    let blitz = TimeControl { initial_secs: 300, increment_secs: 3, move_secs: None };
+    // Opened in this order, far enough apart to tell which is newest
+    let rooms: Vec<GameRoom> = [("b", "glinski", Some(blitz)), ("a", "glinski", None), ("c", "mini", Some(blitz))]
+        .into_iter()
+        .map(|(id, variant, time_control)| {
+            std::thread::sleep(Duration::from_millis(2));
+            GameRoom::new(id.to_string(), variant.to_string(), time_control, &variants).unwrap()
+        })
+        .collect();
+    let ids = |filter: RoomFilter| -> Vec<String> { filter.apply(&rooms).into_iter().map(|room| room.id.clone()).collect() };
+
+    assert_eq!(ids(RoomFilter::default()), ["a", "b", "c"]);
+    assert_eq!(ids(RoomFilter { sort: RoomSort::Newest, ..RoomFilter::default() }), ["c", "a", "b"]);
+    assert_eq!(ids(RoomFilter { variant: Some("glinski".to_string()), ..RoomFilter::default() }), ["a", "b"]);
+    assert_eq!(ids(RoomFilter { time_control: Some("5+3".to_string()), ..RoomFilter::default() }), ["b", "c"]);
+    assert_eq!(ids(RoomFilter { time_control: Some("none".to_string()), ..RoomFilter::default() }), ["a"]);
+}